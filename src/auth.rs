@@ -0,0 +1,410 @@
+//! 签名相关的工具，不依赖 [`Client`](crate::Client)，可用于给自行发送的请求签名
+
+use chrono::{DateTime, Utc};
+use http::{
+    header::{HeaderMap, HeaderValue, CONTENT_TYPE, DATE},
+    Method,
+};
+use url::Url;
+
+use crate::{
+    error::OssError,
+    types::{to_gmt, CanonicalizedResource, Key, Secret},
+};
+
+pub(crate) const CONTENT_MD5: &str = "content-md5";
+
+/// 存放 key, secret 以及 STS 临时凭证，用于计算 OSS 的签名
+#[derive(Debug, Clone)]
+pub struct Auth {
+    pub(crate) key: Key,
+    pub(crate) secret: Secret,
+    pub(crate) security_token: Option<String>,
+}
+
+impl Auth {
+    pub fn new(key: Key, secret: Secret) -> Self {
+        Auth {
+            key,
+            secret,
+            security_token: None,
+        }
+    }
+
+    pub fn new_with_sts(key: Key, secret: Secret, security_token: String) -> Self {
+        Auth {
+            key,
+            secret,
+            security_token: Some(security_token),
+        }
+    }
+
+    /// 计算签名，并返回需要携带的 header（包含传入的 `headers`）
+    ///
+    /// `headers` 中的 `Content-MD5`，`Content-Type` 以及 `x-oss-` 开头的 header 会参与签名
+    ///
+    /// `headers` 中已有 `Date`（GMT 格式，如 `Fri, 24 Feb 2012 06:07:48 GMT`）时会原样使用，
+    /// 而不是当前时间，可用于重放请求或使用外部时钟，格式不正确时返回 `InvalidDate` 错误
+    pub fn get_headers(
+        &self,
+        method: &Method,
+        resource: CanonicalizedResource,
+        headers: HeaderMap,
+    ) -> Result<HeaderMap, OssError> {
+        self.sign(method, resource, headers)
+            .map(|(header_map, _)| header_map)
+    }
+
+    /// 生成带签名的 url，可以在 `expires` 之前不携带任何 header 直接访问，如用于分享文件
+    ///
+    /// 使用 STS 时，`security-token` 会追加到 url 的参数中并参与签名
+    pub fn sign_url(
+        &self,
+        method: &Method,
+        mut url: Url,
+        resource: CanonicalizedResource,
+        expires: DateTime<Utc>,
+    ) -> Result<Url, OssError> {
+        const SECURITY_TOKEN: &str = "security-token";
+
+        let expires = expires.timestamp().to_string();
+        let resource = match &self.security_token {
+            Some(token) => {
+                let sep = if resource.as_str().contains('?') {
+                    '&'
+                } else {
+                    '?'
+                };
+                format!("{}{}{}={}", resource.as_str(), sep, SECURITY_TOKEN, token)
+            }
+            None => resource.as_str().to_owned(),
+        };
+
+        let string_to_sign = format!("{}\n\n\n{}\n{}", method.as_str(), expires, resource);
+        let signature = self
+            .secret
+            .encryption(string_to_sign.as_bytes())
+            .map_err(|_| OssError::InvalidSecret)?;
+
+        {
+            let mut query = url.query_pairs_mut();
+            query
+                .append_pair("OSSAccessKeyId", self.key.as_str())
+                .append_pair("Expires", &expires)
+                .append_pair("Signature", &signature);
+            if let Some(token) = &self.security_token {
+                query.append_pair(SECURITY_TOKEN, token);
+            }
+        }
+
+        Ok(url)
+    }
+
+    /// 生成带签名的 RTMP 推流地址，`resource` 为 `/{bucket}/{channel}`，`play_list` 为推流时
+    /// 生成的播放列表（m3u8）文件名，会作为 `playlistName` 参数参与签名
+    pub fn sign_rtmp_url(
+        &self,
+        mut url: Url,
+        resource: CanonicalizedResource,
+        play_list: Option<&str>,
+        expires: DateTime<Utc>,
+    ) -> Result<Url, OssError> {
+        const PLAYLIST_NAME: &str = "playlistName";
+        const SECURITY_TOKEN: &str = "security-token";
+
+        let expires = expires.timestamp().to_string();
+        // 参数按名称排序，每个参数为一行 `key:value`
+        let mut params = Vec::new();
+        if let Some(play_list) = play_list {
+            params.push((PLAYLIST_NAME, play_list));
+        }
+        if let Some(token) = &self.security_token {
+            params.push((SECURITY_TOKEN, token.as_str()));
+        }
+        let canonicalized_params: String = params
+            .iter()
+            .map(|(key, value)| format!("{}:{}\n", key, value))
+            .collect();
+
+        let string_to_sign = format!("{}\n{}{}", expires, canonicalized_params, resource.as_str());
+        let signature = self
+            .secret
+            .encryption(string_to_sign.as_bytes())
+            .map_err(|_| OssError::InvalidSecret)?;
+
+        {
+            let mut query = url.query_pairs_mut();
+            for (key, value) in params {
+                query.append_pair(key, value);
+            }
+            query
+                .append_pair("OSSAccessKeyId", self.key.as_str())
+                .append_pair("Expires", &expires)
+                .append_pair("Signature", &signature);
+        }
+
+        Ok(url)
+    }
+
+    /// 签名并返回签名后的 header，以及本地计算的待签名字符串
+    pub(crate) fn sign(
+        &self,
+        method: &Method,
+        resource: CanonicalizedResource,
+        mut headers: HeaderMap,
+    ) -> Result<(HeaderMap, String), OssError> {
+        const LINE_BREAK: &str = "\n";
+
+        let date = match headers.get(DATE) {
+            Some(date) => preset_date(date.to_str()?)?,
+            None => now(),
+        };
+        let content_type = match headers.get(CONTENT_TYPE) {
+            Some(content_type) => content_type.to_str()?.to_owned(),
+            None => "text/xml".to_owned(),
+        };
+
+        if let Some(sts_token) = &self.security_token {
+            headers.insert("x-oss-security-token", {
+                let mut token: HeaderValue = sts_token.try_into()?;
+                token.set_sensitive(true);
+                token
+            });
+        }
+
+        let oss_header_str = to_oss_header(&headers);
+        let content_md5 = match headers.get(CONTENT_MD5) {
+            Some(md5) => md5.to_str()?.to_owned(),
+            None => String::new(),
+        };
+
+        let string_to_sign = {
+            let mut string = method.as_str().to_owned();
+            string += LINE_BREAK;
+            string += &content_md5;
+            string += LINE_BREAK;
+            string += &content_type;
+            string += LINE_BREAK;
+            string += date.as_str();
+            string += LINE_BREAK;
+            string += &oss_header_str;
+            string += resource.as_str();
+            string
+        };
+
+        let sign = {
+            let encry = self.secret.encryption(string_to_sign.as_bytes()).unwrap();
+
+            format!("OSS {}:{}", self.key.as_str(), encry)
+        };
+        let header_map = {
+            headers.insert("AccessKeyId", self.key.as_str().try_into()?);
+            headers.insert("VERB", method.as_str().try_into()?);
+            headers.insert("Date", date.try_into()?);
+            headers.insert("Authorization", {
+                let mut token: HeaderValue = sign.try_into()?;
+                token.set_sensitive(true);
+                token
+            });
+            headers.insert(CONTENT_TYPE, content_type.try_into()?);
+            headers.insert("CanonicalizedResource", resource.as_str().try_into()?);
+
+            headers
+        };
+
+        Ok((header_map, string_to_sign))
+    }
+}
+
+fn now() -> String {
+    to_gmt(&Utc::now())
+}
+
+/// 调用方提供的 `Date`，必须是 GMT 格式
+fn preset_date(date: &str) -> Result<String, OssError> {
+    match DateTime::parse_from_rfc2822(date) {
+        Ok(_) if date.ends_with(" GMT") => Ok(date.to_owned()),
+        _ => Err(OssError::InvalidDate(date.into())),
+    }
+}
+
+pub(crate) fn to_oss_header(headers: &HeaderMap) -> String {
+    const X_OSS_PRE: &str = "x-oss-";
+    const LINE_BREAK: &str = "\n";
+    let mut header: Vec<_> = headers
+        .iter()
+        .filter(|(k, _v)| k.as_str().starts_with(X_OSS_PRE))
+        .collect();
+    if header.is_empty() {
+        return String::new();
+    }
+
+    header.sort_by(|(k1, _), (k2, _)| k1.as_str().cmp(k2.as_str()));
+
+    let header_vec: Vec<_> = header
+        .iter()
+        .filter_map(|(k, v)| {
+            v.to_str()
+                .ok()
+                .map(|value| k.as_str().to_owned() + ":" + value)
+        })
+        .collect();
+
+    let mut str = header_vec.join(LINE_BREAK);
+    str += LINE_BREAK;
+    str
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use http::{header::HeaderMap, Method};
+    use url::Url;
+
+    use super::Auth;
+    use crate::types::{CanonicalizedResource, Key, Secret};
+
+    #[test]
+    fn test_preset_date() {
+        let auth = Auth::new(Key::new("foo"), Secret::new("bar"));
+        let date = "Fri, 24 Feb 2012 06:07:48 GMT";
+        let mut headers = HeaderMap::new();
+        headers.insert("date", date.parse().unwrap());
+
+        let resource = || CanonicalizedResource::new("/bucket/abc.txt".to_owned());
+        let (first, string) = auth
+            .sign(&Method::GET, resource(), headers.clone())
+            .unwrap();
+        assert_eq!(string, format!("GET\n\ntext/xml\n{date}\n/bucket/abc.txt"));
+        assert_eq!(first.get("date").unwrap(), date);
+
+        let (second, _) = auth
+            .sign(&Method::GET, resource(), headers.clone())
+            .unwrap();
+        assert_eq!(
+            first.get("Authorization").unwrap(),
+            second.get("Authorization").unwrap()
+        );
+
+        headers.insert("date", "2012-02-24 06:07:48".parse().unwrap());
+        assert!(matches!(
+            auth.sign(&Method::GET, resource(), headers),
+            Err(crate::Error::InvalidDate(d)) if &*d == "2012-02-24 06:07:48"
+        ));
+    }
+
+    #[test]
+    fn test_get_headers() {
+        let auth = Auth::new(Key::new("foo"), Secret::new("bar"));
+        let mut headers = HeaderMap::new();
+        headers.insert("x-oss-meta-a", "1".parse().unwrap());
+
+        let res = auth
+            .get_headers(
+                &Method::GET,
+                CanonicalizedResource::new("/bucket/abc.txt".to_owned()),
+                headers,
+            )
+            .unwrap();
+        assert_eq!(res.get("x-oss-meta-a").unwrap(), "1");
+
+        let date = res.get("Date").unwrap().to_str().unwrap();
+        let string = format!("GET\n\ntext/xml\n{date}\nx-oss-meta-a:1\n/bucket/abc.txt");
+        let sign = Secret::new("bar").encryption(string.as_bytes()).unwrap();
+        assert_eq!(
+            res.get("Authorization").unwrap(),
+            &format!("OSS foo:{sign}")
+        );
+    }
+
+    /// 只开启 `signing-only` 时，不需要 Client 也可以给自行发送的请求签名
+    #[cfg(feature = "signing-only")]
+    #[test]
+    fn test_signing_only() {
+        let auth = Auth::new_with_sts(Key::new("foo"), Secret::new("bar"), "tk".to_owned());
+        let mut headers = HeaderMap::new();
+        headers.insert("date", "Fri, 24 Feb 2012 06:07:48 GMT".parse().unwrap());
+        headers.insert("content-type", "text/plain".parse().unwrap());
+
+        let res = auth
+            .get_headers(
+                &Method::PUT,
+                CanonicalizedResource::new("/bucket/abc.txt".to_owned()),
+                headers,
+            )
+            .unwrap();
+        let sign = Secret::new("bar")
+            .encryption(
+                b"PUT\n\ntext/plain\nFri, 24 Feb 2012 06:07:48 GMT\n\
+                x-oss-security-token:tk\n/bucket/abc.txt",
+            )
+            .unwrap();
+        assert_eq!(
+            res.get("Authorization").unwrap(),
+            &format!("OSS foo:{sign}")
+        );
+        assert_eq!(res.get("x-oss-security-token").unwrap(), "tk");
+    }
+
+    #[test]
+    fn test_sign_url() {
+        let expires = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+        let url = Url::parse("https://bucket.oss-cn-qingdao.aliyuncs.com/abc.txt").unwrap();
+        let resource = CanonicalizedResource::new("/bucket/abc.txt".to_owned());
+
+        let auth = Auth::new(Key::new("foo"), Secret::new("bar"));
+        let res = auth
+            .sign_url(&Method::GET, url.clone(), resource.clone(), expires)
+            .unwrap();
+        let sign = Secret::new("bar")
+            .encryption(b"GET\n\n\n1704110400\n/bucket/abc.txt")
+            .unwrap();
+        let expected: Vec<(String, String)> = vec![
+            ("OSSAccessKeyId".into(), "foo".into()),
+            ("Expires".into(), "1704110400".into()),
+            ("Signature".into(), sign),
+        ];
+        assert_eq!(res.query_pairs().into_owned().collect::<Vec<_>>(), expected);
+
+        let auth = Auth::new_with_sts(Key::new("foo"), Secret::new("bar"), "tk".to_owned());
+        let res = auth.sign_url(&Method::GET, url, resource, expires).unwrap();
+        let sign = Secret::new("bar")
+            .encryption(b"GET\n\n\n1704110400\n/bucket/abc.txt?security-token=tk")
+            .unwrap();
+        let pairs: Vec<_> = res.query_pairs().into_owned().collect();
+        assert_eq!(pairs[2], ("Signature".to_owned(), sign));
+        assert_eq!(pairs[3], ("security-token".to_owned(), "tk".to_owned()));
+    }
+
+    #[test]
+    fn test_sign_rtmp_url() {
+        let expires = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+        let url = Url::parse("rtmp://bucket.oss-cn-qingdao.aliyuncs.com/live/channel").unwrap();
+        let resource = CanonicalizedResource::new("/bucket/channel".to_owned());
+
+        let auth = Auth::new(Key::new("foo"), Secret::new("bar"));
+        let res = auth
+            .sign_rtmp_url(url.clone(), resource.clone(), Some("play.m3u8"), expires)
+            .unwrap();
+        let sign = Secret::new("bar")
+            .encryption(b"1704110400\nplaylistName:play.m3u8\n/bucket/channel")
+            .unwrap();
+        let expected: Vec<(String, String)> = vec![
+            ("playlistName".into(), "play.m3u8".into()),
+            ("OSSAccessKeyId".into(), "foo".into()),
+            ("Expires".into(), "1704110400".into()),
+            ("Signature".into(), sign),
+        ];
+        assert_eq!(res.query_pairs().into_owned().collect::<Vec<_>>(), expected);
+        assert_eq!(res.scheme(), "rtmp");
+
+        let auth = Auth::new_with_sts(Key::new("foo"), Secret::new("bar"), "tk".to_owned());
+        let res = auth.sign_rtmp_url(url, resource, None, expires).unwrap();
+        let sign = Secret::new("bar")
+            .encryption(b"1704110400\nsecurity-token:tk\n/bucket/channel")
+            .unwrap();
+        let pairs: Vec<_> = res.query_pairs().into_owned().collect();
+        assert_eq!(pairs[0], ("security-token".to_owned(), "tk".to_owned()));
+        assert_eq!(pairs[3], ("Signature".to_owned(), sign));
+    }
+}
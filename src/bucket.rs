@@ -1,46 +1,433 @@
-use std::str::FromStr;
+use std::{future::Future, str::FromStr};
 
 use chrono::{DateTime, Utc};
-use reqwest::Method;
+use futures_util::{stream, Stream, TryStreamExt};
+use percent_encoding::percent_decode_str;
+use reqwest::{header::HeaderMap, Method, Response};
 use serde::{de::DeserializeOwned, Deserialize};
 use serde_xml_rs::from_str;
 use url::Url;
 
 use crate::{
+    auth::CONTENT_MD5,
     client::Client,
+    cname::{cname_xml, CnameInfo, CnameToken},
     error::OssError,
-    object::{Object, Objects},
-    types::{CanonicalizedResource, EndPoint, ObjectQuery, StorageClass},
+    inventory::InventoryConfiguration,
+    lifecycle::LifecycleConfiguration,
+    multipart::MultipartUpload,
+    object::{
+        DeleteMarker, Object, ObjectInfo, ObjectInfos, ObjectVersion, ObjectVersions, Objects,
+        VersionEntry,
+    },
+    style::{style_xml, ImageStyle},
+    types::{
+        encode_query, CanonicalizedResource, ContentMd5, EndPoint, Network, ObjectQuery, Owner,
+        StorageClass,
+    },
+    worm::WormConfiguration,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Bucket {
     name: String,
     endpoint: EndPoint,
+    /// 绑定的自定义域名，设置后使用该域名访问（TLS 的 SNI 也使用该域名）
+    custom_domain: Option<Box<str>>,
+    /// 与访问的域名不同的 `Host` header
+    host_header: Option<Box<str>>,
+    /// 文件相关的请求使用的传输加速域名
+    acceleration: Option<Network>,
+}
+
+/// 使用字面量创建 [`Bucket`]，bucket 名称会在编译期校验，不合法时编译失败
+/// ```
+/// # use aliyun_oss_client::{bucket, EndPoint};
+/// let bucket = bucket!("foo-123", EndPoint::CN_QINGDAO);
+/// ```
+/// ```compile_fail
+/// # use aliyun_oss_client::{bucket, EndPoint};
+/// let bucket = bucket!("Foo_123", EndPoint::CN_QINGDAO);
+/// ```
+#[macro_export]
+macro_rules! bucket {
+    ($name:literal, $endpoint:expr) => {{
+        const _: () = assert!($crate::Bucket::is_valid_name($name), "invalid bucket name");
+        $crate::Bucket::new($name, $endpoint)
+    }};
 }
 
 type NextContinuationToken = Option<String>;
 
+const POLICY: &str = "policy";
+const ACL: &str = "acl";
+const VERSIONS: &str = "versions";
+const DELETE: &str = "delete";
+const REQUEST_PAYMENT: &str = "requestPayment";
+const LIFECYCLE: &str = "lifecycle";
+const INVENTORY: &str = "inventory";
+const WORM: &str = "worm";
+const TRANSFER_ACCELERATION: &str = "transferAcceleration";
+const CNAME: &str = "cname";
+const STYLE: &str = "style";
+const UPLOADS: &str = "uploads";
+const ACCESS_MONITOR: &str = "accessmonitor";
+
+/// 列举文件结果中的一页
+trait Page {
+    type Item;
+
+    /// 获取下一页时使用的 `continuation-token`，没有下一页时为 `None`
+    fn page_token(&self) -> Option<&str>;
+
+    fn page_len(&self) -> usize;
+
+    fn into_items(self) -> Vec<Self::Item>;
+}
+
+impl Page for Objects {
+    type Item = Object;
+
+    fn page_token(&self) -> Option<&str> {
+        self.next_token().map(String::as_str)
+    }
+
+    fn page_len(&self) -> usize {
+        self.len()
+    }
+
+    fn into_items(self) -> Vec<Object> {
+        self.into_vec()
+    }
+}
+
+impl Page for ObjectInfos {
+    type Item = (Object, ObjectInfo);
+
+    fn page_token(&self) -> Option<&str> {
+        self.next_token()
+    }
+
+    fn page_len(&self) -> usize {
+        self.len()
+    }
+
+    fn into_items(self) -> Vec<(Object, ObjectInfo)> {
+        self.into_vec()
+    }
+}
+
+/// 列举文件时，每页最多返回的文件数量
+const MAX_LIST_KEYS: usize = 1000;
+
+/// 使用 `fetch` 逐页获取列举结果并交给 `visit` 处理，直到取得 `n` 个文件或没有下一页，
+/// 最后一页的 `max-keys` 会减小到剩余的数量，返回继续列举时使用的 `continuation-token`
+async fn for_each_page<P, F, Fut, V>(
+    query: &ObjectQuery,
+    n: usize,
+    mut fetch: F,
+    mut visit: V,
+) -> Result<Option<String>, OssError>
+where
+    P: Page,
+    F: FnMut(ObjectQuery) -> Fut,
+    Fut: Future<Output = Result<P, OssError>>,
+    V: FnMut(P),
+{
+    let page_size = query
+        .get(ObjectQuery::MAX_KEYS)
+        .and_then(|max| max.parse().ok())
+        .unwrap_or(MAX_LIST_KEYS);
+    let mut query = query.clone();
+    let mut token = query.get(ObjectQuery::CONTINUATION_TOKEN).cloned();
+    let mut taken = 0;
+    while taken < n {
+        query.insert(ObjectQuery::MAX_KEYS, page_size.min(n - taken).to_string());
+        let page = fetch(query.clone()).await?;
+        token = page.page_token().map(|t| t.to_owned());
+        taken += page.page_len();
+        visit(page);
+        match &token {
+            Some(token) => query.insert_next_token(token.clone()),
+            None => break,
+        };
+    }
+
+    Ok(token)
+}
+
+/// 使用 `fetch` 逐页获取列举结果，累加每一页的文件大小
+async fn sum_pages<F, Fut>(query: &ObjectQuery, fetch: F) -> Result<u64, OssError>
+where
+    F: FnMut(ObjectQuery) -> Fut,
+    Fut: Future<Output = Result<ObjectInfos, OssError>>,
+{
+    let mut total = 0;
+    for_each_page(query, usize::MAX, fetch, |page: ObjectInfos| {
+        total += page.total_size()
+    })
+    .await?;
+
+    Ok(total)
+}
+
+/// 使用 `fetch` 逐页获取列举结果，直到取得 `n` 个文件或没有下一页，返回文件列表和继续列举时
+/// 使用的 `continuation-token`，参见 [`for_each_page`]
+async fn take_pages<P, F, Fut>(
+    query: &ObjectQuery,
+    n: usize,
+    fetch: F,
+) -> Result<(Vec<P::Item>, Option<String>), OssError>
+where
+    P: Page,
+    F: FnMut(ObjectQuery) -> Fut,
+    Fut: Future<Output = Result<P, OssError>>,
+{
+    let mut list = Vec::new();
+    let token = for_each_page(query, n, fetch, |page| list.extend(page.into_items())).await?;
+
+    Ok((list, token))
+}
+
+/// 批量删除时，每次请求最多包含的文件数量
+const MAX_DELETE_OBJECTS: usize = 1000;
+
 impl Bucket {
     pub fn new<N: Into<String>>(name: N, endpoint: EndPoint) -> Bucket {
         Bucket {
             name: name.into(),
             endpoint,
+            custom_domain: None,
+            host_header: None,
+            acceleration: None,
+        }
+    }
+
+    /// 初始化 bucket，并校验 bucket 名称是否符合 OSS 的命名规范
+    /// ```
+    /// # use aliyun_oss_client::{Bucket, EndPoint, Error};
+    /// assert!(Bucket::try_new("foo-123", EndPoint::CN_QINGDAO).is_ok());
+    ///
+    /// assert!(matches!(
+    ///     Bucket::try_new("ab", EndPoint::CN_QINGDAO),
+    ///     Err(Error::InvalidBucketNameLength)
+    /// ));
+    /// assert!(matches!(
+    ///     Bucket::try_new("a".repeat(64), EndPoint::CN_QINGDAO),
+    ///     Err(Error::InvalidBucketNameLength)
+    /// ));
+    /// assert!(matches!(
+    ///     Bucket::try_new("1.2.3.4", EndPoint::CN_QINGDAO),
+    ///     Err(Error::InvalidBucketNameIp)
+    /// ));
+    /// assert!(matches!(
+    ///     Bucket::try_new("-foo", EndPoint::CN_QINGDAO),
+    ///     Err(Error::InvalidBucketName)
+    /// ));
+    /// assert!(matches!(
+    ///     Bucket::try_new("Foo", EndPoint::CN_QINGDAO),
+    ///     Err(Error::InvalidBucketName)
+    /// ));
+    /// ```
+    pub fn try_new<N: Into<String>>(name: N, endpoint: EndPoint) -> Result<Bucket, OssError> {
+        let name = name.into();
+        Self::valid_name(&name)?;
+
+        Ok(Bucket::new(name, endpoint))
+    }
+
+    /// 校验 bucket 名称：
+    /// - 长度为 3~63 个字符
+    /// - 不能是 IP 地址的形式
+    /// - 只能包含小写字母，数字和短横线，且必须以小写字母或数字开头和结尾
+    pub fn valid_name(name: &str) -> Result<(), OssError> {
+        const MIN_LEN: usize = 3;
+        const MAX_LEN: usize = 63;
+
+        if name.len() < MIN_LEN || name.len() > MAX_LEN {
+            return Err(OssError::InvalidBucketNameLength);
+        }
+
+        let parts: Vec<&str> = name.split('.').collect();
+        if parts.len() == 4 && parts.iter().all(|p| p.parse::<u8>().is_ok()) {
+            return Err(OssError::InvalidBucketNameIp);
+        }
+
+        if name.starts_with('-')
+            || name.ends_with('-')
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        {
+            return Err(OssError::InvalidBucketName);
+        }
+
+        Ok(())
+    }
+
+    /// 与 [`valid_name`](Self::valid_name) 的规则相同，可以在常量上下文中使用，
+    /// 配合 [`bucket!`](crate::bucket!) 宏在编译期校验 bucket 名称
+    /// ```
+    /// # use aliyun_oss_client::Bucket;
+    /// const VALID: bool = Bucket::is_valid_name("foo-123");
+    /// assert!(VALID);
+    /// assert!(!Bucket::is_valid_name("Foo"));
+    /// assert!(!Bucket::is_valid_name("1.2.3.4"));
+    /// ```
+    pub const fn is_valid_name(name: &str) -> bool {
+        let bytes = name.as_bytes();
+        if bytes.len() < 3 || bytes.len() > 63 {
+            return false;
+        }
+        if bytes[0] == b'-' || bytes[bytes.len() - 1] == b'-' {
+            return false;
+        }
+
+        let mut i = 0;
+        while i < bytes.len() {
+            let c = bytes[i];
+            if !(c.is_ascii_lowercase() || c.is_ascii_digit() || c == b'-') {
+                return false;
+            }
+            i += 1;
         }
+
+        true
     }
 
     pub fn from_env() -> Result<Bucket, OssError> {
         let name = std::env::var("ALIYUN_BUCKET").map_err(|_| OssError::InvalidBucket)?;
+        Self::valid_name(&name)?;
 
         let endpoint = EndPoint::from_env()?;
 
-        Ok(Bucket { name, endpoint })
+        Ok(Bucket::new(name, endpoint))
+    }
+
+    /// 从 bucket 的 url 中解析 bucket 名称和 endpoint，如
+    /// `https://bucket.oss-cn-hangzhou-internal.aliyuncs.com/path/a.txt`，url 中的路径会被忽略，
+    /// endpoint 的解析规则与 [`EndPoint::from_url`] 一致
+    pub fn from_url(url: &Url) -> Result<Bucket, OssError> {
+        let host = url.host_str().ok_or(OssError::InvalidBucket)?;
+        let (name, _) = EndPoint::split_bucket_host(host).ok_or(OssError::InvalidBucket)?;
+        Self::valid_name(name)?;
+
+        Ok(Bucket::new(name, EndPoint::from_url(url)?))
     }
 
     pub(crate) fn as_str(&self) -> &str {
         &self.name
     }
 
+    /// bucket 名称
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// bucket 使用的 endpoint
+    pub fn endpoint(&self) -> &EndPoint {
+        &self.endpoint
+    }
+
+    /// 使用绑定到 bucket 的自定义域名访问，如 `static.example.com`，生成的链接及 TLS
+    /// 校验的域名都是该自定义域名，签名时仍使用 bucket 名称
+    /// ```
+    /// # use aliyun_oss_client::{Bucket, EndPoint};
+    /// let bucket = Bucket::new("foo", EndPoint::CN_QINGDAO).with_custom_domain("static.example.com");
+    /// assert_eq!(bucket.to_url().host_str(), Some("static.example.com"));
+    /// ```
+    pub fn with_custom_domain<D: Into<String>>(mut self, domain: D) -> Bucket {
+        let domain: String = domain.into();
+        self.custom_domain = Some(
+            domain
+                .trim_start_matches("https://")
+                .trim_start_matches("http://")
+                .trim_end_matches('/')
+                .into(),
+        );
+        self
+    }
+
+    pub fn custom_domain(&self) -> Option<&str> {
+        self.custom_domain.as_deref()
+    }
+
+    /// 发送请求时使用与链接域名不同的 `Host` header，例如通过代理或 IP 访问自定义域名时，
+    /// TLS 的 SNI 使用链接中的域名，`Host` 使用该值
+    pub fn with_host_header<H: Into<String>>(mut self, host: H) -> Bucket {
+        self.host_header = Some(host.into().into());
+        self
+    }
+
+    pub fn host_header(&self) -> Option<&str> {
+        self.host_header.as_deref()
+    }
+
+    /// 请求的是该 bucket 的链接时，返回需要覆盖的 `Host` header
+    pub(crate) fn host_header_for(&self, url: &Url) -> Option<&str> {
+        let host = self.host_header()?;
+        if url.host_str() == self.to_url().host_str()
+            || url.host_str() == self.to_data_url().host_str()
+        {
+            Some(host)
+        } else {
+            None
+        }
+    }
+
+    /// 开启传输加速后，上传，下载等文件相关的请求使用全球传输加速域名
+    /// `oss-accelerate.aliyuncs.com`，bucket 相关的请求仍使用原来的 endpoint，签名不受影响
+    ///
+    /// 需要先在 bucket 上开启传输加速，设置了自定义域名时以自定义域名为准
+    /// ```
+    /// # use aliyun_oss_client::{Bucket, EndPoint, Object};
+    /// let bucket = Bucket::new("foo", EndPoint::CN_QINGDAO).with_transfer_acceleration(true);
+    /// assert_eq!(
+    ///     Object::new("a.txt").to_url(&bucket).as_str(),
+    ///     "https://foo.oss-accelerate.aliyuncs.com/a.txt"
+    /// );
+    /// assert_eq!(bucket.to_url().as_str(), "https://foo.oss-cn-qingdao.aliyuncs.com/");
+    /// ```
+    pub fn with_transfer_acceleration(mut self, enable: bool) -> Bucket {
+        self.acceleration = enable.then_some(Network::Accelerate);
+        self
+    }
+
+    /// 与 [`with_transfer_acceleration`](Self::with_transfer_acceleration) 相同，使用非中国内地
+    /// 传输加速域名 `oss-accelerate-overseas.aliyuncs.com`
+    pub fn with_transfer_acceleration_overseas(mut self, enable: bool) -> Bucket {
+        self.acceleration = enable.then_some(Network::AccelerateOverseas);
+        self
+    }
+
+    /// 是否开启了传输加速
+    pub fn transfer_acceleration(&self) -> bool {
+        self.acceleration.is_some()
+    }
+
+    /// 文件相关的请求使用的链接地址，开启传输加速时使用传输加速域名
+    pub(crate) fn to_data_url(&self) -> Url {
+        match self.acceleration {
+            Some(network) if self.custom_domain.is_none() => {
+                let mut endpoint = self.endpoint.clone();
+                endpoint.set_network(network);
+                let url = format!("https://{}.{}", self.name, endpoint.to_host());
+                Url::parse(&url).unwrap_or_else(|_| panic!("covert to url failed, bucket: {}", url))
+            }
+            _ => self.to_url(),
+        }
+    }
+
+    /// 使用外网 endpoint 的 bucket
+    pub(crate) fn to_public(&self) -> Bucket {
+        self.clone().with_internal(false)
+    }
+
+    pub(crate) fn with_internal(mut self, internal: bool) -> Bucket {
+        self.endpoint.set_internal(internal);
+        self
+    }
+
     /// # 返回 bucket 对应的链接地址
     /// 可以是内网地址，默认为外网地址
     /// ```
@@ -55,7 +442,10 @@ impl Bucket {
     /// assert_eq!(bucket_internal.to_url(), Url::parse("https://bar.oss-cn-qingdao-internal.aliyuncs.com").unwrap());
     /// ```
     pub fn to_url(&self) -> Url {
-        let url = format!("https://{}.{}", self.name.as_str(), self.endpoint.host());
+        let url = match &self.custom_domain {
+            Some(domain) => format!("https://{}", domain),
+            None => format!("https://{}.{}", self.name.as_str(), self.endpoint.to_host()),
+        };
 
         Url::parse(&url).unwrap_or_else(|_| panic!("covert to url failed, bucket: {}", url))
     }
@@ -109,35 +499,13 @@ impl Bucket {
 
         let mut url = self.to_url();
         url.set_query(Some(BUCKET_INFO));
-        let method = Method::GET;
         let resource = CanonicalizedResource::from_bucket_info(self);
 
-        let header_map = client.authorization(&method, resource)?;
-
-        let response = reqwest::Client::new()
-            .get(url)
-            .headers(header_map)
-            .send()
-            .await?;
-
-        let is_success = response.status().is_success();
-
-        let content = response.text().await?;
-
-        if !is_success {
-            return Err(OssError::from_service(&content));
-        }
+        let content = client.get_xml(url, resource).await?;
 
         //println!("{}", content);
 
-        #[derive(Debug, Deserialize)]
-        struct BucketInfo<T> {
-            #[serde(rename = "Bucket")]
-            bucket: T,
-        }
-        let res: BucketInfo<B> = from_str(&content)?;
-
-        Ok(res.bucket)
+        parse_bucket_xml(&content)
     }
 
     pub async fn get_info(&self, client: &Client) -> Result<BucketInfo, OssError> {
@@ -145,24 +513,9 @@ impl Bucket {
 
         let mut url = self.to_url();
         url.set_query(Some(BUCKET_INFO));
-        let method = Method::GET;
         let resource = CanonicalizedResource::from_bucket_info(self);
 
-        let header_map = client.authorization(&method, resource)?;
-
-        let response = reqwest::Client::new()
-            .get(url)
-            .headers(header_map)
-            .send()
-            .await?;
-
-        let is_success = response.status().is_success();
-
-        let content = response.text().await?;
-
-        if !is_success {
-            return Err(OssError::from_service(&content));
-        }
+        let content = client.get_xml(url, resource).await?;
 
         //println!("{content}");
         Self::parse_info_xml(content)
@@ -180,11 +533,14 @@ impl Bucket {
             .ok_or(OssError::NoFoundDataRedundancyType)?;
         let data_redundancy_type = DataRedundancyType::from_str(data_redundancy_type)
             .map_err(|_| OssError::NoFoundDataRedundancyType)?;
+        let access_monitor = Self::parse_item(&xml, "AccessMonitor")
+            .and_then(|status| parse_access_monitor_status(status).ok());
 
         Ok(BucketInfo {
             creation_date,
             storage_class,
             data_redundancy_type,
+            access_monitor,
         })
     }
 
@@ -240,39 +596,9 @@ impl Bucket {
         query: &ObjectQuery,
         client: &Client,
     ) -> Result<(Vec<Obj>, NextContinuationToken), OssError> {
-        let mut url = self.to_url();
-        url.set_query(Some(&query.to_oss_query()));
-        let method = Method::GET;
-        let resource = CanonicalizedResource::from_object_list(self, query.get_next_token());
+        let content = self.list_xml(query, client).await?;
 
-        let header_map = client.authorization(&method, resource)?;
-
-        let response = reqwest::Client::new()
-            .get(url)
-            .headers(header_map)
-            .send()
-            .await?;
-
-        let is_success = response.status().is_success();
-
-        let content = response.text().await?;
-
-        if !is_success {
-            return Err(OssError::from_service(&content));
-        }
-
-        //println!("{content}");
-
-        #[derive(Debug, Deserialize)]
-        struct ListBucketResult<T> {
-            #[serde(rename = "Contents")]
-            contents: Vec<T>,
-            #[serde(rename = "NextContinuationToken")]
-            next_token: Option<String>,
-        }
-        let res: ListBucketResult<Obj> = from_str(&content)?;
-
-        Ok((res.contents, res.next_token))
+        parse_list_xml(&content)
     }
 
     pub async fn get_objects(
@@ -280,140 +606,2668 @@ impl Bucket {
         query: &ObjectQuery,
         client: &Client,
     ) -> Result<Objects, OssError> {
-        let mut url = self.to_url();
-        url.set_query(Some(&query.to_oss_query()));
-        let method = Method::GET;
-        let resource = CanonicalizedResource::from_object_list(self, query.get_next_token());
+        let content = self.list_xml(query, client).await?;
 
-        let header_map = client.authorization(&method, resource)?;
+        Ok(Self::parse_objects_xml(&content)?.with_search(self.clone(), query.clone()))
+    }
 
-        let response = reqwest::Client::new()
-            .get(url)
-            .headers(header_map)
-            .send()
-            .await?;
+    fn parse_objects_xml(xml: &str) -> Result<Objects, OssError> {
+        let url_encoded = is_url_encoded(xml);
+        let list = Self::parse_xml_objects(xml)?
+            .into_iter()
+            .map(|obj| Object::new(decode_key(obj.get_path(), url_encoded)))
+            .collect();
+
+        let token = Self::parse_next_token(xml, url_encoded);
+
+        let common_prefixes = Self::parse_blocks(xml, "CommonPrefixes")
+            .into_iter()
+            .filter_map(|block| Self::parse_item(block, "Prefix"))
+            .map(|p| decode_key(p, url_encoded))
+            .collect();
+        let delimiter = Self::parse_item(xml, "Delimiter").map(|d| decode_key(d, url_encoded));
+
+        Ok(Objects::new(list, token)
+            .with_common_prefixes(common_prefixes)
+            .with_delimiter(delimiter))
+    }
 
-        let is_success = response.status().is_success();
+    /// 通过列举文件接口获取单个文件的信息（包括存储类型），文件不存在时返回 `None`
+    ///
+    /// 使用文件路径作为 `prefix` 查询，只返回路径完全一致的文件
+    pub async fn get_object_info(
+        &self,
+        object: &Object,
+        client: &Client,
+    ) -> Result<Option<ObjectInfo>, OssError> {
+        let mut query = ObjectQuery::new();
+        query.insert(ObjectQuery::PREFIX, object.get_path());
+        query.insert(ObjectQuery::MAX_KEYS, "1");
 
-        let content = response.text().await?;
+        let content = self.list_xml(&query, client).await?;
 
-        if !is_success {
-            return Err(OssError::from_service(&content));
-        }
+        Self::parse_object_info_xml(&content, object.get_path())
+    }
 
-        //println!("{content}");
+    /// 列举文件，同时返回每个文件的详细信息（修改时间，etag，大小，存储类型），以及用于翻页的
+    /// `NextContinuationToken`，不需要像 [`export_objects`](Self::export_objects) 那样自定义类型
+    pub async fn list_objects(
+        &self,
+        query: &ObjectQuery,
+        client: &Client,
+    ) -> Result<ObjectInfos, OssError> {
+        let content = self.list_xml(query, client).await?;
 
-        let list = Self::parse_xml_objects(&content)?;
+        Self::parse_object_infos_xml(&content)
+    }
 
-        let token = Self::parse_item(&content, "NextContinuationToken").map(|t| t.to_owned());
+    /// 按 `query`（如 `prefix`）逐页列举所有文件，返回文件大小的总和，可用于统计目录占用的空间
+    pub async fn total_size_all(
+        &self,
+        query: &ObjectQuery,
+        client: &Client,
+    ) -> Result<u64, OssError> {
+        sum_pages(query, |query| async move {
+            self.list_objects(&query, client).await
+        })
+        .await
+    }
 
-        Ok(Objects::new(list, token))
+    /// 按 `query` 逐页列举，最多返回 `n` 个文件，以及继续列举时使用的 `continuation-token`，
+    /// 没有更多文件时 token 为 `None`
+    ///
+    /// `query` 中的 `max-keys` 作为每页的大小（默认 1000），最后一页只请求剩余的数量；
+    /// 将返回的 token 通过 [`ObjectQuery::insert_next_token`] 设置到 `query` 中即可获取后面的 `n` 个文件
+    pub async fn list_objects_take(
+        &self,
+        query: &ObjectQuery,
+        n: usize,
+        client: &Client,
+    ) -> Result<(Vec<(Object, ObjectInfo)>, Option<String>), OssError> {
+        take_pages(query, n, |query| async move {
+            self.list_objects(&query, client).await
+        })
+        .await
     }
 
-    pub(crate) fn parse_xml_objects(xml: &str) -> Result<Vec<Object>, OssError> {
-        let mut start_positions = vec![];
-        let mut end_positions = vec![];
-        let mut start = 0;
-        let mut pattern = "<Key>";
+    /// 与 [`list_objects`](Self::list_objects) 相同，同时返回每个文件的拥有者
+    ///
+    /// 会自动带上 `fetch-owner=true` 查询条件
+    pub async fn list_objects_with_owner(
+        &self,
+        query: &ObjectQuery,
+        client: &Client,
+    ) -> Result<ObjectInfos, OssError> {
+        let mut query = query.clone();
+        query.insert(ObjectQuery::FETCH_OWNER, "true");
 
-        while let Some(pos) = xml[start..].find(pattern) {
-            start_positions.push(start + pos);
-            start += pos + pattern.len();
-        }
-        start = 0;
-        pattern = "</Key>";
-        while let Some(pos) = xml[start..].find(pattern) {
-            end_positions.push(start + pos);
-            start += pos + pattern.len();
-        }
+        self.list_objects(&query, client).await
+    }
 
+    fn parse_object_infos_xml(xml: &str) -> Result<ObjectInfos, OssError> {
+        let url_encoded = is_url_encoded(xml);
         let mut list = vec![];
-        for i in 0..start_positions.len() {
-            let path = &xml[start_positions[i] + 5..end_positions[i]];
-            list.push(Object::new(path.to_owned()))
+        for block in Self::parse_blocks(xml, "Contents") {
+            let path = Self::parse_item(block, "Key").ok_or(OssError::NoFoundKey)?;
+            let path = decode_key(path, url_encoded);
+            let mut info = Self::parse_contents_block(block)?;
+            let owner = Self::parse_blocks(block, "Owner")
+                .first()
+                .and_then(|owner| {
+                    Some(Owner::new(
+                        Self::parse_item(owner, "ID")?,
+                        Self::parse_item(owner, "DisplayName")?,
+                    ))
+                });
+            info.set_owner(owner);
+            list.push((Object::new(path), info));
         }
 
-        Ok(list)
-    }
-}
+        let token = Self::parse_next_token(xml, url_encoded);
 
-#[derive(Debug)]
-pub struct BucketInfo {
-    //base: Bucket,
-    creation_date: DateTime<Utc>,
-    storage_class: StorageClass,
-    data_redundancy_type: DataRedundancyType,
-}
+        Ok(ObjectInfos::new(list, token))
+    }
 
-impl BucketInfo {
-    pub fn new(
-        creation_date: DateTime<Utc>,
-        storage_class: StorageClass,
-        data_redundancy_type: DataRedundancyType,
-    ) -> Self {
-        BucketInfo {
-            creation_date,
-            storage_class,
-            data_redundancy_type,
+    /// 获取下一页时使用的 token，V2 版本的列举接口为 `NextContinuationToken`，
+    /// V1 版本为 `NextMarker`，`NextMarker` 是文件路径，与文件路径一样按 `encoding-type` 解码
+    fn parse_next_token(xml: &str, url_encoded: bool) -> Option<String> {
+        match Self::parse_item(xml, "NextContinuationToken") {
+            Some(token) => Some(token.to_owned()),
+            None => Self::parse_item(xml, "NextMarker")
+                .filter(|marker| !marker.is_empty())
+                .map(|marker| decode_key(marker, url_encoded)),
         }
     }
 
-    pub fn creation_date(&self) -> &DateTime<Utc> {
-        &self.creation_date
-    }
-    pub fn storage_class(&self) -> &StorageClass {
-        &self.storage_class
+    /// 从列举文件的结果中找到路径完全一致的文件信息
+    fn parse_object_info_xml(xml: &str, path: &str) -> Result<Option<ObjectInfo>, OssError> {
+        let url_encoded = is_url_encoded(xml);
+        let block = Self::parse_blocks(xml, "Contents")
+            .into_iter()
+            .find(|block| {
+                Self::parse_item(block, "Key").map(|key| decode_key(key, url_encoded))
+                    == Some(path.to_owned())
+            });
+        match block {
+            Some(block) => Ok(Some(Self::parse_contents_block(block)?)),
+            None => Ok(None),
+        }
     }
-    pub fn data_redundancy_type(&self) -> &DataRedundancyType {
-        &self.data_redundancy_type
+
+    /// 解析列举文件结果中的一个 `<Contents>` 块
+    fn parse_contents_block(block: &str) -> Result<ObjectInfo, OssError> {
+        let last_modified = ObjectInfo::parse_last_modified(
+            Self::parse_item(block, "LastModified").ok_or(OssError::NoFoundLastModified)?,
+        )?;
+        let etag = Self::parse_item(block, "ETag").ok_or(OssError::NoFoundEtag)?;
+        let size = Self::parse_item(block, "Size")
+            .ok_or(OssError::NoFoundContentLength)?
+            .parse()?;
+
+        let mut info = ObjectInfo::new(last_modified, etag.to_owned(), size);
+        info.set_storage_class(Self::parse_item(block, "StorageClass").and_then(StorageClass::new));
+
+        Ok(info)
     }
-}
 
-#[derive(Default, Debug)]
-pub enum Grant {
-    #[default]
-    Private,
-    PublicRead,
-    PublicReadWrite,
-}
+    async fn list_xml(&self, query: &ObjectQuery, client: &Client) -> Result<String, OssError> {
+        let mut url = self.to_url();
+        url.set_query(Some(&query.to_oss_query()));
+        let resource = CanonicalizedResource::from_object_query(self, query);
 
-#[derive(Clone, Debug, Default)]
-pub enum DataRedundancyType {
-    #[default]
-    LRS,
-    ZRS,
-}
+        let content = client.get_xml(url, resource).await?;
 
-impl FromStr for DataRedundancyType {
-    type Err = OssError;
+        //println!("{content}");
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "LRS" => Ok(DataRedundancyType::LRS),
-            "ZRS" => Ok(DataRedundancyType::ZRS),
-            _ => Err(OssError::NoFoundDataRedundancyType),
-        }
+        Ok(content)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use serde::Deserialize;
+    /// 自动翻页，获取符合条件的所有文件，`max` 为最多返回的文件数量，为 `None` 时不限制，
+    /// 同时返回继续列举时使用的 `continuation-token`，没有更多文件时为 `None`
+    ///
+    /// 设置了 `max` 时最后一页只请求剩余的数量，与 [`list_objects_take`](Self::list_objects_take)
+    /// 相同。适用于文件数量不多的场景，文件数量很多时请使用 [`get_objects`](Self::get_objects) 逐页获取
+    pub async fn list_all_objects(
+        &self,
+        query: &ObjectQuery,
+        max: Option<usize>,
+        client: &Client,
+    ) -> Result<(Vec<Object>, Option<String>), OssError> {
+        take_pages(query, max.unwrap_or(usize::MAX), |query| async move {
+            self.get_objects(&query, client).await
+        })
+        .await
+    }
 
-    use crate::{
-        client::init_client,
-        types::{EndPoint, ObjectQuery},
-    };
+    /// 创建 bucket，可以通过 `options` 指定访问权限，存储类型和数据容灾类型，使用
+    /// `Default::default()` 时创建私有的标准存储类型 bucket
+    pub async fn create(
+        &self,
+        options: &CreateBucketOptions,
+        client: &Client,
+    ) -> Result<(), OssError> {
+        let url = self.to_url();
+        let resource = CanonicalizedResource::from_object_list(self, None);
+
+        client
+            .send(
+                Method::PUT,
+                url,
+                resource,
+                options.headers()?,
+                Some(options.body().into_bytes()),
+            )
+            .await?;
 
-    use super::Bucket;
+        Ok(())
+    }
 
-    #[tokio::test]
-    async fn test_get_info() {
-        let bucket = Bucket::new("honglei123", EndPoint::CN_SHANGHAI);
-        let info = bucket.get_info(&init_client()).await.unwrap();
+    /// 删除 bucket，OSS 只允许删除空的 bucket
+    pub async fn delete(&self, client: &Client) -> Result<(), OssError> {
+        let url = self.to_url();
+        let method = Method::DELETE;
+        let resource = CanonicalizedResource::from_object_list(self, None);
 
-        println!("{info:?}");
-        //assert_eq!(list.len(), 2);
+        client
+            .send(method, url, resource, HeaderMap::new(), None)
+            .await?;
+
+        Ok(())
+    }
+
+    /// 批量删除文件，每次请求最多删除 1000 个文件，超过时会分多次请求
+    ///
+    /// 使用简单（quiet）模式，请求会带上 OSS 要求的 `Content-MD5`
+    pub async fn delete_objects(
+        &self,
+        objects: &[Object],
+        client: &Client,
+    ) -> Result<(), OssError> {
+        let mut url = self.to_url();
+        url.set_query(Some(DELETE));
+
+        for chunk in objects.chunks(MAX_DELETE_OBJECTS) {
+            let resource = CanonicalizedResource::from_bucket_query(self, DELETE);
+            let (headers, body) = delete_objects_request(chunk)?;
+            client
+                .send(Method::POST, url.clone(), resource, headers, Some(body))
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// 彻底删除 `prefix` 下所有文件的所有版本和删除标记，返回删除的数量
+    ///
+    /// 开启了版本控制的 bucket 中，普通的删除只会创建删除标记，需要使用该方法才能真正删除文件，
+    /// 会先列举出所有版本，再按每次最多 1000 个批量删除
+    pub async fn delete_all_versions(
+        &self,
+        prefix: &str,
+        client: &Client,
+    ) -> Result<usize, OssError> {
+        let mut query = ObjectQuery::new();
+        query.insert(ObjectQuery::PREFIX, prefix);
+        let entries: Vec<VersionEntry> = self
+            .list_object_versions_stream(&query, client)
+            .try_collect()
+            .await?;
+
+        let mut url = self.to_url();
+        url.set_query(Some(DELETE));
+        for chunk in entries.chunks(MAX_DELETE_OBJECTS) {
+            let resource = CanonicalizedResource::from_bucket_query(self, DELETE);
+            let (headers, body) = delete_versions_request(chunk)?;
+            client
+                .send(Method::POST, url.clone(), resource, headers, Some(body))
+                .await?;
+        }
+
+        Ok(entries.len())
+    }
+
+    /// 先检查 bucket 中是否存在文件，存在时返回 `BucketNotEmpty` 错误，否则删除 bucket
+    pub async fn delete_if_empty(&self, client: &Client) -> Result<(), OssError> {
+        let mut query = ObjectQuery::new();
+        query.insert(ObjectQuery::MAX_KEYS, "1");
+
+        if !self.get_objects(&query, client).await?.is_empty() {
+            return Err(OssError::BucketNotEmpty);
+        }
+
+        self.delete(client).await
+    }
+
+    /// 获取 bucket 的授权策略，OSS 的授权策略是 JSON 格式的文本
+    pub async fn get_policy(&self, client: &Client) -> Result<String, OssError> {
+        let content = self
+            .send_sub_resource(Method::GET, POLICY, None, client)
+            .await?
+            .text()
+            .await?;
+
+        Ok(content)
+    }
+
+    /// 设置 bucket 的授权策略，`policy` 为 JSON 格式的文本
+    pub async fn put_policy<P: Into<String>>(
+        &self,
+        policy: P,
+        client: &Client,
+    ) -> Result<(), OssError> {
+        let policy: String = policy.into();
+        self.send_sub_resource(Method::PUT, POLICY, Some(policy.into_bytes()), client)
+            .await?;
+
+        Ok(())
+    }
+
+    /// 删除 bucket 的授权策略
+    pub async fn delete_policy(&self, client: &Client) -> Result<(), OssError> {
+        self.send_sub_resource(Method::DELETE, POLICY, None, client)
+            .await?;
+
+        Ok(())
+    }
+
+    /// 列举 bucket 中所有对象的版本信息（包括删除标记），每次返回一页
+    ///
+    /// 支持的查询条件有 `prefix`，`delimiter`，`max-keys`，`key-marker`，`version-id-marker`，
+    /// 翻页时将上一页的 `next_key_marker` 和 `next_version_id_marker` 作为 `key-marker`
+    /// 和 `version-id-marker` 传入
+    pub async fn list_object_versions(
+        &self,
+        query: &ObjectQuery,
+        client: &Client,
+    ) -> Result<ObjectVersions, OssError> {
+        let mut url = self.to_url();
+        url.set_query(Some(&query.to_versions_query()));
+        let resource = CanonicalizedResource::from_bucket_query(self, VERSIONS);
+
+        let content = client.get_xml(url, resource).await?;
+
+        Self::parse_versions_xml(&content)
+    }
+
+    /// 自动翻页，逐个返回符合条件的所有对象版本和删除标记，翻页时使用上一页的
+    /// `NextKeyMarker` 和 `NextVersionIdMarker`，可用于清理某个前缀下的所有版本
+    ///
+    /// 每一页中先返回版本，再返回删除标记
+    pub fn list_object_versions_stream<'a>(
+        &'a self,
+        query: &ObjectQuery,
+        client: &'a Client,
+    ) -> impl Stream<Item = Result<VersionEntry, OssError>> + 'a {
+        versions_stream(query.clone(), move |query| async move {
+            self.list_object_versions(&query, client).await
+        })
+    }
+
+    fn parse_versions_xml(xml: &str) -> Result<ObjectVersions, OssError> {
+        fn required<'a>(xml: &'a str, field: &str) -> Result<&'a str, OssError> {
+            Bucket::parse_item(xml, field).ok_or(OssError::InvalidVersions)
+        }
+
+        let mut versions = vec![];
+        for item in Self::parse_blocks(xml, "Version") {
+            versions.push(ObjectVersion::new(
+                required(item, "Key")?.to_owned(),
+                required(item, "VersionId")?.to_owned(),
+                required(item, "IsLatest")? == "true",
+                ObjectInfo::parse_last_modified(required(item, "LastModified")?)?,
+                required(item, "ETag")?.to_owned(),
+                required(item, "Size")?.parse()?,
+            ));
+        }
+
+        let mut delete_markers = vec![];
+        for item in Self::parse_blocks(xml, "DeleteMarker") {
+            delete_markers.push(DeleteMarker::new(
+                required(item, "Key")?.to_owned(),
+                required(item, "VersionId")?.to_owned(),
+                required(item, "IsLatest")? == "true",
+                ObjectInfo::parse_last_modified(required(item, "LastModified")?)?,
+            ));
+        }
+
+        let next_key_marker = Self::parse_item(xml, "NextKeyMarker")
+            .filter(|m| !m.is_empty())
+            .map(|m| m.to_owned());
+        let next_version_id_marker = Self::parse_item(xml, "NextVersionIdMarker")
+            .filter(|m| !m.is_empty())
+            .map(|m| m.to_owned());
+
+        Ok(ObjectVersions::new(
+            versions,
+            delete_markers,
+            next_key_marker,
+            next_version_id_marker,
+        ))
+    }
+
+    /// 获取 xml 中所有 `<tag>...</tag>` 的内容
+    pub(crate) fn parse_blocks<'a>(xml: &'a str, tag: &str) -> Vec<&'a str> {
+        let start_tag = format!("<{}>", tag);
+        let end_tag = format!("</{}>", tag);
+
+        let mut blocks = vec![];
+        let mut rest = xml;
+        while let Some(start) = rest.find(&start_tag) {
+            let content = &rest[start + start_tag.len()..];
+            match content.find(&end_tag) {
+                Some(end) => {
+                    blocks.push(&content[..end]);
+                    rest = &content[end + end_tag.len()..];
+                }
+                None => break,
+            }
+        }
+        blocks
+    }
+
+    /// 获取 bucket 的访问权限
+    pub async fn get_acl(&self, client: &Client) -> Result<Grant, OssError> {
+        self.get_acl_policy(client)
+            .await?
+            .grants
+            .first()
+            .copied()
+            .ok_or(OssError::InvalidGrant)
+    }
+
+    /// 获取 bucket 的访问权限详情，包含拥有者的 ID 和名称
+    pub async fn get_acl_policy(&self, client: &Client) -> Result<AccessControlPolicy, OssError> {
+        let content = self
+            .send_sub_resource(Method::GET, ACL, None, client)
+            .await?
+            .text()
+            .await?;
+
+        Self::parse_acl_xml(&content)
+    }
+
+    fn parse_acl_xml(xml: &str) -> Result<AccessControlPolicy, OssError> {
+        #[derive(Debug, Deserialize)]
+        struct AccessControlPolicyXml {
+            #[serde(rename = "Owner")]
+            owner: Owner,
+            #[serde(rename = "AccessControlList")]
+            acl: AccessControlList,
+        }
+        #[derive(Debug, Deserialize)]
+        struct AccessControlList {
+            #[serde(rename = "Grant", default)]
+            grants: Vec<String>,
+        }
+
+        let res: AccessControlPolicyXml = from_str(xml)?;
+        let grants = res
+            .acl
+            .grants
+            .iter()
+            .map(|g| g.parse())
+            .collect::<Result<_, _>>()?;
+
+        Ok(AccessControlPolicy {
+            owner: res.owner,
+            grants,
+        })
+    }
+
+    /// 设置 bucket 的生命周期规则，会覆盖已有的规则
+    pub async fn put_lifecycle(
+        &self,
+        config: &LifecycleConfiguration,
+        client: &Client,
+    ) -> Result<(), OssError> {
+        self.send_sub_resource(
+            Method::PUT,
+            LIFECYCLE,
+            Some(config.to_xml().into_bytes()),
+            client,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// 获取 bucket 的生命周期规则
+    pub async fn get_lifecycle(&self, client: &Client) -> Result<LifecycleConfiguration, OssError> {
+        let content = self
+            .send_sub_resource(Method::GET, LIFECYCLE, None, client)
+            .await?
+            .text()
+            .await?;
+
+        LifecycleConfiguration::from_xml(&content)
+    }
+
+    /// 删除 bucket 的所有生命周期规则
+    pub async fn delete_lifecycle(&self, client: &Client) -> Result<(), OssError> {
+        self.send_sub_resource(Method::DELETE, LIFECYCLE, None, client)
+            .await?;
+
+        Ok(())
+    }
+
+    /// 添加清单配置，同一个 bucket 最多可以有 1000 个清单配置
+    pub async fn put_inventory(
+        &self,
+        config: &InventoryConfiguration,
+        client: &Client,
+    ) -> Result<(), OssError> {
+        let query = format!("{}&inventoryId={}", INVENTORY, encode_query(config.id()));
+        self.send_sub_resource(
+            Method::PUT,
+            &query,
+            Some(config.to_xml().into_bytes()),
+            client,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// 获取 `id` 对应的清单配置
+    pub async fn get_inventory(
+        &self,
+        id: &str,
+        client: &Client,
+    ) -> Result<InventoryConfiguration, OssError> {
+        let query = format!("{}&inventoryId={}", INVENTORY, encode_query(id));
+        let content = self
+            .send_sub_resource(Method::GET, &query, None, client)
+            .await?
+            .text()
+            .await?;
+
+        InventoryConfiguration::from_xml(&content)
+    }
+
+    /// 获取 bucket 的所有清单配置，会自动翻页
+    pub async fn list_inventory(
+        &self,
+        client: &Client,
+    ) -> Result<Vec<InventoryConfiguration>, OssError> {
+        let mut list = Vec::new();
+        let mut token = None;
+        loop {
+            // 签名时子资源需要按字母顺序排列
+            let query = match &token {
+                Some(token) => format!("continuation-token={}&{}", token, INVENTORY),
+                None => INVENTORY.to_owned(),
+            };
+            let content = self
+                .send_sub_resource(Method::GET, &query, None, client)
+                .await?
+                .text()
+                .await?;
+
+            let (page, next) = InventoryConfiguration::parse_list_xml(&content)?;
+            list.extend(page);
+            match next {
+                Some(next) => token = Some(next),
+                None => return Ok(list),
+            }
+        }
+    }
+
+    /// 删除 `id` 对应的清单配置，已经导出的清单文件不会被删除
+    pub async fn delete_inventory(&self, id: &str, client: &Client) -> Result<(), OssError> {
+        let query = format!("{}&inventoryId={}", INVENTORY, encode_query(id));
+        self.send_sub_resource(Method::DELETE, &query, None, client)
+            .await?;
+
+        Ok(())
+    }
+
+    /// 创建合规保留策略，保护期为 `days` 天，返回策略 ID，
+    /// 需要在 24 小时内调用 [`Bucket::complete_worm`] 锁定，否则策略失效
+    pub async fn initiate_worm(&self, days: u32, client: &Client) -> Result<String, OssError> {
+        let response = self
+            .send_sub_resource(
+                Method::POST,
+                WORM,
+                Some(WormConfiguration::initiate_xml(days).into_bytes()),
+                client,
+            )
+            .await?;
+
+        WormConfiguration::id_from_headers(response.headers())
+    }
+
+    /// 获取 bucket 的合规保留策略
+    pub async fn get_worm(&self, client: &Client) -> Result<WormConfiguration, OssError> {
+        let content = self
+            .send_sub_resource(Method::GET, WORM, None, client)
+            .await?
+            .text()
+            .await?;
+
+        WormConfiguration::from_xml(&content)
+    }
+
+    /// 锁定 `worm_id` 对应的合规保留策略，锁定后不能删除
+    pub async fn complete_worm(&self, worm_id: &str, client: &Client) -> Result<(), OssError> {
+        let query = format!("wormId={}", encode_query(worm_id));
+        self.send_sub_resource(Method::POST, &query, None, client)
+            .await?;
+
+        Ok(())
+    }
+
+    /// 删除未锁定的合规保留策略，OSS 的接口不需要策略 ID
+    pub async fn abort_worm(&self, client: &Client) -> Result<(), OssError> {
+        self.send_sub_resource(Method::DELETE, WORM, None, client)
+            .await?;
+
+        Ok(())
+    }
+
+    /// 将已锁定策略的保护期延长为 `days` 天
+    pub async fn extend_worm(
+        &self,
+        worm_id: &str,
+        days: u32,
+        client: &Client,
+    ) -> Result<(), OssError> {
+        // 签名时子资源需要按字母顺序排列
+        let query = format!("wormExtend&wormId={}", encode_query(worm_id));
+        self.send_sub_resource(
+            Method::POST,
+            &query,
+            Some(WormConfiguration::extend_xml(days).into_bytes()),
+            client,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// 获取 bucket 的请求者付费设置
+    pub async fn get_request_payment(&self, client: &Client) -> Result<Payer, OssError> {
+        let content = self
+            .send_sub_resource(Method::GET, REQUEST_PAYMENT, None, client)
+            .await?
+            .text()
+            .await?;
+
+        Self::parse_item(&content, "Payer")
+            .ok_or(OssError::InvalidPayer)?
+            .parse()
+    }
+
+    /// 设置 bucket 由谁支付请求和流量费用，[`Payer::Requester`] 即开启请求者付费
+    pub async fn put_request_payment(&self, payer: Payer, client: &Client) -> Result<(), OssError> {
+        self.send_sub_resource(
+            Method::PUT,
+            REQUEST_PAYMENT,
+            Some(payer.to_xml().into_bytes()),
+            client,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// 获取 bucket 绑定的所有自定义域名
+    pub async fn list_cname(&self, client: &Client) -> Result<Vec<CnameInfo>, OssError> {
+        let content = self
+            .send_sub_resource(Method::GET, CNAME, None, client)
+            .await?
+            .text()
+            .await?;
+
+        CnameInfo::parse_list_xml(&content)
+    }
+
+    /// 创建验证域名所有权的 token，将其添加到 `domain` 的 TXT 记录后才能绑定
+    pub async fn create_cname_token(
+        &self,
+        domain: &str,
+        client: &Client,
+    ) -> Result<CnameToken, OssError> {
+        let content = self
+            .send_cname(&format!("{}&comp=token", CNAME), domain, client)
+            .await?
+            .text()
+            .await?;
+
+        CnameToken::from_xml(&content)
+    }
+
+    /// 将自定义域名 `domain` 绑定到 bucket，之后可以通过
+    /// [`with_custom_domain`](Self::with_custom_domain) 使用该域名访问
+    pub async fn put_cname(&self, domain: &str, client: &Client) -> Result<(), OssError> {
+        self.send_cname(&format!("{}&comp=add", CNAME), domain, client)
+            .await?;
+
+        Ok(())
+    }
+
+    /// 解绑自定义域名 `domain`
+    pub async fn delete_cname(&self, domain: &str, client: &Client) -> Result<(), OssError> {
+        self.send_cname(&format!("{}&comp=delete", CNAME), domain, client)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn send_cname(
+        &self,
+        query: &str,
+        domain: &str,
+        client: &Client,
+    ) -> Result<Response, OssError> {
+        self.send_sub_resource(
+            Method::POST,
+            query,
+            Some(cname_xml(domain).into_bytes()),
+            client,
+        )
+        .await
+    }
+
+    /// 创建或修改图片样式 `name`，`content` 为图片处理参数，如 `image/resize,p_50`
+    pub async fn put_style(
+        &self,
+        name: &str,
+        content: &str,
+        client: &Client,
+    ) -> Result<(), OssError> {
+        let query = format!("{}&styleName={}", STYLE, encode_query(name));
+        self.send_sub_resource(
+            Method::PUT,
+            &query,
+            Some(style_xml(content).into_bytes()),
+            client,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// 获取图片样式 `name`
+    pub async fn get_style(&self, name: &str, client: &Client) -> Result<ImageStyle, OssError> {
+        let query = format!("{}&styleName={}", STYLE, encode_query(name));
+        let content = self
+            .send_sub_resource(Method::GET, &query, None, client)
+            .await?
+            .text()
+            .await?;
+
+        ImageStyle::from_xml(&content)
+    }
+
+    /// 获取 bucket 的所有图片样式
+    pub async fn list_style(&self, client: &Client) -> Result<Vec<ImageStyle>, OssError> {
+        let content = self
+            .send_sub_resource(Method::GET, STYLE, None, client)
+            .await?
+            .text()
+            .await?;
+
+        ImageStyle::parse_list_xml(&content)
+    }
+
+    /// 删除图片样式 `name`
+    pub async fn delete_style(&self, name: &str, client: &Client) -> Result<(), OssError> {
+        let query = format!("{}&styleName={}", STYLE, encode_query(name));
+        self.send_sub_resource(Method::DELETE, &query, None, client)
+            .await?;
+
+        Ok(())
+    }
+
+    /// 获取 bucket 是否开启了传输加速
+    pub async fn get_transfer_acceleration(&self, client: &Client) -> Result<bool, OssError> {
+        let content = self
+            .send_sub_resource(Method::GET, TRANSFER_ACCELERATION, None, client)
+            .await?
+            .text()
+            .await?;
+
+        parse_transfer_acceleration(&content)
+    }
+
+    /// 开启或关闭 bucket 的传输加速，开启后可以通过
+    /// [`with_transfer_acceleration`](Self::with_transfer_acceleration) 使用加速域名访问文件
+    pub async fn put_transfer_acceleration(
+        &self,
+        enabled: bool,
+        client: &Client,
+    ) -> Result<(), OssError> {
+        self.send_sub_resource(
+            Method::PUT,
+            TRANSFER_ACCELERATION,
+            Some(transfer_acceleration_xml(enabled).into_bytes()),
+            client,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// 获取 bucket 是否开启了访问跟踪
+    pub async fn get_access_monitor(&self, client: &Client) -> Result<bool, OssError> {
+        let content = self
+            .send_sub_resource(Method::GET, ACCESS_MONITOR, None, client)
+            .await?
+            .text()
+            .await?;
+
+        parse_access_monitor(&content)
+    }
+
+    /// 开启或关闭 bucket 的访问跟踪，开启后 OSS 会记录文件的最后访问时间，
+    /// 生命周期规则才能按最后访问时间转换文件的存储类型
+    pub async fn put_access_monitor(&self, enabled: bool, client: &Client) -> Result<(), OssError> {
+        self.send_sub_resource(
+            Method::PUT,
+            ACCESS_MONITOR,
+            Some(access_monitor_xml(enabled).into_bytes()),
+            client,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// 列举 key 以 `prefix` 开头的，已初始化但还没有完成或取消的分片上传任务，自动翻页
+    pub async fn list_multipart_uploads(
+        &self,
+        prefix: &str,
+        client: &Client,
+    ) -> Result<Vec<MultipartUpload>, OssError> {
+        let mut list = Vec::new();
+        let mut marker: Option<(String, String)> = None;
+        loop {
+            let mut url = self.to_url();
+            url.set_query(Some(UPLOADS));
+            {
+                let mut query = url.query_pairs_mut();
+                query.append_pair("prefix", prefix);
+                if let Some((key, upload_id)) = &marker {
+                    query
+                        .append_pair("key-marker", key)
+                        .append_pair("upload-id-marker", upload_id);
+                }
+            }
+            let resource = CanonicalizedResource::from_bucket_query(self, UPLOADS);
+            let content = client.get_xml(url, resource).await?;
+
+            let (page, next) = MultipartUpload::parse_list_xml(&content)?;
+            list.extend(page);
+            match next {
+                Some(next) => marker = Some(next),
+                None => return Ok(list),
+            }
+        }
+    }
+
+    /// 请求 bucket 的子资源，如 `?policy`，子资源会参与签名
+    pub(crate) async fn send_sub_resource(
+        &self,
+        method: Method,
+        sub_resource: &str,
+        body: Option<Vec<u8>>,
+        client: &Client,
+    ) -> Result<Response, OssError> {
+        let mut url = self.to_url();
+        url.set_query(Some(sub_resource));
+        let resource = CanonicalizedResource::from_bucket_query(self, sub_resource);
+
+        client
+            .send(method, url, resource, HeaderMap::new(), body)
+            .await
+    }
+
+    pub(crate) fn parse_xml_objects(xml: &str) -> Result<Vec<Object>, OssError> {
+        let mut start_positions = vec![];
+        let mut end_positions = vec![];
+        let mut start = 0;
+        let mut pattern = "<Key>";
+
+        while let Some(pos) = xml[start..].find(pattern) {
+            start_positions.push(start + pos);
+            start += pos + pattern.len();
+        }
+        start = 0;
+        pattern = "</Key>";
+        while let Some(pos) = xml[start..].find(pattern) {
+            end_positions.push(start + pos);
+            start += pos + pattern.len();
+        }
+
+        let mut list = vec![];
+        for i in 0..start_positions.len() {
+            let path = &xml[start_positions[i] + 5..end_positions[i]];
+            list.push(Object::new(path.to_owned()))
+        }
+
+        Ok(list)
+    }
+}
+
+/// 生成批量删除的请求体，以及包含其 `Content-MD5` 的 header
+fn delete_objects_request(objects: &[Object]) -> Result<(HeaderMap, Vec<u8>), OssError> {
+    delete_request(objects.iter().map(|obj| (obj.as_ref(), None)))
+}
+
+/// 批量删除指定版本（包括删除标记）的请求
+fn delete_versions_request(entries: &[VersionEntry]) -> Result<(HeaderMap, Vec<u8>), OssError> {
+    delete_request(
+        entries
+            .iter()
+            .map(|entry| (entry.path(), Some(entry.version_id()))),
+    )
+}
+
+/// 批量删除请求的 header 和 body，`keys` 为文件路径及可选的 version id
+fn delete_request<'a, I>(keys: I) -> Result<(HeaderMap, Vec<u8>), OssError>
+where
+    I: Iterator<Item = (&'a str, Option<&'a str>)>,
+{
+    let mut body =
+        String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?><Delete><Quiet>true</Quiet>");
+    for (key, version_id) in keys {
+        body += "<Object><Key>";
+        body += &xml_escape(key);
+        body += "</Key>";
+        if let Some(version_id) = version_id {
+            body += "<VersionId>";
+            body += &xml_escape(version_id);
+            body += "</VersionId>";
+        }
+        body += "</Object>";
+    }
+    body += "</Delete>";
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        CONTENT_MD5,
+        ContentMd5::from_bytes(body.as_bytes())
+            .as_str()
+            .try_into()?,
+    );
+
+    Ok((headers, body.into_bytes()))
+}
+
+/// 使用 `fetch` 逐页获取对象版本列表，直到没有下一页
+fn versions_stream<F, Fut>(
+    query: ObjectQuery,
+    fetch: F,
+) -> impl Stream<Item = Result<VersionEntry, OssError>>
+where
+    F: FnMut(ObjectQuery) -> Fut,
+    Fut: Future<Output = Result<ObjectVersions, OssError>>,
+{
+    stream::try_unfold((Some(query), fetch), |(query, mut fetch)| async move {
+        let query = match query {
+            Some(query) => query,
+            None => return Ok(None),
+        };
+        let page = fetch(query.clone()).await?;
+        let next = page.next_query(&query);
+        Ok::<_, OssError>(Some((page, (next, fetch))))
+    })
+    .map_ok(|page| stream::iter(page.into_entries().into_iter().map(Ok)))
+    .try_flatten()
+}
+
+pub(crate) fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// [`xml_escape`] 的逆操作，`&amp;` 最后替换，避免 `&amp;lt;` 被还原为 `<`
+pub(crate) fn xml_unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn parse_bucket_xml<B: DeserializeOwned>(xml: &str) -> Result<B, OssError> {
+    #[derive(Debug, Deserialize)]
+    struct BucketInfo<T> {
+        #[serde(rename = "Bucket")]
+        bucket: T,
+    }
+    let res: BucketInfo<B> = from_str(xml)?;
+
+    Ok(res.bucket)
+}
+
+fn parse_list_xml<Obj: DeserializeOwned>(
+    xml: &str,
+) -> Result<(Vec<Obj>, NextContinuationToken), OssError> {
+    #[derive(Debug, Deserialize)]
+    struct ListBucketResult<T> {
+        #[serde(rename = "Contents")]
+        contents: Vec<T>,
+        #[serde(rename = "NextContinuationToken")]
+        next_token: Option<String>,
+        /// V1 版本的列举接口使用 `NextMarker` 翻页
+        #[serde(rename = "NextMarker")]
+        next_marker: Option<String>,
+    }
+    let res: ListBucketResult<Obj> = from_str(xml)?;
+    let next_marker = res.next_marker.filter(|marker| !marker.is_empty());
+
+    Ok((res.contents, res.next_token.or(next_marker)))
+}
+
+/// 列举结果是否使用了 `encoding-type=url`，此时 `Key`，`Prefix` 等字段经过了 URL 编码
+fn is_url_encoded(xml: &str) -> bool {
+    Bucket::parse_item(xml, "EncodingType") == Some("url")
+}
+
+/// 使用了 `encoding-type=url` 时解码文件路径或前缀
+fn decode_key(value: &str, url_encoded: bool) -> String {
+    if url_encoded {
+        percent_decode_str(value).decode_utf8_lossy().into_owned()
+    } else {
+        value.to_owned()
+    }
+}
+
+/// 读取响应内容，非 2xx 的响应会被转换为 `OssError`，响应内容不是 UTF-8 时返回错误
+async fn response_text(response: Response) -> Result<String, OssError> {
+    let status = response.status();
+    let content = response.bytes().await?;
+    if !status.is_success() {
+        return Err(OssError::from_status(
+            status,
+            &String::from_utf8_lossy(&content),
+        ));
+    }
+
+    Ok(String::from_utf8(content.into())?)
+}
+
+/// 将自定义请求（如 `?bucketInfo`）的响应解析为自定义的 bucket 类型，解析规则与
+/// [`Bucket::export_info`] 相同
+pub async fn decode_bucket_response<B: DeserializeOwned>(
+    response: Response,
+) -> Result<B, OssError> {
+    parse_bucket_xml(&response_text(response).await?)
+}
+
+/// 将自定义的列举文件请求的响应解析为自定义的 object 类型，同时返回用于翻页的
+/// `NextContinuationToken`，解析规则与 [`Bucket::export_objects`] 相同
+pub async fn decode_list_response<Obj: DeserializeOwned>(
+    response: Response,
+) -> Result<(Vec<Obj>, NextContinuationToken), OssError> {
+    parse_list_xml(&response_text(response).await?)
+}
+
+/// 列举 bucket 的结果，每个 bucket 使用其所在区域（`<Location>`）的 endpoint
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListBuckets {
+    buckets: Vec<Bucket>,
+}
+
+impl ListBuckets {
+    pub(crate) fn from_xml(xml: &str) -> Result<ListBuckets, OssError> {
+        let mut buckets = vec![];
+        for block in Bucket::parse_blocks(xml, "Bucket") {
+            let name = Bucket::parse_item(block, "Name").ok_or(OssError::InvalidBucket)?;
+            let location = Bucket::parse_item(block, "Location").ok_or(OssError::InvalidBucket)?;
+            buckets.push(Bucket::new(name, location.parse()?));
+        }
+        Ok(ListBuckets { buckets })
+    }
+
+    pub fn buckets(&self) -> &[Bucket] {
+        &self.buckets
+    }
+
+    pub fn len(&self) -> usize {
+        self.buckets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buckets.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Bucket> {
+        self.buckets.iter()
+    }
+}
+
+impl IntoIterator for ListBuckets {
+    type Item = Bucket;
+    type IntoIter = std::vec::IntoIter<Bucket>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.buckets.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ListBuckets {
+    type Item = &'a Bucket;
+    type IntoIter = std::slice::Iter<'a, Bucket>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.buckets.iter()
+    }
+}
+
+#[derive(Debug)]
+pub struct BucketInfo {
+    //base: Bucket,
+    creation_date: DateTime<Utc>,
+    storage_class: StorageClass,
+    data_redundancy_type: DataRedundancyType,
+    access_monitor: Option<bool>,
+}
+
+impl BucketInfo {
+    pub fn new(
+        creation_date: DateTime<Utc>,
+        storage_class: StorageClass,
+        data_redundancy_type: DataRedundancyType,
+    ) -> Self {
+        BucketInfo {
+            creation_date,
+            storage_class,
+            data_redundancy_type,
+            access_monitor: None,
+        }
+    }
+
+    pub fn creation_date(&self) -> &DateTime<Utc> {
+        &self.creation_date
+    }
+    pub fn storage_class(&self) -> &StorageClass {
+        &self.storage_class
+    }
+    pub fn data_redundancy_type(&self) -> &DataRedundancyType {
+        &self.data_redundancy_type
+    }
+
+    /// 是否开启了访问跟踪，响应中没有 `<AccessMonitor>` 或值无法识别时为 `None`
+    pub fn access_monitor(&self) -> Option<bool> {
+        self.access_monitor
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Grant {
+    #[default]
+    Private,
+    PublicRead,
+    PublicReadWrite,
+}
+
+impl FromStr for Grant {
+    type Err = OssError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "private" => Ok(Grant::Private),
+            "public-read" => Ok(Grant::PublicRead),
+            "public-read-write" => Ok(Grant::PublicReadWrite),
+            _ => Err(OssError::InvalidGrant),
+        }
+    }
+}
+
+impl AsRef<str> for Grant {
+    fn as_ref(&self) -> &str {
+        match self {
+            Grant::Private => "private",
+            Grant::PublicRead => "public-read",
+            Grant::PublicReadWrite => "public-read-write",
+        }
+    }
+}
+
+/// 请求者付费设置中的付费方
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Payer {
+    #[default]
+    BucketOwner,
+    Requester,
+}
+
+impl Payer {
+    fn to_xml(self) -> String {
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+            <RequestPaymentConfiguration><Payer>{}</Payer></RequestPaymentConfiguration>",
+            self.as_ref()
+        )
+    }
+}
+
+fn transfer_acceleration_xml(enabled: bool) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+        <TransferAccelerationConfiguration><Enabled>{}</Enabled></TransferAccelerationConfiguration>",
+        enabled
+    )
+}
+
+fn access_monitor_xml(enabled: bool) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+        <AccessMonitorConfiguration><Status>{}</Status></AccessMonitorConfiguration>",
+        if enabled { "Enabled" } else { "Disabled" }
+    )
+}
+
+fn parse_access_monitor(xml: &str) -> Result<bool, OssError> {
+    parse_access_monitor_status(
+        Bucket::parse_item(xml, "Status").ok_or(OssError::InvalidAccessMonitor)?,
+    )
+}
+
+fn parse_access_monitor_status(status: &str) -> Result<bool, OssError> {
+    match status {
+        "Enabled" => Ok(true),
+        "Disabled" => Ok(false),
+        _ => Err(OssError::InvalidAccessMonitor),
+    }
+}
+
+fn parse_transfer_acceleration(xml: &str) -> Result<bool, OssError> {
+    match Bucket::parse_item(xml, "Enabled") {
+        Some("true") => Ok(true),
+        Some("false") => Ok(false),
+        _ => Err(OssError::InvalidTransferAcceleration),
+    }
+}
+
+impl FromStr for Payer {
+    type Err = OssError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "BucketOwner" => Ok(Payer::BucketOwner),
+            "Requester" => Ok(Payer::Requester),
+            _ => Err(OssError::InvalidPayer),
+        }
+    }
+}
+
+impl AsRef<str> for Payer {
+    fn as_ref(&self) -> &str {
+        match self {
+            Payer::BucketOwner => "BucketOwner",
+            Payer::Requester => "Requester",
+        }
+    }
+}
+
+/// bucket 的访问权限详情，包含拥有者信息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessControlPolicy {
+    owner: Owner,
+    grants: Vec<Grant>,
+}
+
+impl AccessControlPolicy {
+    pub fn new(owner: Owner, grants: Vec<Grant>) -> Self {
+        AccessControlPolicy { owner, grants }
+    }
+
+    pub fn owner(&self) -> &Owner {
+        &self.owner
+    }
+
+    pub fn grants(&self) -> &[Grant] {
+        &self.grants
+    }
+}
+
+/// 创建 bucket 时的选项，默认为私有（`private`），标准存储类型，本地冗余（`LRS`）
+/// ```
+/// # use aliyun_oss_client::bucket::{CreateBucketOptions, DataRedundancyType, Grant};
+/// let options = CreateBucketOptions::default()
+///     .acl(Grant::PublicRead)
+///     .data_redundancy_type(DataRedundancyType::ZRS);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CreateBucketOptions {
+    acl: Grant,
+    storage_class: StorageClass,
+    data_redundancy_type: DataRedundancyType,
+}
+
+impl CreateBucketOptions {
+    pub fn acl(mut self, acl: Grant) -> Self {
+        self.acl = acl;
+        self
+    }
+
+    pub fn storage_class(mut self, storage_class: StorageClass) -> Self {
+        self.storage_class = storage_class;
+        self
+    }
+
+    pub fn data_redundancy_type(mut self, data_redundancy_type: DataRedundancyType) -> Self {
+        self.data_redundancy_type = data_redundancy_type;
+        self
+    }
+
+    fn headers(&self) -> Result<HeaderMap, OssError> {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-oss-acl", self.acl.as_ref().try_into()?);
+        Ok(headers)
+    }
+
+    fn body(&self) -> String {
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+            <CreateBucketConfiguration>\
+            <StorageClass>{}</StorageClass>\
+            <DataRedundancyType>{}</DataRedundancyType>\
+            </CreateBucketConfiguration>",
+            self.storage_class.as_ref(),
+            self.data_redundancy_type.as_ref()
+        )
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub enum DataRedundancyType {
+    #[default]
+    LRS,
+    ZRS,
+}
+
+impl FromStr for DataRedundancyType {
+    type Err = OssError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "LRS" => Ok(DataRedundancyType::LRS),
+            "ZRS" => Ok(DataRedundancyType::ZRS),
+            _ => Err(OssError::NoFoundDataRedundancyType),
+        }
+    }
+}
+
+impl AsRef<str> for DataRedundancyType {
+    fn as_ref(&self) -> &str {
+        match self {
+            DataRedundancyType::LRS => "LRS",
+            DataRedundancyType::ZRS => "ZRS",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use crate::{
+        client::init_client,
+        object::Object,
+        types::{EndPoint, ObjectQuery, Owner, StorageClass},
+    };
+
+    use super::Bucket;
+
+    #[tokio::test]
+    async fn test_get_info() {
+        let bucket = Bucket::new("honglei123", EndPoint::CN_SHANGHAI);
+        let info = bucket.get_info(&init_client()).await.unwrap();
+
+        println!("{info:?}");
+        //assert_eq!(list.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_versions_stream() {
+        use super::versions_stream;
+        use futures_util::TryStreamExt;
+        use std::sync::Mutex;
+
+        fn page(marker: Option<(&str, &str)>, entries: &str) -> String {
+            let next = match marker {
+                Some((key, version)) => format!(
+                    "<IsTruncated>true</IsTruncated><NextKeyMarker>{key}</NextKeyMarker>\
+                    <NextVersionIdMarker>{version}</NextVersionIdMarker>"
+                ),
+                None => "<IsTruncated>false</IsTruncated>".to_owned(),
+            };
+            format!("<ListVersionsResult><Name>foo</Name>{next}{entries}</ListVersionsResult>")
+        }
+        let version = |key: &str, id: &str| {
+            format!(
+                "<Version><Key>{key}</Key><VersionId>{id}</VersionId><IsLatest>false</IsLatest>\
+                <LastModified>2019-04-09T07:27:28.000Z</LastModified><ETag>\"A\"</ETag>\
+                <Size>1</Size></Version>"
+            )
+        };
+        let marker = |key: &str, id: &str| {
+            format!(
+                "<DeleteMarker><Key>{key}</Key><VersionId>{id}</VersionId><IsLatest>true</IsLatest>\
+                <LastModified>2019-04-09T07:27:28.000Z</LastModified></DeleteMarker>"
+            )
+        };
+        let first = page(
+            Some(("a.txt", "v2")),
+            &format!("{}{}", version("a.txt", "v1"), marker("a.txt", "v3")),
+        );
+        let second = page(None, &version("b.txt", "v4"));
+
+        let mut query = ObjectQuery::new();
+        query.insert(ObjectQuery::PREFIX, "pre/");
+        let requests = Mutex::new(Vec::new());
+        let entries: Vec<_> = versions_stream(query, |query| {
+            let key_marker = query.get(ObjectQuery::KEY_MARKER).cloned();
+            let version_marker = query.get(ObjectQuery::VERSION_ID_MARKER).cloned();
+            assert_eq!(query.get(ObjectQuery::PREFIX).unwrap(), "pre/");
+            requests
+                .lock()
+                .unwrap()
+                .push((key_marker.clone(), version_marker));
+            let xml = if key_marker.is_none() {
+                first.clone()
+            } else {
+                second.clone()
+            };
+            async move { Bucket::parse_versions_xml(&xml) }
+        })
+        .try_collect()
+        .await
+        .unwrap();
+
+        assert_eq!(
+            *requests.lock().unwrap(),
+            [
+                (None, None),
+                (Some("a.txt".to_owned()), Some("v2".to_owned()))
+            ]
+        );
+        let ids: Vec<_> = entries
+            .iter()
+            .map(|e| (e.path(), e.version_id(), e.is_delete_marker()))
+            .collect();
+        assert_eq!(
+            ids,
+            [
+                ("a.txt", "v1", false),
+                ("a.txt", "v3", true),
+                ("b.txt", "v4", false)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_versions_xml() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListVersionsResult>
+  <Name>examplebucket</Name>
+  <Prefix></Prefix>
+  <KeyMarker></KeyMarker>
+  <VersionIdMarker></VersionIdMarker>
+  <MaxKeys>2</MaxKeys>
+  <Delimiter></Delimiter>
+  <IsTruncated>true</IsTruncated>
+  <NextKeyMarker>example</NextKeyMarker>
+  <NextVersionIdMarker>CAEQMxiBgMCZov2D0BYiIDY4MDllOTc2YmY5MjQxMzdiOGI3OTlhNTU0ODIx****</NextVersionIdMarker>
+  <DeleteMarker>
+    <Key>example</Key>
+    <VersionId>CAEQMxiBgICAof2D0BYiIDJhMGE3N2M1YTI1NDQzOGY5NTkyNTI3MGYyMzJm****</VersionId>
+    <IsLatest>true</IsLatest>
+    <LastModified>2019-04-09T07:27:28.000Z</LastModified>
+    <Owner>
+      <ID>1234512528586****</ID>
+      <DisplayName>12345125285864390</DisplayName>
+    </Owner>
+  </DeleteMarker>
+  <Version>
+    <Key>example</Key>
+    <VersionId>CAEQMxiBgMDNoP2D0BYiIDE3MWUxNzgxZDQxNTRiODI5OGYwZGMwNGY3MzZjN****</VersionId>
+    <IsLatest>false</IsLatest>
+    <LastModified>2019-04-09T07:27:28.000Z</LastModified>
+    <ETag>"250F8A0AE989679A22926A875F0A2****"</ETag>
+    <Type>Normal</Type>
+    <Size>93731</Size>
+    <StorageClass>Standard</StorageClass>
+    <Owner>
+      <ID>1234512528586****</ID>
+      <DisplayName>12345125285864390</DisplayName>
+    </Owner>
+  </Version>
+</ListVersionsResult>"#;
+        let list = Bucket::parse_versions_xml(xml).unwrap();
+
+        assert_eq!(list.versions().len(), 1);
+        let version = &list.versions()[0];
+        assert_eq!(version.path(), "example");
+        assert!(!version.is_latest());
+        assert_eq!(version.size(), 93731);
+        assert_eq!(version.etag(), "\"250F8A0AE989679A22926A875F0A2****\"");
+
+        assert_eq!(list.delete_markers().len(), 1);
+        assert!(list.delete_markers()[0].is_latest());
+
+        assert_eq!(list.next_key_marker(), Some("example"));
+        assert_eq!(
+            list.next_version_id_marker(),
+            Some("CAEQMxiBgMCZov2D0BYiIDY4MDllOTc2YmY5MjQxMzdiOGI3OTlhNTU0ODIx****")
+        );
+    }
+
+    #[test]
+    fn test_parse_acl_xml() {
+        use super::Grant;
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<AccessControlPolicy>
+    <Owner>
+        <ID>0022012****</ID>
+        <DisplayName>user_example</DisplayName>
+    </Owner>
+    <AccessControlList>
+        <Grant>public-read</Grant>
+    </AccessControlList>
+</AccessControlPolicy>"#;
+        let policy = Bucket::parse_acl_xml(xml).unwrap();
+        assert_eq!(policy.owner().id(), "0022012****");
+        assert_eq!(policy.owner().display_name(), "user_example");
+        assert_eq!(policy.grants(), &[Grant::PublicRead]);
+    }
+
+    #[tokio::test]
+    async fn test_policy() {
+        let bucket = Bucket::new("honglei123", EndPoint::CN_SHANGHAI);
+        let client = init_client();
+        let policy = r#"{"Version":"1","Statement":[{"Action":["oss:GetObject"],"Effect":"Allow","Principal":["1234567890"],"Resource":["acs:oss:*:*:honglei123/*"]}]}"#;
+
+        bucket.put_policy(policy, &client).await.unwrap();
+
+        let res = bucket.get_policy(&client).await.unwrap();
+        assert!(res.contains("oss:GetObject"));
+
+        bucket.delete_policy(&client).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_lifecycle() {
+        use crate::lifecycle::{LifecycleConfiguration, LifecycleRule};
+
+        let bucket = Bucket::new("honglei123", EndPoint::CN_SHANGHAI);
+        let client = init_client();
+        let config = LifecycleConfiguration::new(vec![LifecycleRule::new("lifecycle/")
+            .id("lifecycle-test")
+            .tag("type", "tmp")
+            .object_size_greater_than(1024)
+            .expiration_days(30)]);
+
+        bucket.put_lifecycle(&config, &client).await.unwrap();
+        assert_eq!(bucket.get_lifecycle(&client).await.unwrap(), config);
+        bucket.delete_lifecycle(&client).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_inventory() {
+        use crate::inventory::{InventoryConfiguration, InventoryDestination, InventoryField};
+
+        let bucket = Bucket::new("honglei123", EndPoint::CN_SHANGHAI);
+        let client = init_client();
+        let destination = InventoryDestination::new(
+            "honglei123",
+            "100000000000000",
+            "acs:ram::100000000000000:role/AliyunOSSRole",
+        )
+        .prefix("inventory/");
+        let config = InventoryConfiguration::new("inventory-test", destination)
+            .prefix("log/")
+            .field(InventoryField::Size);
+
+        bucket.put_inventory(&config, &client).await.unwrap();
+        let res = bucket
+            .get_inventory("inventory-test", &client)
+            .await
+            .unwrap();
+        assert_eq!(res, config);
+        let list = bucket.list_inventory(&client).await.unwrap();
+        assert!(list.contains(&config));
+        bucket
+            .delete_inventory("inventory-test", &client)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_worm() {
+        use crate::worm::WormState;
+
+        let bucket = Bucket::new("honglei123", EndPoint::CN_SHANGHAI);
+        let client = init_client();
+
+        let id = bucket.initiate_worm(1, &client).await.unwrap();
+        let config = bucket.get_worm(&client).await.unwrap();
+        assert_eq!(config.id(), id);
+        assert_eq!(config.state(), WormState::InProgress);
+        assert_eq!(config.retention_days(), 1);
+
+        bucket.complete_worm(&id, &client).await.unwrap();
+        let config = bucket.get_worm(&client).await.unwrap();
+        assert_eq!(config.id(), id);
+        assert_eq!(config.state(), WormState::Locked);
+    }
+
+    #[tokio::test]
+    async fn test_request_payment() {
+        use super::Payer;
+
+        let bucket = Bucket::new("honglei123", EndPoint::CN_SHANGHAI);
+        let client = init_client();
+
+        for payer in [Payer::Requester, Payer::BucketOwner] {
+            bucket.put_request_payment(payer, &client).await.unwrap();
+            assert_eq!(bucket.get_request_payment(&client).await.unwrap(), payer);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cname() {
+        let bucket = Bucket::new("honglei123", EndPoint::CN_SHANGHAI);
+        let client = init_client();
+        let domain = "oss-rs.example.com";
+
+        let token = bucket.create_cname_token(domain, &client).await.unwrap();
+        assert_eq!(token.domain(), domain);
+
+        bucket.put_cname(domain, &client).await.unwrap();
+        let list = bucket.list_cname(&client).await.unwrap();
+        assert!(list.iter().any(|c| c.domain() == domain));
+
+        bucket.delete_cname(domain, &client).await.unwrap();
+        let list = bucket.list_cname(&client).await.unwrap();
+        assert!(list.iter().all(|c| c.domain() != domain));
+    }
+
+    #[tokio::test]
+    async fn test_style() {
+        let bucket = Bucket::new("honglei123", EndPoint::CN_SHANGHAI);
+        let client = init_client();
+
+        bucket
+            .put_style("half", "image/resize,p_50", &client)
+            .await
+            .unwrap();
+        let style = bucket.get_style("half", &client).await.unwrap();
+        assert_eq!(style.content(), "image/resize,p_50");
+        let list = bucket.list_style(&client).await.unwrap();
+        assert!(list.iter().any(|s| s.name() == "half"));
+
+        bucket.delete_style("half", &client).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_sub_resource_ids_encoded() {
+        use crate::{
+            types::{Key, Secret},
+            Client,
+        };
+        use std::sync::{Arc, Mutex};
+
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let mut client = Client::new(Key::new("foo"), Secret::new("bar"));
+        {
+            let requests = requests.clone();
+            client.set_mock(move |request| {
+                let query = request.url().query().unwrap_or_default().to_owned();
+                requests.lock().unwrap().push(query);
+                http::Response::new("").into()
+            });
+        }
+        let bucket = Bucket::new("foo", EndPoint::CN_QINGDAO);
+
+        // 含有 `&`，`=` 和空格的 id 不能破坏查询参数
+        let id = "a b&c=d";
+        bucket.delete_inventory(id, &client).await.unwrap();
+        bucket.complete_worm(id, &client).await.unwrap();
+        bucket.extend_worm(id, 1, &client).await.unwrap();
+        bucket.delete_style(id, &client).await.unwrap();
+        assert_eq!(
+            *requests.lock().unwrap(),
+            [
+                "inventory&inventoryId=a%20b%26c%3Dd",
+                "wormId=a%20b%26c%3Dd",
+                "wormExtend&wormId=a%20b%26c%3Dd",
+                "style&styleName=a%20b%26c%3Dd",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_put_transfer_acceleration() {
+        let bucket = Bucket::new("honglei123", EndPoint::CN_SHANGHAI);
+        let client = init_client();
+
+        for enabled in [true, false] {
+            bucket
+                .put_transfer_acceleration(enabled, &client)
+                .await
+                .unwrap();
+            assert_eq!(
+                bucket.get_transfer_acceleration(&client).await.unwrap(),
+                enabled
+            );
+        }
+    }
+
+    #[test]
+    fn test_transfer_acceleration_xml() {
+        use super::{parse_transfer_acceleration, transfer_acceleration_xml};
+
+        for enabled in [true, false] {
+            let xml = transfer_acceleration_xml(enabled);
+            assert!(xml.ends_with(&format!(
+                "<TransferAccelerationConfiguration><Enabled>{}</Enabled>\
+                </TransferAccelerationConfiguration>",
+                enabled
+            )));
+            assert_eq!(parse_transfer_acceleration(&xml).unwrap(), enabled);
+        }
+        assert!(parse_transfer_acceleration("<TransferAccelerationConfiguration/>").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_put_access_monitor() {
+        let bucket = Bucket::new("honglei123", EndPoint::CN_SHANGHAI);
+        let client = init_client();
+
+        for enabled in [true, false] {
+            bucket.put_access_monitor(enabled, &client).await.unwrap();
+            assert_eq!(bucket.get_access_monitor(&client).await.unwrap(), enabled);
+            let info = bucket.get_info(&client).await.unwrap();
+            assert_eq!(info.access_monitor(), Some(enabled));
+        }
+    }
+
+    #[test]
+    fn test_access_monitor_xml() {
+        use super::{access_monitor_xml, parse_access_monitor};
+
+        let xml = access_monitor_xml(true);
+        assert!(xml.ends_with(
+            "<AccessMonitorConfiguration><Status>Enabled</Status></AccessMonitorConfiguration>"
+        ));
+        for enabled in [true, false] {
+            assert_eq!(
+                parse_access_monitor(&access_monitor_xml(enabled)).unwrap(),
+                enabled
+            );
+        }
+        assert!(parse_access_monitor("<AccessMonitorConfiguration/>").is_err());
+        assert!(parse_access_monitor("<Status>On</Status>").is_err());
+    }
+
+    #[test]
+    fn test_parse_info_xml() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <BucketInfo>
+          <Bucket>
+            <AccessMonitor>Enabled</AccessMonitor>
+            <CreationDate>2016-11-05T13:10:10.000Z</CreationDate>
+            <DataRedundancyType>LRS</DataRedundancyType>
+            <Name>honglei123</Name>
+            <StorageClass>Standard</StorageClass>
+          </Bucket>
+        </BucketInfo>"#;
+        let info = Bucket::parse_info_xml(xml.to_owned()).unwrap();
+        assert_eq!(info.access_monitor(), Some(true));
+
+        let info =
+            Bucket::parse_info_xml(xml.replace("<AccessMonitor>Enabled</AccessMonitor>", ""))
+                .unwrap();
+        assert_eq!(info.access_monitor(), None);
+
+        // 无法识别的值不影响其他字段的解析
+        let info = Bucket::parse_info_xml(xml.replace("Enabled", "Unknown")).unwrap();
+        assert_eq!(info.access_monitor(), None);
+    }
+
+    #[test]
+    fn test_xml_unescape() {
+        use super::{xml_escape, xml_unescape};
+
+        let raw = r#"a&b<c>"d"'e'&lt;"#;
+        assert_eq!(xml_unescape(&xml_escape(raw)), raw);
+        assert_eq!(xml_unescape("&amp;lt;"), "&lt;");
+        assert_eq!(xml_unescape("log/"), "log/");
+    }
+
+    #[test]
+    fn test_payer_xml() {
+        use super::Payer;
+
+        for payer in [Payer::BucketOwner, Payer::Requester] {
+            let xml = payer.to_xml();
+            assert!(xml.ends_with(&format!(
+                "<RequestPaymentConfiguration><Payer>{}</Payer></RequestPaymentConfiguration>",
+                payer.as_ref()
+            )));
+            let parsed: Payer = Bucket::parse_item(&xml, "Payer").unwrap().parse().unwrap();
+            assert_eq!(parsed, payer);
+        }
+        assert!("requester".parse::<Payer>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_list_all_objects() {
+        use crate::object::Object;
+
+        let mut client = init_client();
+        let bucket = Bucket::new("honglei123", EndPoint::CN_SHANGHAI);
+        client.set_bucket(bucket.clone());
+        for name in ["list-all/1.txt", "list-all/2.txt", "list-all/3.txt"] {
+            Object::new(name).upload("a".into(), &client).await.unwrap();
+        }
+
+        let mut query = ObjectQuery::new();
+        query.insert(ObjectQuery::PREFIX, "list-all/");
+        query.insert(ObjectQuery::MAX_KEYS, "2");
+
+        let (all, token) = bucket
+            .list_all_objects(&query, None, &client)
+            .await
+            .unwrap();
+        assert_eq!(token, None);
+        assert_eq!(
+            all,
+            vec![
+                Object::new("list-all/1.txt"),
+                Object::new("list-all/2.txt"),
+                Object::new("list-all/3.txt"),
+            ]
+        );
+
+        let (capped, token) = bucket
+            .list_all_objects(&query, Some(1), &client)
+            .await
+            .unwrap();
+        assert_eq!(capped, vec![Object::new("list-all/1.txt")]);
+        assert!(token.is_some());
+    }
+
+    #[test]
+    fn test_relative_common_prefixes() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <ListBucketResult>
+          <Name>foo</Name>
+          <Prefix>fun/</Prefix>
+          <MaxKeys>100</MaxKeys>
+          <Delimiter>/</Delimiter>
+          <IsTruncated>false</IsTruncated>
+          <Contents>
+            <Key>fun/test.jpg</Key>
+            <LastModified>2012-02-24T08:42:32.000Z</LastModified>
+            <ETag>"5B3C1A2E053D763E1B002CC607C5A0FE1****"</ETag>
+            <Type>Normal</Type>
+            <Size>344606</Size>
+            <StorageClass>Standard</StorageClass>
+          </Contents>
+          <CommonPrefixes>
+            <Prefix>fun/movie/</Prefix>
+          </CommonPrefixes>
+          <CommonPrefixes>
+            <Prefix>fun/music/</Prefix>
+          </CommonPrefixes>
+        </ListBucketResult>"#;
+
+        let mut query = ObjectQuery::new();
+        query.insert(ObjectQuery::PREFIX, "fun/");
+        query.insert(ObjectQuery::DELIMITER, "/");
+        let bucket = Bucket::new("foo", EndPoint::CN_QINGDAO);
+        let objects = Bucket::parse_objects_xml(xml)
+            .unwrap()
+            .with_search(bucket, query);
+
+        assert_eq!(objects.len(), 1);
+        assert_eq!(objects.delimiter(), Some("/"));
+        assert_eq!(objects.common_prefixes(), ["fun/movie/", "fun/music/"]);
+        let folders: Vec<_> = objects.relative_common_prefixes().collect();
+        assert_eq!(folders, ["movie/", "music/"]);
+    }
+
+    #[test]
+    fn test_from_url() {
+        use url::Url;
+
+        for url in [
+            "https://foo.oss-cn-hangzhou.aliyuncs.com/path/a.txt",
+            "http://foo.oss-cn-nanjing-internal.aliyuncs.com",
+            "https://foo.cn-hangzhou.oss.aliyuncs.com",
+        ] {
+            let url = Url::parse(url).unwrap();
+            let bucket = Bucket::from_url(&url).unwrap();
+            assert_eq!(bucket.name(), "foo");
+            assert_eq!(bucket.endpoint(), &EndPoint::from_url(&url).unwrap());
+        }
+
+        for url in [
+            "https://oss-cn-hangzhou.aliyuncs.com",
+            "https://cn-hangzhou.oss.aliyuncs.com",
+            "https://foo.bar.oss-cn-hangzhou.aliyuncs.com",
+            "https://foo.example.com",
+        ] {
+            assert!(Bucket::from_url(&Url::parse(url).unwrap()).is_err());
+        }
+    }
+
+    #[test]
+    fn test_url_encoded_keys() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <ListBucketResult>
+          <Name>foo</Name>
+          <Prefix>fun%2F</Prefix>
+          <MaxKeys>100</MaxKeys>
+          <Delimiter>%2F</Delimiter>
+          <EncodingType>url</EncodingType>
+          <IsTruncated>false</IsTruncated>
+          <Contents>
+            <Key>fun%2Fa%26b%3Dc%0Ad.txt</Key>
+            <LastModified>2012-02-24T08:42:32.000Z</LastModified>
+            <ETag>"5B3C1A2E053D763E1B002CC607C5A0FE1****"</ETag>
+            <Type>Normal</Type>
+            <Size>344606</Size>
+            <StorageClass>Standard</StorageClass>
+          </Contents>
+          <CommonPrefixes>
+            <Prefix>fun%2Fx%3Dy%2F</Prefix>
+          </CommonPrefixes>
+        </ListBucketResult>"#;
+
+        let mut query = ObjectQuery::new();
+        query.url_encode(true);
+        assert_eq!(query.get(ObjectQuery::ENCODING_TYPE).unwrap(), "url");
+
+        let objects = Bucket::parse_objects_xml(xml).unwrap();
+        assert_eq!(objects[0].get_path(), "fun/a&b=c\nd.txt");
+        assert_eq!(objects.delimiter(), Some("/"));
+        assert_eq!(objects.common_prefixes(), ["fun/x=y/"]);
+
+        let infos = Bucket::parse_object_infos_xml(xml).unwrap();
+        assert_eq!(infos[0].0.get_path(), "fun/a&b=c\nd.txt");
+
+        assert!(Bucket::parse_object_info_xml(xml, "fun/a&b=c\nd.txt")
+            .unwrap()
+            .is_some());
+
+        // 没有 `EncodingType` 时保持原样
+        let plain = xml.replace("<EncodingType>url</EncodingType>", "");
+        let objects = Bucket::parse_objects_xml(&plain).unwrap();
+        assert_eq!(objects[0].get_path(), "fun%2Fa%26b%3Dc%0Ad.txt");
+    }
+
+    #[test]
+    fn test_parse_objects_with_owner_xml() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <ListBucketResult>
+          <Name>foo</Name>
+          <Prefix></Prefix>
+          <MaxKeys>100</MaxKeys>
+          <IsTruncated>true</IsTruncated>
+          <NextContinuationToken>CgJiYw--</NextContinuationToken>
+          <Contents>
+            <Key>fun/movie/001.avi</Key>
+            <LastModified>2012-02-24T08:43:07.000Z</LastModified>
+            <ETag>"5B3C1A2E053D763E1B002CC607C5A0FE1****"</ETag>
+            <Type>Normal</Type>
+            <Size>344606</Size>
+            <StorageClass>Standard</StorageClass>
+            <Owner>
+              <ID>0022012****</ID>
+              <DisplayName>user-example</DisplayName>
+            </Owner>
+          </Contents>
+          <Contents>
+            <Key>fun/test.jpg</Key>
+            <LastModified>2012-02-24T08:42:32.000Z</LastModified>
+            <ETag>"5B3C1A2E053D763E1B002CC607C5A0FE1****"</ETag>
+            <Type>Normal</Type>
+            <Size>344606</Size>
+            <StorageClass>IA</StorageClass>
+            <Owner>
+              <ID>0022012****</ID>
+              <DisplayName>user-example2</DisplayName>
+            </Owner>
+          </Contents>
+        </ListBucketResult>"#;
+
+        let list = Bucket::parse_object_infos_xml(xml).unwrap();
+
+        assert_eq!(list.next_token(), Some("CgJiYw--"));
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[0].0.get_path(), "fun/movie/001.avi");
+        assert_eq!(list[1].0.get_path(), "fun/test.jpg");
+        assert_eq!(list[1].1.storage_class(), Some(StorageClass::IA));
+
+        let owners: Vec<_> = list
+            .iter()
+            .map(|(_, info)| info.owner().unwrap().clone())
+            .collect();
+        assert_eq!(
+            owners,
+            [
+                Owner::new("0022012****", "user-example"),
+                Owner::new("0022012****", "user-example2")
+            ]
+        );
+
+        let map = list.into_map();
+        assert_eq!(map.len(), 2);
+        let info = &map[&Object::new("fun/test.jpg")];
+        assert_eq!(info.storage_class(), Some(StorageClass::IA));
+        assert_eq!(info.owner().unwrap().display_name(), "user-example2");
+        let info = &map[&Object::new("fun/movie/001.avi")];
+        assert_eq!(info.storage_class(), Some(StorageClass::STANDARD));
+        assert!(!map.contains_key(&Object::new("fun/")));
+    }
+
+    #[tokio::test]
+    async fn test_total_size() {
+        let page = |token: &str, sizes: &[u64]| {
+            let contents: String = sizes
+                .iter()
+                .enumerate()
+                .map(|(i, size)| {
+                    format!(
+                        "<Contents><Key>{i}.txt</Key>\
+                        <LastModified>2022-06-26T09:53:21.000Z</LastModified>\
+                        <ETag>\"F75A15996D0857B16FA31A3B16624C26\"</ETag><Size>{size}</Size></Contents>"
+                    )
+                })
+                .collect();
+            let token = if token.is_empty() {
+                String::new()
+            } else {
+                format!("<NextContinuationToken>{token}</NextContinuationToken>")
+            };
+            format!("<ListBucketResult>{contents}{token}</ListBucketResult>")
+        };
+        let first = page("CgJiYw--", &[10, 20]);
+        let second = page("", &[5]);
+
+        assert_eq!(
+            Bucket::parse_object_infos_xml(&first).unwrap().total_size(),
+            30
+        );
+
+        let mut tokens = Vec::new();
+        let total = super::sum_pages(&ObjectQuery::new(), |query| {
+            let token = query.get(ObjectQuery::CONTINUATION_TOKEN).cloned();
+            tokens.push(token.clone());
+            let xml = if token.is_some() { &second } else { &first };
+            let page = Bucket::parse_object_infos_xml(xml);
+            async move { page }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(total, 35);
+        assert_eq!(tokens, [None, Some("CgJiYw--".to_owned())]);
+    }
+
+    #[tokio::test]
+    async fn test_take_pages() {
+        // 共 12 个文件，token 为下一页起始文件的序号
+        let page = |query: &ObjectQuery| {
+            let start: usize = query
+                .get(ObjectQuery::CONTINUATION_TOKEN)
+                .map_or(0, |t| t.parse().unwrap());
+            let max: usize = query.get(ObjectQuery::MAX_KEYS).unwrap().parse().unwrap();
+            let end = (start + max).min(12);
+            let contents: String = (start..end)
+                .map(|i| {
+                    format!(
+                        "<Contents><Key>{i}.txt</Key>\
+                        <LastModified>2022-06-26T09:53:21.000Z</LastModified>\
+                        <ETag>\"F75A15996D0857B16FA31A3B16624C26\"</ETag><Size>1</Size></Contents>"
+                    )
+                })
+                .collect();
+            let token = if end < 12 {
+                format!("<NextContinuationToken>{end}</NextContinuationToken>")
+            } else {
+                String::new()
+            };
+            format!("<ListBucketResult>{contents}{token}</ListBucketResult>")
+        };
+
+        let mut query = ObjectQuery::new();
+        query.insert(ObjectQuery::MAX_KEYS, "5");
+        let mut sizes = Vec::new();
+        let (list, token) = super::take_pages(&query, 7, |query| {
+            sizes.push(query.get(ObjectQuery::MAX_KEYS).cloned().unwrap());
+            let page = Bucket::parse_object_infos_xml(&page(&query));
+            async move { page }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(list.len(), 7);
+        assert_eq!(list[6].0, Object::new("6.txt"));
+        assert_eq!(sizes, ["5", "2"]);
+        assert_eq!(token.as_deref(), Some("7"));
+
+        query.insert_next_token(token.unwrap());
+        let (list, token) = super::take_pages(&query, 7, |query| {
+            let page = Bucket::parse_object_infos_xml(&page(&query));
+            async move { page }
+        })
+        .await
+        .unwrap();
+        assert_eq!(list.len(), 5);
+        assert_eq!(list[0].0, Object::new("7.txt"));
+        assert_eq!(token, None);
+    }
+
+    #[tokio::test]
+    async fn test_list_all_objects_max() {
+        use crate::{
+            types::{Key, Secret},
+            Client,
+        };
+        use std::sync::{Arc, Mutex};
+
+        // 共 12 个文件，token 为下一页起始文件的序号
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let mut client = Client::new(Key::new("foo"), Secret::new("bar"));
+        {
+            let requests = requests.clone();
+            client.set_mock(move |request| {
+                let query: std::collections::HashMap<_, _> =
+                    request.url().query_pairs().into_owned().collect();
+                let start: usize = query
+                    .get("continuation-token")
+                    .map_or(0, |t| t.parse().unwrap());
+                let max: usize = query["max-keys"].parse().unwrap();
+                requests.lock().unwrap().push(max);
+                let end = (start + max).min(12);
+                let contents: String = (start..end)
+                    .map(|i| format!("<Contents><Key>{i}.txt</Key></Contents>"))
+                    .collect();
+                let token = if end < 12 {
+                    format!("<NextContinuationToken>{end}</NextContinuationToken>")
+                } else {
+                    String::new()
+                };
+                http::Response::new(format!(
+                    "<ListBucketResult>{contents}{token}</ListBucketResult>"
+                ))
+                .into()
+            });
+        }
+        let bucket = Bucket::new("foo", EndPoint::CN_QINGDAO);
+        let mut query = ObjectQuery::new();
+        query.insert(ObjectQuery::MAX_KEYS, "5");
+
+        // 最后一页只请求剩余的数量，返回的 token 可以继续列举
+        let (list, token) = bucket
+            .list_all_objects(&query, Some(7), &client)
+            .await
+            .unwrap();
+        assert_eq!(list.len(), 7);
+        assert_eq!(list[6], Object::new("6.txt"));
+        assert_eq!(token.as_deref(), Some("7"));
+        assert_eq!(*requests.lock().unwrap(), [5, 2]);
+
+        requests.lock().unwrap().clear();
+        let (list, token) = bucket
+            .list_all_objects(&query, None, &client)
+            .await
+            .unwrap();
+        assert_eq!(list.len(), 12);
+        assert_eq!(token, None);
+        assert_eq!(*requests.lock().unwrap(), [5, 5, 5]);
+    }
+
+    #[tokio::test]
+    async fn test_list_objects_v1_marker() {
+        use crate::{
+            types::{Key, Secret},
+            Client,
+        };
+        use std::sync::{Arc, Mutex};
+
+        // V1 接口翻页使用 marker，第一页的 NextMarker 中含有需要编码的字符
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let mut client = Client::new(Key::new("foo"), Secret::new("bar"));
+        {
+            let requests = requests.clone();
+            client.set_mock(move |request| {
+                let mut query: Vec<_> = request.url().query_pairs().into_owned().collect();
+                query.sort();
+                let xml = if query.iter().any(|(k, _)| k == "marker") {
+                    "<ListBucketResult><IsTruncated>false</IsTruncated>\
+                    <Contents><Key>c.txt</Key></Contents></ListBucketResult>"
+                } else {
+                    "<ListBucketResult><IsTruncated>true</IsTruncated>\
+                    <NextMarker>a b+.txt</NextMarker>\
+                    <Contents><Key>a.txt</Key></Contents>\
+                    <Contents><Key>a b+.txt</Key></Contents></ListBucketResult>"
+                };
+                requests.lock().unwrap().push(query);
+                http::Response::new(xml).into()
+            });
+        }
+        let bucket = Bucket::new("foo", EndPoint::CN_QINGDAO);
+        let mut query = ObjectQuery::new();
+        query.without_list_type();
+        query.insert(ObjectQuery::PREFIX, "a");
+
+        let first = bucket.get_objects(&query, &client).await.unwrap();
+        assert_eq!(first.len(), 2);
+        let second = first.next_page(&client).await.unwrap().unwrap();
+        assert_eq!(second.get_vec(), &[Object::new("c.txt")]);
+        assert!(second.next_page(&client).await.unwrap().is_none());
+
+        let pair = |k: &str, v: &str| (k.to_owned(), v.to_owned());
+        assert_eq!(
+            *requests.lock().unwrap(),
+            [
+                vec![pair("prefix", "a")],
+                vec![pair("marker", "a b+.txt"), pair("prefix", "a")],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_object_infos_xml() {
+        use chrono::{TimeZone, Utc};
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <ListBucketResult>
+          <Name>foo</Name>
+          <Prefix></Prefix>
+          <MaxKeys>100</MaxKeys>
+          <Delimiter></Delimiter>
+          <IsTruncated>false</IsTruncated>
+          <Contents>
+            <Key>9AB932LY.jpeg</Key>
+            <LastModified>2022-06-26T09:53:21.000Z</LastModified>
+            <ETag>"F75A15996D0857B16FA31A3B16624C26"</ETag>
+            <Type>Normal</Type>
+            <Size>18027</Size>
+            <StorageClass>Archive</StorageClass>
+          </Contents>
+        </ListBucketResult>"#;
+
+        let list = Bucket::parse_object_infos_xml(xml).unwrap();
+        assert_eq!(Bucket::parse_objects_xml(xml).unwrap().delimiter(), None);
+
+        assert!(list.next_token().is_none());
+        assert_eq!(list.len(), 1);
+        let (object, info) = &list[0];
+        assert_eq!(object.get_path(), "9AB932LY.jpeg");
+        assert_eq!(
+            info.last_modified(),
+            &Utc.with_ymd_and_hms(2022, 6, 26, 9, 53, 21).unwrap()
+        );
+        assert_eq!(info.etag(), "\"F75A15996D0857B16FA31A3B16624C26\"");
+        assert_eq!(info.size(), 18027);
+        assert_eq!(info.storage_class(), Some(StorageClass::ARCHIVE));
+        assert!(info.owner().is_none());
+    }
+
+    #[test]
+    fn test_empty_prefix_and_duplicate_common_prefixes() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <ListBucketResult>
+          <Name>foo</Name>
+          <Prefix></Prefix>
+          <Delimiter>/</Delimiter>
+          <CommonPrefixes>
+            <Prefix>foo2/</Prefix>
+          </CommonPrefixes>
+          <CommonPrefixes>
+            <Prefix>foo2/</Prefix>
+          </CommonPrefixes>
+          <CommonPrefixes>
+            <Prefix>foo3/</Prefix>
+          </CommonPrefixes>
+        </ListBucketResult>"#;
+
+        let mut query = ObjectQuery::new();
+        query.insert(ObjectQuery::PREFIX, "");
+        let bucket = Bucket::new("foo", EndPoint::CN_QINGDAO);
+        let objects = Bucket::parse_objects_xml(xml)
+            .unwrap()
+            .with_search(bucket.clone(), query);
+
+        assert_eq!(objects.prefix(), None);
+        assert_eq!(objects.common_prefixes(), ["foo2/", "foo3/"]);
+
+        let mut query = ObjectQuery::new();
+        query.insert(ObjectQuery::PREFIX, "foo2/");
+        let objects = Bucket::parse_objects_xml(xml)
+            .unwrap()
+            .with_search(bucket, query);
+        assert_eq!(objects.prefix(), Some("foo2/"));
+    }
+
+    #[test]
+    fn test_delete_objects_request() {
+        use super::delete_objects_request;
+        use crate::types::ContentMd5;
+
+        let objects = [Object::new("a.txt"), Object::new("b&c/<d>.txt")];
+        let (headers, body) = delete_objects_request(&objects).unwrap();
+
+        assert_eq!(
+            String::from_utf8(body.clone()).unwrap(),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?><Delete><Quiet>true</Quiet>\
+            <Object><Key>a.txt</Key></Object>\
+            <Object><Key>b&amp;c/&lt;d&gt;.txt</Key></Object></Delete>"
+        );
+        assert_eq!(
+            headers.get("content-md5").unwrap(),
+            ContentMd5::from_bytes(&body).as_str()
+        );
+    }
+
+    #[test]
+    fn test_delete_versions_request() {
+        use super::delete_versions_request;
+        use crate::object::{DeleteMarker, ObjectVersion, VersionEntry};
+        use crate::types::ContentMd5;
+        use chrono::Utc;
+
+        let entries = [
+            VersionEntry::Version(ObjectVersion::new(
+                "a.txt".to_owned(),
+                "CAEQMxiBgMDNoP2D0BYiIDE3MWUx".to_owned(),
+                false,
+                Utc::now(),
+                "\"A\"".to_owned(),
+                1,
+            )),
+            VersionEntry::DeleteMarker(DeleteMarker::new(
+                "a.txt".to_owned(),
+                "CAEQMxiBgICAof2D0BYiIDJhMGE3".to_owned(),
+                true,
+                Utc::now(),
+            )),
+        ];
+        let (headers, body) = delete_versions_request(&entries).unwrap();
+
+        assert_eq!(
+            String::from_utf8(body.clone()).unwrap(),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?><Delete><Quiet>true</Quiet>\
+            <Object><Key>a.txt</Key><VersionId>CAEQMxiBgMDNoP2D0BYiIDE3MWUx</VersionId></Object>\
+            <Object><Key>a.txt</Key><VersionId>CAEQMxiBgICAof2D0BYiIDJhMGE3</VersionId></Object>\
+            </Delete>"
+        );
+        assert_eq!(
+            headers.get("content-md5").unwrap(),
+            ContentMd5::from_bytes(&body).as_str()
+        );
+    }
+
+    #[test]
+    fn test_list_buckets() {
+        use super::ListBuckets;
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <ListAllMyBucketsResult>
+          <Owner>
+            <ID>512**</ID>
+            <DisplayName>51264</DisplayName>
+          </Owner>
+          <Buckets>
+            <Bucket>
+              <CreationDate>2015-12-17T18:12:43.000Z</CreationDate>
+              <ExtranetEndpoint>oss-cn-shanghai.aliyuncs.com</ExtranetEndpoint>
+              <IntranetEndpoint>oss-cn-shanghai-internal.aliyuncs.com</IntranetEndpoint>
+              <Location>oss-cn-shanghai</Location>
+              <Name>app-base-oss</Name>
+              <Region>cn-shanghai</Region>
+              <StorageClass>Standard</StorageClass>
+            </Bucket>
+            <Bucket>
+              <CreationDate>2014-12-25T11:21:04.000Z</CreationDate>
+              <ExtranetEndpoint>oss-cn-hangzhou.aliyuncs.com</ExtranetEndpoint>
+              <IntranetEndpoint>oss-cn-hangzhou-internal.aliyuncs.com</IntranetEndpoint>
+              <Location>oss-cn-hangzhou</Location>
+              <Name>atestleo23</Name>
+              <Region>cn-hangzhou</Region>
+              <StorageClass>IA</StorageClass>
+            </Bucket>
+          </Buckets>
+        </ListAllMyBucketsResult>"#;
+
+        let list = ListBuckets::from_xml(xml).unwrap();
+        assert_eq!(list.len(), 2);
+        assert!(!list.is_empty());
+
+        let names: Vec<_> = list.iter().map(|b| b.name()).collect();
+        assert_eq!(names, ["app-base-oss", "atestleo23"]);
+
+        let endpoints: Vec<_> = list.into_iter().map(|b| b.endpoint().clone()).collect();
+        assert_eq!(endpoints, [EndPoint::CN_SHANGHAI, EndPoint::CN_HANGZHOU]);
+    }
+
+    #[test]
+    fn test_network_endpoint() {
+        use crate::object::Object;
+        use crate::types::{CanonicalizedResource, Network};
+
+        let mut endpoint = EndPoint::CN_HANGZHOU;
+        endpoint.set_network(Network::Accelerate);
+        let bucket = Bucket::new("foo", endpoint);
+        let object = Object::new("a/b.txt");
+        assert_eq!(
+            object.to_url(&bucket).as_str(),
+            "https://foo.oss-accelerate.aliyuncs.com/a/b.txt"
+        );
+        assert_eq!(
+            CanonicalizedResource::from_object(&bucket, &object).as_str(),
+            "/foo/a/b.txt"
+        );
+        assert_eq!(bucket.endpoint().region_id(), "cn-hangzhou");
+
+        let mut endpoint = EndPoint::CN_HANGZHOU;
+        endpoint.set_network(Network::DualStack);
+        let bucket = Bucket::new("foo", endpoint);
+        assert_eq!(
+            bucket.to_url().as_str(),
+            "https://foo.cn-hangzhou.oss.aliyuncs.com/"
+        );
+    }
+
+    #[test]
+    fn test_transfer_acceleration() {
+        use crate::object::Object;
+        use crate::types::CanonicalizedResource;
+
+        let bucket = Bucket::new("foo", EndPoint::CN_SHANGHAI).with_transfer_acceleration(true);
+        assert!(bucket.transfer_acceleration());
+        let object = Object::new("a/b.txt");
+        assert_eq!(
+            object.to_url(&bucket).as_str(),
+            "https://foo.oss-accelerate.aliyuncs.com/a/b.txt"
+        );
+        assert_eq!(
+            CanonicalizedResource::from_object(&bucket, &object).as_str(),
+            "/foo/a/b.txt"
+        );
+        // bucket 相关的请求仍使用原来的 endpoint
+        assert_eq!(
+            bucket.to_url().as_str(),
+            "https://foo.oss-cn-shanghai.aliyuncs.com/"
+        );
+
+        let bucket = bucket.with_transfer_acceleration_overseas(true);
+        assert_eq!(
+            object.to_url(&bucket).as_str(),
+            "https://foo.oss-accelerate-overseas.aliyuncs.com/a/b.txt"
+        );
+
+        let bucket = bucket.with_transfer_acceleration(false);
+        assert!(!bucket.transfer_acceleration());
+        assert_eq!(
+            object.to_url(&bucket).as_str(),
+            "https://foo.oss-cn-shanghai.aliyuncs.com/a/b.txt"
+        );
+    }
+
+    #[test]
+    fn test_custom_domain() {
+        use crate::object::Object;
+        use url::Url;
+
+        let bucket = Bucket::new("foo", EndPoint::CN_QINGDAO)
+            .with_custom_domain("https://static.example.com/")
+            .with_host_header("cdn.example.com");
+
+        assert_eq!(bucket.custom_domain(), Some("static.example.com"));
+        let url = Object::new("a/b.txt").to_url(&bucket);
+        assert_eq!(url.host_str(), Some("static.example.com"));
+        assert_eq!(url.as_str(), "https://static.example.com/a/b.txt");
+
+        assert_eq!(bucket.host_header_for(&url), Some("cdn.example.com"));
+        let other = Url::parse("https://foo.oss-cn-qingdao.aliyuncs.com/a.txt").unwrap();
+        assert_eq!(bucket.host_header_for(&other), None);
+        assert_eq!(
+            Bucket::new("foo", EndPoint::CN_QINGDAO).host_header_for(&url),
+            None
+        );
+    }
+
+    #[test]
+    fn test_create_bucket_options() {
+        use super::{CreateBucketOptions, DataRedundancyType, Grant};
+
+        let options = CreateBucketOptions::default();
+        assert_eq!(
+            options.headers().unwrap().get("x-oss-acl").unwrap(),
+            "private"
+        );
+        let body = options.body();
+        assert!(body.contains("<StorageClass>Standard</StorageClass>"));
+        assert!(body.contains("<DataRedundancyType>LRS</DataRedundancyType>"));
+
+        let options = CreateBucketOptions::default()
+            .acl(Grant::PublicRead)
+            .storage_class(StorageClass::IA)
+            .data_redundancy_type(DataRedundancyType::ZRS);
+        assert_eq!(
+            options.headers().unwrap().get("x-oss-acl").unwrap(),
+            "public-read"
+        );
+        let body = options.body();
+        assert!(body.contains("<StorageClass>IA</StorageClass>"));
+        assert!(body.contains("<DataRedundancyType>ZRS</DataRedundancyType>"));
+    }
+
+    #[test]
+    fn test_parse_object_info_xml() {
+        use crate::types::StorageClass;
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult>
+  <Name>foo</Name>
+  <Prefix>abc.txt</Prefix>
+  <Contents>
+    <Key>abc.txt</Key>
+    <LastModified>2012-02-24T08:42:32.000Z</LastModified>
+    <ETag>"5B3C1A2E053D763E1B002CC607C5A0FE"</ETag>
+    <Size>344606</Size>
+    <StorageClass>IA</StorageClass>
+  </Contents>
+  <Contents>
+    <Key>abc.txt.bak</Key>
+    <LastModified>2012-02-24T08:43:07.000Z</LastModified>
+    <ETag>"5B3C1A2E053D763E1B002CC607C5A0FE"</ETag>
+    <Size>1</Size>
+    <StorageClass>Standard</StorageClass>
+  </Contents>
+</ListBucketResult>"#;
+
+        let info = Bucket::parse_object_info_xml(xml, "abc.txt")
+            .unwrap()
+            .unwrap();
+        assert_eq!(info.size(), 344606);
+        assert_eq!(info.etag(), "\"5B3C1A2E053D763E1B002CC607C5A0FE\"");
+        assert_eq!(info.storage_class(), Some(StorageClass::IA));
+        assert_eq!(
+            info.last_modified().to_rfc3339(),
+            "2012-02-24T08:42:32+00:00"
+        );
+
+        let info = Bucket::parse_object_info_xml(xml, "abc.txt.bak")
+            .unwrap()
+            .unwrap();
+        assert_eq!(info.size(), 1);
+
+        assert!(Bucket::parse_object_info_xml(xml, "abc").unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_delete_if_empty() {
+        let bucket = Bucket::new("honglei123", EndPoint::CN_SHANGHAI);
+
+        let res = bucket.delete_if_empty(&init_client()).await;
+        assert!(matches!(res, Err(crate::Error::BucketNotEmpty)));
+    }
+
+    #[tokio::test]
+    async fn test_decode_list_response() {
+        use super::{decode_bucket_response, decode_list_response};
+
+        fn response(status: u16, body: impl Into<reqwest::Body>) -> reqwest::Response {
+            http::Response::builder()
+                .status(status)
+                .body(body)
+                .unwrap()
+                .into()
+        }
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct MyObject {
+            #[serde(rename = "Key")]
+            key: String,
+        }
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult>
+  <Name>foo</Name>
+  <Contents><Key>a.txt</Key><Size>1</Size></Contents>
+  <Contents><Key>b.txt</Key><Size>2</Size></Contents>
+  <NextContinuationToken>token</NextContinuationToken>
+</ListBucketResult>"#;
+        let (list, token) = decode_list_response::<MyObject>(response(200, xml))
+            .await
+            .unwrap();
+        assert_eq!(
+            list,
+            vec![
+                MyObject {
+                    key: "a.txt".into()
+                },
+                MyObject {
+                    key: "b.txt".into()
+                }
+            ]
+        );
+        assert_eq!(token.as_deref(), Some("token"));
+
+        #[derive(Debug, Deserialize)]
+        struct MyBucket {
+            #[serde(rename = "Name")]
+            name: String,
+        }
+        let xml = "<BucketInfo><Bucket><Name>foo</Name></Bucket></BucketInfo>";
+        let bucket: MyBucket = decode_bucket_response(response(200, xml)).await.unwrap();
+        assert_eq!(bucket.name, "foo");
+
+        let res = decode_list_response::<MyObject>(response(200, vec![0xff, 0xfe])).await;
+        assert!(matches!(res, Err(crate::Error::FromUtf8(_))));
+
+        let xml = "<Error><Code>NoSuchBucket</Code><Message>not exist</Message><RequestId>1</RequestId></Error>";
+        let res = decode_list_response::<MyObject>(response(404, xml)).await;
+        assert!(matches!(res, Err(crate::Error::Service(e)) if e.code() == "NoSuchBucket"));
+    }
+
+    #[tokio::test]
+    async fn test_gzip_list_response() {
+        use crate::client::read_xml;
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult>
+  <Name>foo</Name>
+  <Contents><Key>a.txt</Key><Size>1</Size></Contents>
+  <Contents><Key>b.txt</Key><Size>2</Size></Contents>
+  <NextContinuationToken>token</NextContinuationToken>
+</ListBucketResult>"#;
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(xml.as_bytes()).unwrap();
+        let gzip = encoder.finish().unwrap();
+
+        let response: reqwest::Response = http::Response::builder()
+            .header("content-encoding", "gzip")
+            .body(gzip)
+            .unwrap()
+            .into();
+        let content = read_xml(response).await.unwrap();
+        let objects = Bucket::parse_objects_xml(&content).unwrap();
+        assert_eq!(
+            objects.get_vec(),
+            &vec![Object::new("a.txt"), Object::new("b.txt")]
+        );
+        assert_eq!(objects.next_token().map(String::as_str), Some("token"));
+
+        let response: reqwest::Response = http::Response::builder().body(xml).unwrap().into();
+        assert_eq!(read_xml(response).await.unwrap(), xml);
     }
 
     #[tokio::test]
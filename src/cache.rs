@@ -0,0 +1,209 @@
+//! 基于 ETag 的下载缓存
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+};
+
+use reqwest::{
+    header::{HeaderMap, IF_NONE_MATCH},
+    Response, StatusCode,
+};
+
+use crate::error::OssError;
+
+/// 在内存中缓存文件内容及其 ETag，配合 [`Object::download_cached`] 使用
+///
+/// 再次下载时会携带 `If-None-Match`，文件没有变化时 OSS 返回 304，直接使用缓存的内容。
+/// 缓存的文件数量超过 `capacity` 时，最早缓存的文件会被移除。不同 bucket 中的同名文件分别缓存
+///
+/// [`Object::download_cached`]: crate::Object::download_cached
+#[derive(Debug)]
+pub struct DownloadCache {
+    capacity: usize,
+    inner: Mutex<Entries>,
+}
+
+/// 缓存的 key，为 bucket 名称和文件路径
+type CacheKey = (String, String);
+
+#[derive(Debug, Default)]
+struct Entries {
+    map: HashMap<CacheKey, (String, Vec<u8>)>,
+    order: VecDeque<CacheKey>,
+}
+
+fn cache_key(bucket: &str, path: &str) -> CacheKey {
+    (bucket.to_owned(), path.to_owned())
+}
+
+impl DownloadCache {
+    pub fn new(capacity: usize) -> DownloadCache {
+        DownloadCache {
+            capacity,
+            inner: Mutex::new(Entries::default()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 清空缓存
+    pub fn clear(&self) {
+        let mut entries = self.lock();
+        entries.map.clear();
+        entries.order.clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Entries> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 已缓存该文件时，返回携带 `If-None-Match` 的 header
+    pub(crate) fn headers(&self, bucket: &str, path: &str) -> Result<HeaderMap, OssError> {
+        let mut headers = HeaderMap::new();
+        if let Some((etag, _)) = self.lock().map.get(&cache_key(bucket, path)) {
+            headers.insert(IF_NONE_MATCH, etag.try_into()?);
+        }
+        Ok(headers)
+    }
+
+    /// 读取下载的响应，304 时返回缓存的内容，2xx 时更新缓存
+    pub(crate) async fn read_response(
+        &self,
+        bucket: &str,
+        path: &str,
+        response: Response,
+    ) -> Result<Vec<u8>, OssError> {
+        let key = cache_key(bucket, path);
+        let status = response.status();
+        if status == StatusCode::NOT_MODIFIED {
+            if let Some((_, content)) = self.lock().map.get(&key) {
+                return Ok(content.clone());
+            }
+        }
+        if !status.is_success() {
+            return Err(OssError::from_status(status, &response.text().await?));
+        }
+
+        let etag = response
+            .headers()
+            .get("etag")
+            .map(|v| v.to_str().map(|s| s.to_owned()))
+            .transpose()?;
+        let content = response.bytes().await?.to_vec();
+        if let Some(etag) = etag {
+            self.insert(key, etag, content.clone());
+        }
+
+        Ok(content)
+    }
+
+    fn insert(&self, key: CacheKey, etag: String, content: Vec<u8>) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut entries = self.lock();
+        if entries.map.insert(key.clone(), (etag, content)).is_none() {
+            entries.order.push_back(key);
+        }
+        while entries.map.len() > self.capacity {
+            match entries.order.pop_front() {
+                Some(oldest) => {
+                    entries.map.remove(&oldest);
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DownloadCache;
+
+    fn response(status: u16, etag: &str, body: &'static str) -> reqwest::Response {
+        http::Response::builder()
+            .status(status)
+            .header("etag", etag)
+            .body(body)
+            .unwrap()
+            .into()
+    }
+
+    #[tokio::test]
+    async fn test_read_response() {
+        let cache = DownloadCache::new(10);
+        assert!(cache.headers("foo", "a.txt").unwrap().is_empty());
+
+        let content = cache
+            .read_response("foo", "a.txt", response(200, "\"abc\"", "hello"))
+            .await
+            .unwrap();
+        assert_eq!(content, b"hello");
+        assert_eq!(cache.len(), 1);
+        assert_eq!(
+            cache
+                .headers("foo", "a.txt")
+                .unwrap()
+                .get("if-none-match")
+                .unwrap(),
+            "\"abc\""
+        );
+
+        let content = cache
+            .read_response("foo", "a.txt", response(304, "\"abc\"", ""))
+            .await
+            .unwrap();
+        assert_eq!(content, b"hello");
+    }
+
+    #[tokio::test]
+    async fn test_same_path_in_buckets() {
+        let cache = DownloadCache::new(10);
+        cache
+            .read_response("foo", "a.txt", response(200, "\"abc\"", "hello"))
+            .await
+            .unwrap();
+        assert!(cache.headers("bar", "a.txt").unwrap().is_empty());
+
+        cache
+            .read_response("bar", "a.txt", response(200, "\"def\"", "world"))
+            .await
+            .unwrap();
+        assert_eq!(cache.len(), 2);
+
+        let content = cache
+            .read_response("foo", "a.txt", response(304, "\"abc\"", ""))
+            .await
+            .unwrap();
+        assert_eq!(content, b"hello");
+        let content = cache
+            .read_response("bar", "a.txt", response(304, "\"def\"", ""))
+            .await
+            .unwrap();
+        assert_eq!(content, b"world");
+    }
+
+    #[tokio::test]
+    async fn test_capacity() {
+        let cache = DownloadCache::new(2);
+        for path in ["a", "b", "c"] {
+            cache
+                .read_response("foo", path, response(200, "\"1\"", "x"))
+                .await
+                .unwrap();
+        }
+        assert_eq!(cache.len(), 2);
+        assert!(cache.headers("foo", "a").unwrap().is_empty());
+        assert!(!cache.headers("foo", "c").unwrap().is_empty());
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+}
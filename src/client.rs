@@ -1,35 +1,238 @@
-use std::env::VarError;
+use std::{
+    env::VarError,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, RwLock, RwLockReadGuard},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
 
-use chrono::Utc;
+use chrono::{DateTime, SecondsFormat, Utc};
+use futures_util::{stream, StreamExt};
 use reqwest::{
-    header::{HeaderMap, HeaderValue, CONTENT_TYPE},
-    Method,
+    header::{
+        HeaderMap, HeaderValue, ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_LENGTH, DATE, HOST,
+        USER_AGENT,
+    },
+    Method, RequestBuilder, Response,
 };
+use reqwest::{Body, ResponseBuilderExt};
 use serde::{de::DeserializeOwned, Deserialize};
 use serde_xml_rs::from_str;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use url::Url;
 
 use crate::{
-    bucket::Bucket,
+    auth::Auth,
+    bucket::{Bucket, ListBuckets},
     error::OssError,
+    object::{decode_content, Object, RestoreStatus, DOWNLOAD_PART_SIZE},
     types::{CanonicalizedResource, EndPoint, Key, Secret},
 };
 
+const LOCATION: &str = "location";
+
+/// 批量解冻文件时，同时发送的请求数量
+pub const RESTORE_CONCURRENCY: usize = 8;
+
+/// 分片大小的最小值，100KB
+pub const MIN_PART_SIZE: u64 = 100 * 1024;
+
+/// 分片大小的最大值，5GB
+pub const MAX_PART_SIZE: u64 = 5 * 1024 * 1024 * 1024;
+
+fn valid_part_size(size: u64) -> Result<u64, OssError> {
+    if (MIN_PART_SIZE..=MAX_PART_SIZE).contains(&size) {
+        Ok(size)
+    } else {
+        Err(OssError::InvalidPartSize)
+    }
+}
+
+/// [`Client`] 的构建器，未设置的选项与 [`Client::new`] 的默认值相同
+/// ```
+/// # use aliyun_oss_client::{Bucket, Client, EndPoint, Key, Secret};
+/// # use std::time::Duration;
+/// let client = Client::builder()
+///     .key(Key::new("foo"))
+///     .secret(Secret::new("bar"))
+///     .bucket(Bucket::new("honglei123", EndPoint::CN_SHANGHAI))
+///     .internal(true)
+///     .timeout(Duration::from_secs(30))
+///     .user_agent("my-app/1.0")
+///     .build()
+///     .unwrap();
+/// assert!(client.bucket().unwrap().endpoint().is_internal());
+///
+/// assert!(Client::builder().key(Key::new("foo")).build().is_err());
+/// ```
+#[derive(Debug, Default)]
+pub struct ClientBuilder {
+    key: Option<Key>,
+    secret: Option<Secret>,
+    security_token: Option<String>,
+    bucket: Option<Bucket>,
+    internal: Option<bool>,
+    timeout: Option<Duration>,
+    user_agent: Option<String>,
+    follow_redirect: bool,
+    default_headers: HeaderMap,
+    max_concurrent_requests: Option<usize>,
+    fallback_endpoints: Vec<EndPoint>,
+}
+
+impl ClientBuilder {
+    pub fn key(mut self, key: Key) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    pub fn secret(mut self, secret: Secret) -> Self {
+        self.secret = Some(secret);
+        self
+    }
+
+    /// STS 临时凭证的 security token
+    pub fn security_token<T: Into<String>>(mut self, token: T) -> Self {
+        self.security_token = Some(token.into());
+        self
+    }
+
+    /// 默认 bucket
+    pub fn bucket(mut self, bucket: Bucket) -> Self {
+        self.bucket = Some(bucket);
+        self
+    }
+
+    /// 是否通过内网访问默认 bucket，不设置时使用 bucket 本身的设置
+    pub fn internal(mut self, internal: bool) -> Self {
+        self.internal = Some(internal);
+        self
+    }
+
+    /// 每个请求的超时时间，包括读取响应内容的时间
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// 每个请求携带的 `User-Agent`
+    pub fn user_agent<U: Into<String>>(mut self, user_agent: U) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// 遇到区域重定向错误时是否自动切换 endpoint 重试，见 [`Client::set_follow_redirect`]
+    pub fn follow_redirect(mut self, follow: bool) -> Self {
+        self.follow_redirect = follow;
+        self
+    }
+
+    /// 每个请求都会携带的 header，见 [`Client::set_default_headers`]
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.default_headers = headers;
+        self
+    }
+
+    /// 同时进行中的请求数量上限，见 [`Client::set_max_concurrent_requests`]
+    pub fn max_concurrent_requests(mut self, max: usize) -> Self {
+        self.max_concurrent_requests = Some(max);
+        self
+    }
+
+    /// 连接失败时依次尝试的备用 endpoint，见 [`Client::set_fallback_endpoints`]
+    pub fn fallback_endpoints(mut self, endpoints: Vec<EndPoint>) -> Self {
+        self.fallback_endpoints = endpoints;
+        self
+    }
+
+    /// 创建 Client，没有设置 key 或 secret 时返回 `NoFoundCredentials` 错误
+    pub fn build(self) -> Result<Client, OssError> {
+        let key = self.key.ok_or(OssError::NoFoundCredentials)?;
+        let secret = self.secret.ok_or(OssError::NoFoundCredentials)?;
+        let mut client = match self.security_token {
+            Some(token) => Client::new_with_sts(key, secret, token),
+            None => Client::new(key, secret),
+        };
+
+        if let Some(bucket) = self.bucket {
+            let bucket = match self.internal {
+                Some(internal) => bucket.with_internal(internal),
+                None => bucket,
+            };
+            client.set_bucket(bucket);
+        }
+        if let Some(timeout) = self.timeout {
+            client.http = reqwest::Client::builder().timeout(timeout).build()?;
+        }
+        let mut headers = self.default_headers;
+        if let Some(user_agent) = self.user_agent {
+            headers.insert(USER_AGENT, user_agent.try_into()?);
+        }
+        client.set_default_headers(headers);
+        client.set_follow_redirect(self.follow_redirect);
+        client.set_max_concurrent_requests(self.max_concurrent_requests);
+        client.set_fallback_endpoints(self.fallback_endpoints);
+
+        Ok(client)
+    }
+}
+
+/// 分片上传和分段下载相关的大小设置
+#[derive(Debug, Clone)]
+struct TransferSizes {
+    part_size: u64,
+    multipart_threshold: u64,
+    download_part_size: u64,
+}
+
+impl Default for TransferSizes {
+    fn default() -> Self {
+        TransferSizes {
+            part_size: 200 * 1024 * 1024,
+            multipart_threshold: 200 * 1024 * 1024,
+            download_part_size: DOWNLOAD_PART_SIZE,
+        }
+    }
+}
+
 /// 存放 key, secret 以及默认 bucket 信息，几乎每个 api 都会用到它的引用
 #[derive(Debug, Clone)]
 pub struct Client {
-    key: Key,
-    secret: Secret,
+    /// clone 出的 Client 共享同一份凭证，更新凭证后都会使用新的凭证签名
+    auth: Arc<RwLock<Auth>>,
     bucket: Option<Bucket>,
-    security_token: Option<String>,
+    follow_redirect: bool,
+    accept_gzip: bool,
+    decompress_by_ext: bool,
+    sizes: TransferSizes,
+    /// 每个请求都会携带的 header
+    default_headers: HeaderMap,
+    /// 同时进行中的请求数量上限，clone 出的 Client 共享同一个限制
+    limiter: Option<(usize, Arc<Semaphore>)>,
+    /// 连接失败时依次尝试的备用 endpoint
+    fallback_endpoints: Vec<EndPoint>,
+    http: reqwest::Client,
+    /// 测试中代替网络请求
+    #[cfg(test)]
+    mock: Option<Mock>,
 }
 
 impl Client {
     pub fn new(key: Key, secret: Secret) -> Client {
         Self {
-            key,
-            secret,
+            auth: Arc::new(RwLock::new(Auth::new(key, secret))),
             bucket: None,
-            security_token: None,
+            follow_redirect: false,
+            accept_gzip: false,
+            decompress_by_ext: false,
+            sizes: TransferSizes::default(),
+            default_headers: HeaderMap::new(),
+            limiter: None,
+            fallback_endpoints: Vec::new(),
+            http: reqwest::Client::new(),
+            #[cfg(test)]
+            mock: None,
         }
     }
 
@@ -39,22 +242,106 @@ impl Client {
         let bucket = Bucket::from_env().ok();
 
         Ok(Client {
-            key,
-            secret,
+            auth: Arc::new(RwLock::new(Auth::new(key, secret))),
             bucket,
-            security_token: None,
+            follow_redirect: false,
+            accept_gzip: false,
+            decompress_by_ext: false,
+            sizes: TransferSizes::default(),
+            default_headers: HeaderMap::new(),
+            limiter: None,
+            fallback_endpoints: Vec::new(),
+            http: reqwest::Client::new(),
+            #[cfg(test)]
+            mock: None,
         })
     }
 
+    /// 使用具名的方法创建 Client，可以同时设置超时时间，User-Agent 等选项
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::default()
+    }
+
+    /// 根据 bucket 的 url（如从控制台复制的 `https://bucket.oss-cn-hangzhou.aliyuncs.com`）
+    /// 创建 Client，url 中的 bucket 作为默认 bucket，内网域名会设置 endpoint 为内网
+    pub fn from_url(url: &str, key: Key, secret: Secret) -> Result<Self, OssError> {
+        let bucket = Bucket::from_url(&Url::parse(url)?)?;
+        let mut client = Client::new(key, secret);
+        client.set_bucket(bucket);
+
+        Ok(client)
+    }
+
     pub fn new_with_sts(key: Key, secret: Secret, security_token: String) -> Self {
         Self {
-            key,
-            secret,
+            auth: Arc::new(RwLock::new(Auth::new_with_sts(key, secret, security_token))),
             bucket: None,
-            security_token: Some(security_token),
+            follow_redirect: false,
+            accept_gzip: false,
+            decompress_by_ext: false,
+            sizes: TransferSizes::default(),
+            default_headers: HeaderMap::new(),
+            limiter: None,
+            fallback_endpoints: Vec::new(),
+            http: reqwest::Client::new(),
+            #[cfg(test)]
+            mock: None,
         }
     }
 
+    /// 返回一个全局共享的 Client，第一次调用时从环境变量初始化，之后的调用都返回同一个实例，
+    /// 不会重复读取环境变量，也不会重复创建 http 连接池
+    ///
+    /// 初始化是线程安全的，多个线程同时首次调用时，只有一个初始化结果会被保存；
+    /// 环境变量缺失时返回错误，下次调用会重新尝试初始化
+    #[cfg(feature = "global")]
+    pub fn global() -> Result<&'static Client, VarError> {
+        static GLOBAL: std::sync::OnceLock<Client> = std::sync::OnceLock::new();
+
+        get_or_try_init(&GLOBAL, Client::from_env)
+    }
+
+    /// 校验文件内容与 etag 是否一致
+    ///
+    /// 非分片上传的文件，etag 为内容 MD5 的十六进制（大写）形式，分片上传的文件不适用
+    /// ```
+    /// # use aliyun_oss_client::Client;
+    /// assert!(Client::verify_etag(b"aaa", "\"47BCE5C74F589F4867DBD57E9CA9F808\""));
+    /// assert!(Client::verify_etag(b"aaa", "47bce5c74f589f4867dbd57e9ca9f808"));
+    /// assert!(!Client::verify_etag(b"aab", "\"47BCE5C74F589F4867DBD57E9CA9F808\""));
+    /// ```
+    pub fn verify_etag(content: &[u8], etag: &str) -> bool {
+        let digest = format!("{:X}", md5::compute(content));
+        etag.trim_matches('"').eq_ignore_ascii_case(&digest)
+    }
+
+    /// 更新凭证，如轮换 STS 临时凭证，之后发送的请求都会使用新的凭证签名，
+    /// 通过 `clone` 或 [`with_bucket`](Self::with_bucket) 得到的 Client 也会同时更新
+    /// ```
+    /// # use aliyun_oss_client::{types::CanonicalizedResource, Client, Key, Secret};
+    /// # use reqwest::Method;
+    /// let client = Client::new(Key::new("foo"), Secret::new("bar"));
+    /// client.update_credentials(Key::new("foo2"), Secret::new("bar2"), Some("token".to_owned()));
+    ///
+    /// let headers = client
+    ///     .authorization(&Method::GET, CanonicalizedResource::new("/bucket/".to_owned()))
+    ///     .unwrap();
+    /// assert!(headers["Authorization"].to_str().unwrap().starts_with("OSS foo2:"));
+    /// assert_eq!(headers["x-oss-security-token"], "token");
+    /// ```
+    pub fn update_credentials(&self, key: Key, secret: Secret, security_token: Option<String>) {
+        let auth = match security_token {
+            Some(token) => Auth::new_with_sts(key, secret, token),
+            None => Auth::new(key, secret),
+        };
+        *self.auth.write().unwrap_or_else(|e| e.into_inner()) = auth;
+    }
+
+    /// 读取当前的凭证，签名期间持有读锁，保证同一次签名使用的 key，secret，token 一致
+    fn auth(&self) -> RwLockReadGuard<'_, Auth> {
+        self.auth.read().unwrap_or_else(|e| e.into_inner())
+    }
+
     /// 设置默认的 bucket(bucket 也会包含 endpoint 信息)
     /// 当设置的时候，会返回上次设置的值，默认值为 None
     /// ```
@@ -80,66 +367,473 @@ impl Client {
         self.bucket.as_ref()
     }
 
-    pub fn authorization(
+    /// 返回一个使用指定 bucket 作为默认 bucket 的 Client，与当前 Client 共享凭证和 http 连接池，
+    /// 用于临时操作其他 bucket（可以是其他区域）中的文件
+    /// ```
+    /// # use aliyun_oss_client::{types::CanonicalizedResource, Bucket, Client, EndPoint, Key, Object, Secret};
+    /// let mut client = Client::new(Key::new("foo"), Secret::new("bar"));
+    /// client.set_bucket(Bucket::new("bucket1", EndPoint::CN_QINGDAO));
+    ///
+    /// let other = client.with_bucket(Bucket::new("bucket2", EndPoint::CN_SHANGHAI));
+    /// let bucket = other.bucket().unwrap();
+    /// let object = Object::new("abc.txt");
+    /// assert_eq!(
+    ///     object.to_url(bucket).as_str(),
+    ///     "https://bucket2.oss-cn-shanghai.aliyuncs.com/abc.txt"
+    /// );
+    /// assert_eq!(
+    ///     CanonicalizedResource::from_object(bucket, &object).as_str(),
+    ///     "/bucket2/abc.txt"
+    /// );
+    /// assert_eq!(client.bucket().unwrap(), &Bucket::new("bucket1", EndPoint::CN_QINGDAO));
+    /// ```
+    pub fn with_bucket(&self, bucket: Bucket) -> Client {
+        let mut client = self.clone();
+        client.bucket = Some(bucket);
+        client
+    }
+
+    /// 设置当 bucket 所在区域与请求的 endpoint 不一致时，是否根据 OSS 返回的错误信息
+    /// 自动切换到正确的 endpoint 并重试一次，默认为 false
+    pub fn set_follow_redirect(&mut self, follow: bool) {
+        self.follow_redirect = follow;
+    }
+
+    /// 设置列举文件，获取 bucket 信息等返回 XML 的请求是否携带 `Accept-Encoding: gzip`，
+    /// 开启后 OSS 返回压缩的内容并自动解压，bucket 中文件很多时可以减少流量，默认为 false
+    pub fn set_accept_gzip(&mut self, accept: bool) {
+        self.accept_gzip = accept;
+    }
+
+    pub fn accept_gzip(&self) -> bool {
+        self.accept_gzip
+    }
+
+    /// 设置 [`Object::download_decompressed`] 遇到没有 `Content-Encoding` 的文件时，是否根据扩展名
+    /// （`.gz`，`.zst`）解压，适用于压缩后上传但没有设置 `Content-Encoding` 的文件，默认为 false，
+    /// 解压 `.zst` 文件需要开启 `zstd` feature
+    pub fn set_decompress_by_ext(&mut self, decompress: bool) {
+        self.decompress_by_ext = decompress;
+    }
+
+    pub fn decompress_by_ext(&self) -> bool {
+        self.decompress_by_ext
+    }
+
+    /// 设置每个请求都会携带的 header，如网关要求的固定 header，会在签名前合并到请求的 header 中，
+    /// 其中 `x-oss-` 开头的 header 会参与签名，请求本身携带的同名 header 优先
+    /// ```
+    /// # use aliyun_oss_client::{types::CanonicalizedResource, Client, Key, Secret};
+    /// # use reqwest::{header::HeaderMap, Method};
+    /// let mut client = Client::new(Key::new("foo"), Secret::new("bar"));
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert("x-api-key", "abc".parse().unwrap());
+    /// client.set_default_headers(headers);
+    ///
+    /// let res = client
+    ///     .authorization(&Method::GET, CanonicalizedResource::new("/bucket/".to_owned()))
+    ///     .unwrap();
+    /// assert_eq!(res["x-api-key"], "abc");
+    /// ```
+    pub fn set_default_headers(&mut self, headers: HeaderMap) {
+        self.default_headers = headers;
+    }
+
+    pub fn default_headers(&self) -> &HeaderMap {
+        &self.default_headers
+    }
+
+    /// 设置同时进行中的请求数量上限，批量下载，批量解冻，分段下载等并发操作都会遵守该限制，
+    /// 为 `None` 时不限制（默认），上限为 0 时按 1 处理
+    ///
+    /// 在此之后 clone 出的 Client 共享同一个限制
+    pub fn set_max_concurrent_requests(&mut self, max: Option<usize>) {
+        self.limiter = max.map(|max| {
+            let max = max.max(1);
+            (max, Arc::new(Semaphore::new(max)))
+        });
+    }
+
+    /// 同时进行中的请求数量上限
+    pub fn max_concurrent_requests(&self) -> Option<usize> {
+        self.limiter.as_ref().map(|(max, _)| *max)
+    }
+
+    /// 设置备用 endpoint，如内网 endpoint 不可达时使用的外网 endpoint，默认为空
+    ///
+    /// 请求因连接失败或域名解析失败而没有发出时，依次将请求地址中的 endpoint 替换为备用 endpoint，
+    /// 重新签名后再次发送，bucket 和签名使用的资源路径不变；只适用于 `aliyuncs.com` 域名，
+    /// 使用自定义域名时不会切换
+    /// ```
+    /// # use aliyun_oss_client::{Client, EndPoint, Key, Secret};
+    /// let mut client = Client::new(Key::new("foo"), Secret::new("bar"));
+    /// client.set_fallback_endpoints(vec![EndPoint::CN_QINGDAO]);
+    /// assert_eq!(client.fallback_endpoints(), [EndPoint::CN_QINGDAO]);
+    /// ```
+    pub fn set_fallback_endpoints(&mut self, endpoints: Vec<EndPoint>) {
+        self.fallback_endpoints = endpoints;
+    }
+
+    pub fn fallback_endpoints(&self) -> &[EndPoint] {
+        &self.fallback_endpoints
+    }
+
+    /// 设置分片上传时每个分片的大小，取值范围为 100KB 到 5GB，默认为 200MB
+    /// ```
+    /// # use aliyun_oss_client::{Client, Key, Secret};
+    /// let mut client = Client::new(Key::new("foo"), Secret::new("bar"));
+    /// client.set_part_size(10 * 1024 * 1024).unwrap();
+    /// assert_eq!(client.part_size(), 10 * 1024 * 1024);
+    /// assert!(client.set_part_size(1024).is_err());
+    /// ```
+    pub fn set_part_size(&mut self, size: u64) -> Result<(), OssError> {
+        self.sizes.part_size = valid_part_size(size)?;
+        Ok(())
+    }
+
+    /// 以 MB 为单位设置分片大小，取值范围为 1MB 到 5120MB
+    /// ```
+    /// # use aliyun_oss_client::{Client, Key, Secret};
+    /// let mut client = Client::new(Key::new("foo"), Secret::new("bar"));
+    /// client.set_part_size_mb(10).unwrap();
+    /// assert_eq!(client.part_size(), 10 * 1024 * 1024);
+    /// ```
+    pub fn set_part_size_mb(&mut self, mb: u32) -> Result<(), OssError> {
+        self.set_part_size(mb as u64 * 1024 * 1024)
+    }
+
+    /// 分片上传时每个分片的大小
+    pub fn part_size(&self) -> u64 {
+        self.sizes.part_size
+    }
+
+    /// 设置文件大小超过多少时使用分片上传，默认为 200MB
+    pub fn set_multipart_threshold(&mut self, size: u64) {
+        self.sizes.multipart_threshold = size;
+    }
+
+    /// 文件大小超过该值时使用分片上传
+    pub fn multipart_threshold(&self) -> u64 {
+        self.sizes.multipart_threshold
+    }
+
+    /// 设置分段下载时每一段的大小，取值范围与分片大小相同，默认为 [`DOWNLOAD_PART_SIZE`]
+    ///
+    /// [`DOWNLOAD_PART_SIZE`]: crate::object::DOWNLOAD_PART_SIZE
+    pub fn set_download_part_size(&mut self, size: u64) -> Result<(), OssError> {
+        self.sizes.download_part_size = valid_part_size(size)?;
+        Ok(())
+    }
+
+    /// 分段下载时每一段的大小
+    pub fn download_part_size(&self) -> u64 {
+        self.sizes.download_part_size
+    }
+
+    /// 签名并发送请求，非 2xx 的响应会被转换为 `OssError`
+    ///
+    /// 开启 `follow_redirect` 时，遇到区域重定向错误会根据错误信息中的 `Endpoint`
+    /// 重新签名，并重试一次
+    pub(crate) async fn send(
         &self,
-        method: &Method,
+        method: Method,
+        url: Url,
         resource: CanonicalizedResource,
-    ) -> Result<HeaderMap, OssError> {
-        self.authorization_header(method, resource, HeaderMap::new())
+        headers: HeaderMap,
+        body: Option<Vec<u8>>,
+    ) -> Result<Response, OssError> {
+        let retry = self
+            .follow_redirect
+            .then(|| (url.clone(), headers.clone(), body.clone()));
+
+        match self
+            .send_failover(method.clone(), url, resource.clone(), headers, body)
+            .await
+        {
+            Err(OssError::Service(xml)) if retry.is_some() && xml.is_redirect() => {
+                let (url, headers, body) = retry.expect("retry is some");
+                let url = xml
+                    .endpoint()
+                    .and_then(|endpoint| redirect_url(&url, endpoint))
+                    .ok_or(OssError::Service(xml))?;
+
+                self.send_failover(method, url, resource, headers, body)
+                    .await
+            }
+            res => res,
+        }
     }
 
-    pub fn authorization_header(
+    /// 发送请求，连接 `url` 失败时依次使用备用 endpoint 重新签名并发送
+    async fn send_failover(
         &self,
-        method: &Method,
+        method: Method,
+        url: Url,
         resource: CanonicalizedResource,
-        mut headers: HeaderMap,
-    ) -> Result<HeaderMap, OssError> {
-        const LINE_BREAK: &str = "\n";
+        headers: HeaderMap,
+        body: Option<Vec<u8>>,
+    ) -> Result<Response, OssError> {
+        let fallbacks = fallback_urls(&url, &self.fallback_endpoints);
+        if fallbacks.is_empty() {
+            return self.send_once(method, url, resource, headers, body).await;
+        }
 
-        let date = now();
-        let content_type = "text/xml";
+        with_failover(url, fallbacks, |url| {
+            self.send_once(
+                method.clone(),
+                url,
+                resource.clone(),
+                headers.clone(),
+                body.clone(),
+            )
+        })
+        .await
+    }
 
-        if let Some(sts_token) = &self.security_token {
-            headers.insert("x-oss-security-token", {
-                let mut token: HeaderValue = sts_token.try_into()?;
-                token.set_sensitive(true);
-                token
-            });
+    /// 发送 GET 请求并读取返回的 XML，开启 `accept_gzip` 时请求压缩的内容
+    pub(crate) async fn get_xml(
+        &self,
+        url: Url,
+        resource: CanonicalizedResource,
+    ) -> Result<String, OssError> {
+        let mut headers = HeaderMap::new();
+        if self.accept_gzip {
+            headers.insert(ACCEPT_ENCODING, HeaderValue::from_static("gzip"));
         }
+        let response = self.send(Method::GET, url, resource, headers, None).await?;
 
-        let oss_header_str = to_oss_header(&headers);
+        read_xml(response).await
+    }
 
-        let sign = {
-            let mut string = method.as_str().to_owned();
-            string += LINE_BREAK;
-            string += LINE_BREAK;
-            string += content_type;
-            string += LINE_BREAK;
-            string += date.as_str();
-            string += LINE_BREAK;
-            string += &oss_header_str;
-            string += resource.as_str();
+    /// 签名并发送请求，不检查响应的状态码，调用方需要自行处理非 2xx 的响应
+    pub(crate) async fn send_unchecked(
+        &self,
+        method: Method,
+        url: Url,
+        resource: CanonicalizedResource,
+        headers: HeaderMap,
+        body: Option<Vec<u8>>,
+    ) -> Result<Response, OssError> {
+        let (request, _) = self.build_request(method, url, resource, headers, body)?;
 
-            let encry = self.secret.encryption(string.as_bytes()).unwrap();
+        self.execute(request).await
+    }
 
-            format!("OSS {}:{}", self.key.as_str(), encry)
+    /// 在请求数量上限内发送请求，达到上限时等待其他请求完成
+    ///
+    /// 占用的名额在响应内容读取完或响应被丢弃后才释放，而不是收到响应 header 时
+    async fn execute(&self, request: RequestBuilder) -> Result<Response, OssError> {
+        let permit = match &self.limiter {
+            Some((_, semaphore)) => Some(
+                semaphore
+                    .clone()
+                    .acquire_owned()
+                    .await
+                    .expect("the semaphore is never closed"),
+            ),
+            None => None,
         };
-        let header_map = {
-            headers.insert("AccessKeyId", self.key.as_str().try_into()?);
-            headers.insert("VERB", method.as_str().try_into()?);
-            headers.insert("Date", date.try_into()?);
-            headers.insert("Authorization", {
-                let mut token: HeaderValue = sign.try_into()?;
-                token.set_sensitive(true);
-                token
-            });
-            headers.insert(CONTENT_TYPE, content_type.try_into()?);
-            headers.insert("CanonicalizedResource", resource.as_str().try_into()?);
 
-            headers
+        #[cfg(test)]
+        let response = match &self.mock {
+            Some(mock) => (mock.0)(request.build()?)?,
+            None => request.send().await?,
         };
+        #[cfg(not(test))]
+        let response = request.send().await?;
+
+        Ok(match permit {
+            Some(permit) => hold_permit(response, permit),
+            None => response,
+        })
+    }
+
+    async fn send_once(
+        &self,
+        method: Method,
+        url: Url,
+        resource: CanonicalizedResource,
+        headers: HeaderMap,
+        body: Option<Vec<u8>>,
+    ) -> Result<Response, OssError> {
+        let local_resource = resource.as_str().to_owned();
+        let (request, string_to_sign) = self.build_request(method, url, resource, headers, body)?;
+
+        let response = self.execute(request).await?;
+
+        let status = response.status();
+        if status.is_success() {
+            Ok(response)
+        } else if let Some(err) = OssError::from_range_not_satisfiable(status, response.headers()) {
+            Err(err)
+        } else {
+            let content = response.text().await?;
+            let string_to_sign = match &self.auth().security_token {
+                Some(token) => string_to_sign.replace(token.as_str(), "******"),
+                None => string_to_sign,
+            };
+            Err(OssError::from_service_with_sign(
+                status,
+                &content,
+                local_resource,
+                string_to_sign,
+            ))
+        }
+    }
+
+    fn build_request(
+        &self,
+        method: Method,
+        url: Url,
+        resource: CanonicalizedResource,
+        headers: HeaderMap,
+        body: Option<Vec<u8>>,
+    ) -> Result<(RequestBuilder, String), OssError> {
+        let (mut header_map, string_to_sign) = self.sign_header(&method, resource, headers)?;
+        if let Some(host) = self.bucket().and_then(|b| b.host_header_for(&url)) {
+            header_map.insert(HOST, host.try_into()?);
+        }
+
+        let mut request = self.http.request(method, url);
+        if let Some(body) = body {
+            if body.is_empty() {
+                header_map.insert(CONTENT_LENGTH, 0.into());
+            }
+            request = request.body(body);
+        }
+
+        Ok((request.headers(header_map), string_to_sign))
+    }
+
+    /// 签名但不发送请求，返回将要发送的请求内容
+    pub(crate) fn dry_run(
+        &self,
+        method: Method,
+        url: Url,
+        resource: CanonicalizedResource,
+        headers: HeaderMap,
+    ) -> Result<SignedRequest, OssError> {
+        let resource_str = resource.as_str().to_owned();
+        let (headers, _) = self.sign_header(&method, resource, headers)?;
+
+        Ok(SignedRequest {
+            method,
+            url,
+            resource: resource_str,
+            headers,
+        })
+    }
+
+    /// 获取 OSS 服务器的当前时间，可用于检测本地时钟的偏差
+    ///
+    /// 向 `endpoint` 发送一个 HEAD 请求，读取响应中的 `Date` header，不关心响应的状态码，
+    /// 本地时钟偏差过大导致签名过期时同样可以获取到服务器时间
+    pub async fn server_time(&self, endpoint: &EndPoint) -> Result<DateTime<Utc>, OssError> {
+        let url = endpoint.to_url();
+        let fallbacks = fallback_urls(&url, &self.fallback_endpoints);
+        let response = with_failover(url, fallbacks, |url| {
+            self.send_unchecked(
+                Method::HEAD,
+                url,
+                CanonicalizedResource::default(),
+                HeaderMap::new(),
+                None,
+            )
+        })
+        .await?;
+
+        parse_date_header(response.headers())
+    }
+
+    /// 生成浏览器表单直传（PostObject）所需的 policy 和签名
+    ///
+    /// `conditions` 为 policy 中的条件列表，如 `["content-length-range", 0, 1048576]`，
+    /// `{"bucket": "foo"}`，`expiration` 为 policy 的过期时间
+    pub fn sign_post_policy(
+        &self,
+        conditions: &[serde_json::Value],
+        expiration: DateTime<Utc>,
+    ) -> Result<PostFormData, OssError> {
+        use base64::engine::general_purpose::STANDARD;
+        use base64::Engine;
+
+        let policy = format!(
+            r#"{{"expiration":"{}","conditions":{}}}"#,
+            expiration.to_rfc3339_opts(SecondsFormat::Millis, true),
+            serde_json::to_string(conditions)?
+        );
+        let policy = STANDARD.encode(policy);
+        let auth = self.auth();
+        let signature = auth
+            .secret
+            .encryption(policy.as_bytes())
+            .map_err(|_| OssError::InvalidSecret)?;
+
+        Ok(PostFormData {
+            access_key_id: auth.key.as_str().to_owned(),
+            policy,
+            signature,
+            security_token: auth.security_token.clone(),
+        })
+    }
+
+    pub fn authorization(
+        &self,
+        method: &Method,
+        resource: CanonicalizedResource,
+    ) -> Result<HeaderMap, OssError> {
+        self.authorization_header(method, resource, HeaderMap::new())
+    }
+
+    pub fn authorization_header(
+        &self,
+        method: &Method,
+        resource: CanonicalizedResource,
+        headers: HeaderMap,
+    ) -> Result<HeaderMap, OssError> {
+        self.sign_header(method, resource, headers)
+            .map(|(header_map, _)| header_map)
+    }
+
+    /// 生成默认 bucket 中 `channel` 的带签名 RTMP 推流地址，在 `expires` 之前有效
+    /// ```
+    /// # use aliyun_oss_client::{Bucket, Client, EndPoint, Key, Secret};
+    /// # use chrono::{TimeZone, Utc};
+    /// let mut client = Client::new(Key::new("foo"), Secret::new("bar"));
+    /// client.set_bucket(Bucket::new("bucket", EndPoint::CN_QINGDAO));
+    ///
+    /// let expires = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+    /// let url = client
+    ///     .sign_rtmp_url("channel", expires, Some("play.m3u8"))
+    ///     .unwrap();
+    /// assert!(url.as_str().starts_with(
+    ///     "rtmp://bucket.oss-cn-qingdao.aliyuncs.com/live/channel?playlistName=play.m3u8&OSSAccessKeyId=foo&Expires=1704110400&Signature="
+    /// ));
+    /// ```
+    pub fn sign_rtmp_url(
+        &self,
+        channel: &str,
+        expires: DateTime<Utc>,
+        play_list: Option<&str>,
+    ) -> Result<Url, OssError> {
+        let bucket = self.bucket().ok_or(OssError::NoFoundBucket)?;
+        let host = bucket.to_url();
+        let host = host.host_str().ok_or(OssError::InvalidBucket)?;
+        let url = Url::parse(&format!("rtmp://{}/live/{}", host, channel))?;
+        let resource = CanonicalizedResource::new(format!("/{}/{}", bucket.as_str(), channel));
 
-        Ok(header_map)
+        self.auth().sign_rtmp_url(url, resource, play_list, expires)
+    }
+
+    /// 签名并返回签名后的 header，以及本地计算的待签名字符串
+    fn sign_header(
+        &self,
+        method: &Method,
+        resource: CanonicalizedResource,
+        headers: HeaderMap,
+    ) -> Result<(HeaderMap, String), OssError> {
+        let headers = with_default_headers(&self.default_headers, headers);
+        self.auth().sign(method, resource, headers)
     }
 
     /// 调用 api 导出 bucket 列表信息到自定义类型
@@ -186,22 +880,12 @@ impl Client {
         let method = Method::GET;
         let resource = CanonicalizedResource::default();
 
-        let header_map = self.authorization(&method, resource)?;
-
-        let response = reqwest::Client::new()
-            .get(url)
-            .headers(header_map)
-            .send()
+        let content = self
+            .send(method, url, resource, HeaderMap::new(), None)
+            .await?
+            .text()
             .await?;
 
-        let is_success = response.status().is_success();
-
-        let content = response.text().await?;
-
-        if !is_success {
-            return Err(OssError::from_service(&content));
-        }
-
         //println!("{}", content);
 
         #[derive(Debug, Deserialize)]
@@ -221,96 +905,301 @@ impl Client {
         Ok(xml_res.buckets.bucket)
     }
 
-    pub async fn get_buckets(&self, endpoint: &EndPoint) -> Result<Vec<Bucket>, OssError> {
-        let url = endpoint.to_url();
-        let method = Method::GET;
-        let resource = CanonicalizedResource::default();
+    /// 批量解冻默认 bucket 中的文件，最多同时发送 [`RESTORE_CONCURRENCY`] 个请求
+    ///
+    /// 返回的结果与 `paths` 的顺序一一对应，单个文件失败不影响其他文件
+    pub async fn restore_objects<P: AsRef<str>>(
+        &self,
+        paths: &[P],
+        days: u32,
+    ) -> Vec<Result<RestoreStatus, OssError>> {
+        stream::iter(paths)
+            .map(|path| async move { Object::new(path.as_ref()).restore(days, self).await })
+            .buffered(RESTORE_CONCURRENCY)
+            .collect()
+            .await
+    }
 
-        let header_map = self.authorization(&method, resource)?;
+    /// 请求默认 bucket 的 `?location` 接口，返回请求的往返耗时，可用于健康检查，
+    /// 或者比较外网和内网 endpoint 的延迟
+    pub async fn ping(&self) -> Result<Duration, OssError> {
+        let bucket = self.bucket().ok_or(OssError::NoFoundBucket)?;
 
-        let response = reqwest::Client::new()
-            .get(url)
-            .headers(header_map)
-            .send()
+        let start = Instant::now();
+        bucket
+            .send_sub_resource(Method::GET, LOCATION, None, self)
             .await?;
 
-        let is_success = response.status().is_success();
-        let content = response.text().await?;
-        if !is_success {
-            return Err(OssError::from_service(&content));
-        }
+        Ok(start.elapsed())
+    }
 
-        // println!("{content}");
+    /// 列举当前账号下的所有 bucket，每个 bucket 使用其所在区域的 endpoint
+    pub async fn list_buckets(&self, endpoint: &EndPoint) -> Result<ListBuckets, OssError> {
+        let content = self
+            .send(
+                Method::GET,
+                endpoint.to_url(),
+                CanonicalizedResource::default(),
+                HeaderMap::new(),
+                None,
+            )
+            .await?
+            .text()
+            .await?;
 
-        Self::parse_xml(content, endpoint)
+        ListBuckets::from_xml(&content)
     }
 
-    fn parse_xml(xml: String, endpoint: &EndPoint) -> Result<Vec<Bucket>, OssError> {
-        let mut start_positions = vec![];
-        let mut end_positions = vec![];
-        let mut start = 0;
-        let mut pattern = "<Name>";
-        let pattern_len = pattern.len();
+    /// 与 [`list_buckets`](Self::list_buckets) 相同，返回 bucket 列表
+    pub async fn get_buckets(&self, endpoint: &EndPoint) -> Result<Vec<Bucket>, OssError> {
+        Ok(self.list_buckets(endpoint).await?.into_iter().collect())
+    }
+}
 
-        while let Some(pos) = xml[start..].find(pattern) {
-            start_positions.push(start + pos);
-            start += pos + pattern.len();
-        }
-        start = 0;
-        pattern = "</Name>";
-        while let Some(pos) = xml[start..].find(pattern) {
-            end_positions.push(start + pos);
-            start += pos + pattern.len();
+/// 返回 `cell` 中已经初始化的 Client，没有时调用 `init` 初始化，`init` 失败时不保存结果，
+/// 下次调用会重新尝试
+#[cfg(feature = "global")]
+fn get_or_try_init<E>(
+    cell: &std::sync::OnceLock<Client>,
+    init: impl FnOnce() -> Result<Client, E>,
+) -> Result<&Client, E> {
+    if let Some(client) = cell.get() {
+        return Ok(client);
+    }
+    let client = init()?;
+
+    Ok(cell.get_or_init(|| client))
+}
+
+/// 将默认 header 合并到请求的 header 中，请求本身携带的同名 header 优先
+fn with_default_headers(defaults: &HeaderMap, mut headers: HeaderMap) -> HeaderMap {
+    for name in defaults.keys() {
+        if !headers.contains_key(name) {
+            for value in defaults.get_all(name) {
+                headers.append(name, value.clone());
+            }
         }
+    }
+    headers
+}
+
+/// 读取 XML 响应内容，根据 `Content-Encoding` 解压
+pub(crate) async fn read_xml(response: Response) -> Result<String, OssError> {
+    let encoding = response
+        .headers()
+        .get(CONTENT_ENCODING)
+        .map(|value| value.to_str())
+        .transpose()?
+        .map(|value| value.to_owned());
+    let content = decode_content(encoding.as_deref(), response.bytes().await?.into())?;
 
-        debug_assert!(start_positions.len() == end_positions.len());
+    Ok(String::from_utf8(content)?)
+}
 
-        let mut bucket = vec![];
-        for i in 0..start_positions.len() {
-            let name = &xml[start_positions[i] + pattern_len..end_positions[i]];
-            bucket.push(Bucket::new(name.to_owned(), endpoint.clone()))
+/// 已签名但没有发送的请求，用于展示实际会发送的内容（dry run）
+#[derive(Debug, Clone)]
+pub struct SignedRequest {
+    method: Method,
+    url: Url,
+    resource: String,
+    headers: HeaderMap,
+}
+
+impl SignedRequest {
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// 参与签名的 `CanonicalizedResource`
+    pub fn resource(&self) -> &str {
+        &self.resource
+    }
+
+    /// 包含 `Authorization`，`Date` 等签名后的 header
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+}
+
+/// 浏览器表单直传时需要携带的表单字段
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PostFormData {
+    access_key_id: String,
+    policy: String,
+    signature: String,
+    security_token: Option<String>,
+}
+
+impl PostFormData {
+    pub fn access_key_id(&self) -> &str {
+        &self.access_key_id
+    }
+
+    /// base64 编码后的 policy
+    pub fn policy(&self) -> &str {
+        &self.policy
+    }
+
+    pub fn signature(&self) -> &str {
+        &self.signature
+    }
+
+    /// 返回表单中需要的字段，使用 STS 时会包含 `x-oss-security-token`
+    pub fn form_fields(&self) -> Vec<(&'static str, &str)> {
+        let mut fields = vec![
+            ("OSSAccessKeyId", self.access_key_id.as_str()),
+            ("policy", self.policy.as_str()),
+            ("Signature", self.signature.as_str()),
+        ];
+        if let Some(token) = &self.security_token {
+            fields.push(("x-oss-security-token", token.as_str()));
         }
+        fields
+    }
+}
 
-        Ok(bucket)
+/// 将 `permit` 放入响应内容中，响应内容读取完或响应被丢弃时释放
+fn hold_permit(response: Response, permit: OwnedSemaphorePermit) -> Response {
+    let url = response.url().clone();
+    let (parts, body) = http::Response::from(response).into_parts();
+    let body = Body::wrap(PermitBody {
+        body,
+        _permit: permit,
+    });
+
+    let mut builder = http::Response::builder()
+        .status(parts.status)
+        .version(parts.version)
+        .url(url);
+    if let Some(headers) = builder.headers_mut() {
+        *headers = parts.headers;
     }
+    builder
+        .body(body)
+        .expect("status and headers come from a valid response")
+        .into()
 }
 
-fn now() -> String {
-    Utc::now().format("%a, %d %b %Y %T GMT").to_string()
+/// 持有请求数量名额的响应内容
+struct PermitBody {
+    body: Body,
+    _permit: OwnedSemaphorePermit,
 }
 
-// fn now() -> DateTime<Utc> {
-//     use chrono::NaiveDateTime;
-//     let naive = NaiveDateTime::parse_from_str("2022/10/6 20:40:00", "%Y/%m/%d %H:%M:%S").unwrap();
-//     DateTime::from_utc(naive, Utc)
-// }
-
-fn to_oss_header(headers: &HeaderMap) -> String {
-    const X_OSS_PRE: &str = "x-oss-";
-    const LINE_BREAK: &str = "\n";
-    //return Some("x-oss-copy-source:/honglei123/file1.txt");
-    let mut header: Vec<_> = headers
-        .iter()
-        .filter(|(k, _v)| k.as_str().starts_with(X_OSS_PRE))
-        .collect();
-    if header.is_empty() {
-        return String::new();
+impl http_body::Body for PermitBody {
+    type Data = <Body as http_body::Body>::Data;
+    type Error = <Body as http_body::Body>::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<http_body::Frame<Self::Data>, Self::Error>>> {
+        Pin::new(&mut self.body).poll_frame(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.body.is_end_stream()
     }
 
-    header.sort_by(|(k1, _), (k2, _)| k1.as_str().cmp(k2.as_str()));
+    fn size_hint(&self) -> http_body::SizeHint {
+        self.body.size_hint()
+    }
+}
+
+/// 测试中代替网络请求，根据请求返回预设的响应
+#[cfg(test)]
+#[derive(Clone)]
+pub(crate) struct Mock(
+    Arc<dyn Fn(reqwest::Request) -> Result<Response, reqwest::Error> + Send + Sync>,
+);
+
+#[cfg(test)]
+impl std::fmt::Debug for Mock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Mock")
+    }
+}
 
-    let header_vec: Vec<_> = header
+#[cfg(test)]
+impl Client {
+    /// 之后的请求都交给 `mock` 处理，不会发送到网络，签名，请求数量上限等逻辑不变
+    pub(crate) fn set_mock<F>(&mut self, mock: F)
+    where
+        F: Fn(reqwest::Request) -> Response + Send + Sync + 'static,
+    {
+        self.set_mock_result(move |request| Ok(mock(request)));
+    }
+
+    /// 与 [`set_mock`](Self::set_mock) 相同，`mock` 可以返回错误，用于模拟网络错误
+    pub(crate) fn set_mock_result<F>(&mut self, mock: F)
+    where
+        F: Fn(reqwest::Request) -> Result<Response, reqwest::Error> + Send + Sync + 'static,
+    {
+        self.mock = Some(Mock(Arc::new(mock)));
+    }
+}
+
+/// 将请求地址中的 endpoint 部分替换为 OSS 重定向错误中给出的 endpoint，bucket 部分保持不变
+fn redirect_url(url: &Url, endpoint: &str) -> Option<Url> {
+    const OSS_DOMAIN_PREFIX: &str = "oss-";
+    let host = url.host_str()?;
+
+    let new_host = if host.starts_with(OSS_DOMAIN_PREFIX) {
+        endpoint.to_owned()
+    } else {
+        let (bucket, _) = host.split_once('.')?;
+        format!("{}.{}", bucket, endpoint)
+    };
+
+    let mut url = url.clone();
+    url.set_host(Some(&new_host)).ok()?;
+    Some(url)
+}
+
+/// 将请求地址中的 endpoint 依次替换为备用 endpoint，跳过与原地址相同的，非 `aliyuncs.com` 域名时返回空
+fn fallback_urls(url: &Url, endpoints: &[EndPoint]) -> Vec<Url> {
+    const OSS_DOMAIN_SUFFIX: &str = ".aliyuncs.com";
+    if !url
+        .host_str()
+        .is_some_and(|h| h.ends_with(OSS_DOMAIN_SUFFIX))
+    {
+        return Vec::new();
+    }
+
+    endpoints
         .iter()
-        .filter_map(|(k, v)| {
-            v.to_str()
-                .ok()
-                .map(|value| k.as_str().to_owned() + ":" + value)
-        })
-        .collect();
+        .filter_map(|endpoint| redirect_url(url, &endpoint.to_host()))
+        .filter(|fallback| fallback != url)
+        .collect()
+}
+
+/// 依次使用 `url` 和 `fallbacks` 调用 `f`，只有在连接失败或域名解析失败，
+/// 即请求没有发出时才尝试下一个地址，返回最后一次调用的结果
+async fn with_failover<T, F, Fut>(url: Url, fallbacks: Vec<Url>, mut f: F) -> Result<T, OssError>
+where
+    F: FnMut(Url) -> Fut,
+    Fut: Future<Output = Result<T, OssError>>,
+{
+    let mut result = f(url).await;
+    for url in fallbacks {
+        if !matches!(result, Err(OssError::Connect(_) | OssError::Dns(_))) {
+            break;
+        }
+        result = f(url).await;
+    }
+
+    result
+}
+
+/// 解析响应中的 `Date` header，格式如 `Wed, 20 Mar 2024 08:00:00 GMT`
+fn parse_date_header(headers: &HeaderMap) -> Result<DateTime<Utc>, OssError> {
+    let date = headers.get(DATE).ok_or(OssError::NoFoundDate)?.to_str()?;
 
-    let mut str = header_vec.join(LINE_BREAK);
-    str += LINE_BREAK;
-    str
+    Ok(DateTime::parse_from_rfc2822(date)?.with_timezone(&Utc))
 }
 
 #[cfg(test)]
@@ -320,6 +1209,64 @@ mod tests {
         types::{EndPoint, StorageClass},
     };
 
+    #[tokio::test]
+    async fn test_ping() {
+        use crate::bucket::Bucket;
+
+        let mut client = init_client();
+        client.set_bucket(Bucket::new("honglei123", EndPoint::CN_SHANGHAI));
+
+        let latency = client.ping().await.unwrap();
+        assert!(latency.as_secs() < 30);
+    }
+
+    #[tokio::test]
+    async fn test_ping_mocked() {
+        use super::Client;
+        use crate::{
+            bucket::Bucket,
+            types::{Key, Secret},
+        };
+        use std::sync::{Arc, Mutex};
+
+        let mut client = Client::new(Key::new("foo"), Secret::new("bar"));
+        assert!(matches!(
+            client.ping().await,
+            Err(crate::Error::NoFoundBucket)
+        ));
+
+        client.set_bucket(Bucket::new("foo", EndPoint::CN_QINGDAO));
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        {
+            let requests = requests.clone();
+            client.set_mock(move |request| {
+                requests.lock().unwrap().push(request.url().to_string());
+                http::Response::new("<LocationConstraint>oss-cn-qingdao</LocationConstraint>")
+                    .into()
+            });
+        }
+        let latency = client.ping().await.unwrap();
+        assert!(latency.as_secs() < 30);
+        assert_eq!(
+            *requests.lock().unwrap(),
+            ["https://foo.oss-cn-qingdao.aliyuncs.com/?location"]
+        );
+
+        // 请求失败时返回错误
+        client.set_mock(|_| {
+            http::Response::builder()
+                .status(403)
+                .body(
+                    "<Error><Code>AccessDenied</Code><Message></Message>\
+                    <RequestId>1</RequestId></Error>",
+                )
+                .unwrap()
+                .into()
+        });
+        let res = client.ping().await;
+        assert!(matches!(res, Err(crate::Error::Service(e)) if e.code() == "AccessDenied"));
+    }
+
     #[tokio::test]
     async fn test_get_buckets() {
         let list = init_client().get_buckets(&EndPoint::CN_QINGDAO).await;
@@ -327,6 +1274,759 @@ mod tests {
         println!("{list:?}");
     }
 
+    #[test]
+    fn test_sign_post_policy() {
+        use super::Client;
+        use crate::types::{Key, Secret};
+        use chrono::{TimeZone, Utc};
+        use serde_json::json;
+
+        let client = Client::new(Key::new("foo"), Secret::new("bar"));
+        let conditions = [
+            json!(["content-length-range", 0, 1048576]),
+            json!({"bucket": "foo"}),
+        ];
+        let expiration = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+
+        let data = client.sign_post_policy(&conditions, expiration).unwrap();
+        assert_eq!(data.policy(), "eyJleHBpcmF0aW9uIjoiMjAyNC0wMS0wMVQxMjowMDowMC4wMDBaIiwiY29uZGl0aW9ucyI6W1siY29udGVudC1sZW5ndGgtcmFuZ2UiLDAsMTA0ODU3Nl0seyJidWNrZXQiOiJmb28ifV19");
+        assert_eq!(data.signature(), "42TcKE9ODxXhAPKrbIwxdBMxbcU=");
+        assert_eq!(
+            data.form_fields(),
+            vec![
+                ("OSSAccessKeyId", "foo"),
+                ("policy", data.policy()),
+                ("Signature", data.signature()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sign_with_content_md5() {
+        use super::Client;
+        use crate::auth::CONTENT_MD5;
+        use crate::types::{CanonicalizedResource, Key, Secret};
+        use reqwest::{header::HeaderMap, Method};
+
+        let client = Client::new(Key::new("foo"), Secret::new("bar"));
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_MD5, "R7zlx09Yn0hn29V+nKn4CA==".parse().unwrap());
+
+        let res = client
+            .authorization_header(
+                &Method::PUT,
+                CanonicalizedResource::new("/bucket/abc.txt".to_owned()),
+                headers,
+            )
+            .unwrap();
+        assert_eq!(res.get(CONTENT_MD5).unwrap(), "R7zlx09Yn0hn29V+nKn4CA==");
+
+        let date = res.get("Date").unwrap().to_str().unwrap();
+        let string = format!("PUT\nR7zlx09Yn0hn29V+nKn4CA==\ntext/xml\n{date}\n/bucket/abc.txt");
+        let sign = Secret::new("bar").encryption(string.as_bytes()).unwrap();
+        assert_eq!(
+            res.get("Authorization").unwrap(),
+            &format!("OSS foo:{sign}")
+        );
+    }
+
+    #[test]
+    fn test_update_credentials() {
+        use super::Client;
+        use crate::types::{CanonicalizedResource, Key, Secret};
+        use reqwest::Method;
+
+        let client = Client::new(Key::new("foo"), Secret::new("bar"));
+        let shared = client.clone();
+        let sign = |client: &Client| {
+            client
+                .authorization(
+                    &Method::GET,
+                    CanonicalizedResource::new("/bucket/abc.txt".to_owned()),
+                )
+                .unwrap()
+        };
+        let res = sign(&shared);
+        assert!(res["Authorization"]
+            .to_str()
+            .unwrap()
+            .starts_with("OSS foo:"));
+        assert!(res.get("x-oss-security-token").is_none());
+
+        client.update_credentials(
+            Key::new("foo2"),
+            Secret::new("bar2"),
+            Some("tok".to_owned()),
+        );
+
+        let res = sign(&shared);
+        let date = res.get("Date").unwrap().to_str().unwrap();
+        let string = format!("GET\n\ntext/xml\n{date}\nx-oss-security-token:tok\n/bucket/abc.txt");
+        let signature = Secret::new("bar2").encryption(string.as_bytes()).unwrap();
+        assert_eq!(res["Authorization"], format!("OSS foo2:{signature}"));
+        assert_eq!(res["x-oss-security-token"], "tok");
+
+        client.update_credentials(Key::new("foo3"), Secret::new("bar3"), None);
+        let res = sign(&client);
+        assert!(res["Authorization"]
+            .to_str()
+            .unwrap()
+            .starts_with("OSS foo3:"));
+        assert!(res.get("x-oss-security-token").is_none());
+    }
+
+    #[test]
+    fn test_builder() {
+        use super::Client;
+        use crate::{
+            bucket::Bucket,
+            object::Object,
+            types::{Key, Secret},
+        };
+        use reqwest::header::{HeaderMap, USER_AGENT};
+        use std::time::Duration;
+
+        let mut defaults = HeaderMap::new();
+        defaults.insert("x-api-key", "abc".parse().unwrap());
+        let client = Client::builder()
+            .key(Key::new("foo"))
+            .secret(Secret::new("bar"))
+            .security_token("tk")
+            .bucket(Bucket::new("honglei123", EndPoint::CN_SHANGHAI))
+            .internal(true)
+            .timeout(Duration::from_secs(5))
+            .user_agent("my-app/1.0")
+            .default_headers(defaults)
+            .max_concurrent_requests(4)
+            .build()
+            .unwrap();
+        assert_eq!(client.max_concurrent_requests(), Some(4));
+
+        let request = Object::new("a.txt")
+            .upload_dry_run(3, HeaderMap::new(), &client)
+            .unwrap();
+        assert_eq!(
+            request.url().as_str(),
+            "https://honglei123.oss-cn-shanghai-internal.aliyuncs.com/a.txt"
+        );
+        let headers = request.headers();
+        assert_eq!(headers[USER_AGENT], "my-app/1.0");
+        assert_eq!(headers["x-api-key"], "abc");
+        assert_eq!(headers["x-oss-security-token"], "tk");
+
+        let res = Client::builder().secret(Secret::new("bar")).build();
+        assert!(matches!(res, Err(crate::Error::NoFoundCredentials)));
+    }
+
+    #[test]
+    fn test_from_url() {
+        use super::Client;
+        use crate::{
+            bucket::Bucket,
+            types::{Key, Secret},
+        };
+
+        let client = Client::from_url(
+            "https://foo-bar.oss-cn-hangzhou.aliyuncs.com/path/a.txt",
+            Key::new("foo"),
+            Secret::new("bar"),
+        )
+        .unwrap();
+        let bucket = client.bucket().unwrap();
+        assert_eq!(bucket, &Bucket::new("foo-bar", EndPoint::CN_HANGZHOU));
+        assert!(!bucket.endpoint().is_internal());
+
+        let client = Client::from_url(
+            "http://foo-bar.oss-cn-shanghai-internal.aliyuncs.com",
+            Key::new("foo"),
+            Secret::new("bar"),
+        )
+        .unwrap();
+        let endpoint = client.bucket().unwrap().endpoint();
+        assert_eq!(endpoint.region_id(), "cn-shanghai");
+        assert!(endpoint.is_internal());
+
+        for url in [
+            "https://oss-cn-hangzhou.aliyuncs.com",
+            "https://ab.oss-cn-hangzhou.aliyuncs.com",
+            "https://foo.example.com",
+            "not a url",
+        ] {
+            assert!(Client::from_url(url, Key::new("foo"), Secret::new("bar")).is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_restore_objects_mixed() {
+        use super::Client;
+        use crate::{
+            bucket::Bucket,
+            object::RestoreStatus,
+            types::{Key, Secret},
+        };
+
+        let error = |code: &str| {
+            format!(
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+                <Error><Code>{code}</Code><Message></Message><RequestId>1</RequestId></Error>"
+            )
+        };
+        let mut client = Client::new(Key::new("foo"), Secret::new("bar"));
+        client.set_bucket(Bucket::new("foo", EndPoint::CN_QINGDAO));
+        client.set_mock(move |request| {
+            let (status, body) = match request.url().path() {
+                "/archive.txt" => (202, String::new()),
+                "/restored.txt" => (200, String::new()),
+                "/restoring.txt" => (409, error("RestoreAlreadyInProgress")),
+                "/standard.txt" => (400, error("OperationNotSupported")),
+                _ => (404, error("NoSuchKey")),
+            };
+            http::Response::builder()
+                .status(status)
+                .body(body)
+                .unwrap()
+                .into()
+        });
+
+        let paths = [
+            "archive.txt",
+            "restored.txt",
+            "restoring.txt",
+            "standard.txt",
+            "missing.txt",
+        ];
+        let results = client.restore_objects(&paths, 1).await;
+        let statuses: Vec<_> = results[..4]
+            .iter()
+            .map(|res| *res.as_ref().unwrap())
+            .collect();
+        assert_eq!(
+            statuses,
+            [
+                RestoreStatus::Started,
+                RestoreStatus::AlreadyRestored,
+                RestoreStatus::InProgress,
+                RestoreStatus::NotArchived,
+            ]
+        );
+        assert!(matches!(&results[4], Err(crate::Error::Service(e)) if e.code() == "NoSuchKey"));
+    }
+
+    #[tokio::test]
+    async fn test_get_buckets_mocked() {
+        use super::Client;
+        use crate::{
+            bucket::Bucket,
+            types::{Key, Secret},
+        };
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListAllMyBucketsResult>
+  <Buckets>
+    <Bucket>
+      <CreationDate>2014-02-17T18:12:43.000Z</CreationDate>
+      <Location>oss-cn-shanghai</Location>
+      <Name>app-base-oss</Name>
+    </Bucket>
+    <Bucket>
+      <CreationDate>2014-02-25T11:21:04.000Z</CreationDate>
+      <Location>oss-cn-hangzhou</Location>
+      <Name>mybucket</Name>
+    </Bucket>
+  </Buckets>
+</ListAllMyBucketsResult>"#;
+        let mut client = Client::new(Key::new("foo"), Secret::new("bar"));
+        client.set_mock(move |_| http::Response::new(xml).into());
+
+        let buckets = client.get_buckets(&EndPoint::CN_QINGDAO).await.unwrap();
+        assert_eq!(
+            buckets,
+            [
+                Bucket::new("app-base-oss", EndPoint::CN_SHANGHAI),
+                Bucket::new("mybucket", EndPoint::CN_HANGZHOU),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_max_concurrent_requests() {
+        use super::Client;
+        use crate::types::{CanonicalizedResource, Key, Secret};
+        use futures_util::future::join_all;
+        use http_body::{Body, Frame};
+        use reqwest::{header::HeaderMap, Method};
+        use std::{
+            convert::Infallible,
+            pin::Pin,
+            sync::{
+                atomic::{AtomicUsize, Ordering},
+                Arc,
+            },
+            task::{Context, Poll},
+        };
+
+        /// 每读取一块内容前都让出一次，响应被丢弃时视为请求结束
+        struct SlowBody {
+            chunks: usize,
+            ready: bool,
+            in_flight: Arc<AtomicUsize>,
+        }
+
+        impl Body for SlowBody {
+            type Data = &'static [u8];
+            type Error = Infallible;
+
+            fn poll_frame(
+                mut self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+            ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+                if self.chunks == 0 {
+                    return Poll::Ready(None);
+                }
+                if !self.ready {
+                    self.ready = true;
+                    cx.waker().wake_by_ref();
+                    return Poll::Pending;
+                }
+                self.ready = false;
+                self.chunks -= 1;
+                Poll::Ready(Some(Ok(Frame::data(b"abc".as_slice()))))
+            }
+        }
+
+        impl Drop for SlowBody {
+            fn drop(&mut self) {
+                self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            }
+        }
+
+        let mut client = Client::new(Key::new("foo"), Secret::new("bar"));
+        assert_eq!(client.max_concurrent_requests(), None);
+        client.set_max_concurrent_requests(Some(3));
+        assert_eq!(client.max_concurrent_requests(), Some(3));
+
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        {
+            let (in_flight, peak) = (in_flight.clone(), peak.clone());
+            client.set_mock(move |_| {
+                let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(current, Ordering::SeqCst);
+                let body = SlowBody {
+                    chunks: 3,
+                    ready: false,
+                    in_flight: in_flight.clone(),
+                };
+                http::Response::new(reqwest::Body::wrap(body)).into()
+            });
+        }
+
+        let cloned = client.clone();
+        let tasks = (0..20).map(|i| {
+            let client = if i % 2 == 0 { &client } else { &cloned };
+            async move {
+                let response = client
+                    .send(
+                        Method::GET,
+                        "https://foo.oss-cn-qingdao.aliyuncs.com/a.txt"
+                            .parse()
+                            .unwrap(),
+                        CanonicalizedResource::new("/foo/a.txt".to_owned()),
+                        HeaderMap::new(),
+                        None,
+                    )
+                    .await
+                    .unwrap();
+                response.bytes().await.unwrap()
+            }
+        });
+        let contents = join_all(tasks).await;
+
+        assert!(contents.iter().all(|c| c.as_ref() == b"abcabcabc"));
+        assert_eq!(peak.load(Ordering::SeqCst), 3);
+        assert_eq!(in_flight.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_default_headers() {
+        use super::Client;
+        use crate::types::{CanonicalizedResource, Key, Secret};
+        use reqwest::{header::HeaderMap, Method};
+
+        let mut client = Client::new(Key::new("foo"), Secret::new("bar"));
+        let mut defaults = HeaderMap::new();
+        defaults.insert("x-api-key", "abc".parse().unwrap());
+        defaults.insert("x-oss-meta-from", "gateway".parse().unwrap());
+        client.set_default_headers(defaults);
+
+        for method in [Method::GET, Method::PUT] {
+            let res = client
+                .authorization(
+                    &method,
+                    CanonicalizedResource::new("/bucket/abc.txt".to_owned()),
+                )
+                .unwrap();
+            assert_eq!(res["x-api-key"], "abc");
+
+            let date = res.get("Date").unwrap().to_str().unwrap();
+            let string = format!(
+                "{}\n\ntext/xml\n{date}\nx-oss-meta-from:gateway\n/bucket/abc.txt",
+                method.as_str()
+            );
+            let sign = Secret::new("bar").encryption(string.as_bytes()).unwrap();
+            assert_eq!(res["Authorization"], format!("OSS foo:{sign}"));
+        }
+
+        // 请求本身携带的 header 优先
+        let mut headers = HeaderMap::new();
+        headers.insert("x-api-key", "override".parse().unwrap());
+        let res = client
+            .authorization_header(
+                &Method::GET,
+                CanonicalizedResource::new("/bucket/".to_owned()),
+                headers,
+            )
+            .unwrap();
+        assert_eq!(res["x-api-key"], "override");
+        assert_eq!(res["x-oss-meta-from"], "gateway");
+    }
+
+    #[test]
+    fn test_sign_with_content_type() {
+        use super::Client;
+        use crate::types::{CanonicalizedResource, Key, Secret};
+        use reqwest::{
+            header::{HeaderMap, CONTENT_TYPE},
+            Method,
+        };
+
+        let client = Client::new(Key::new("foo"), Secret::new("bar"));
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, "image/png".parse().unwrap());
+
+        let res = client
+            .authorization_header(
+                &Method::PUT,
+                CanonicalizedResource::new("/bucket/abc.png".to_owned()),
+                headers,
+            )
+            .unwrap();
+        assert_eq!(res.get(CONTENT_TYPE).unwrap(), "image/png");
+
+        let date = res.get("Date").unwrap().to_str().unwrap();
+        let string = format!("PUT\n\nimage/png\n{date}\n/bucket/abc.png");
+        let sign = Secret::new("bar").encryption(string.as_bytes()).unwrap();
+        assert_eq!(
+            res.get("Authorization").unwrap(),
+            &format!("OSS foo:{sign}")
+        );
+    }
+
+    #[test]
+    fn test_parse_date_header() {
+        use super::parse_date_header;
+        use chrono::{TimeZone, Utc};
+        use reqwest::header::{HeaderMap, DATE};
+
+        let mut headers = HeaderMap::new();
+        assert!(parse_date_header(&headers).is_err());
+
+        headers.insert(DATE, "Wed, 20 Mar 2024 08:00:00 GMT".parse().unwrap());
+        assert_eq!(
+            parse_date_header(&headers).unwrap(),
+            Utc.with_ymd_and_hms(2024, 3, 20, 8, 0, 0).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_server_time() {
+        use super::Client;
+        use crate::types::{Key, Secret};
+        use chrono::{TimeZone, Utc};
+        use std::sync::{Arc, Mutex};
+
+        let mut client = Client::new(Key::new("foo"), Secret::new("bar"));
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        {
+            let requests = requests.clone();
+            client.set_mock(move |request| {
+                requests
+                    .lock()
+                    .unwrap()
+                    .push(format!("{} {}", request.method(), request.url()));
+                // 签名过期等错误的响应中同样带有 Date
+                http::Response::builder()
+                    .status(403)
+                    .header("date", "Wed, 20 Mar 2024 08:00:00 GMT")
+                    .body("")
+                    .unwrap()
+                    .into()
+            });
+        }
+
+        let time = client.server_time(&EndPoint::CN_QINGDAO).await.unwrap();
+        assert_eq!(time, Utc.with_ymd_and_hms(2024, 3, 20, 8, 0, 0).unwrap());
+        assert_eq!(
+            *requests.lock().unwrap(),
+            ["HEAD https://oss-cn-qingdao.aliyuncs.com/"]
+        );
+
+        client.set_mock(|_| http::Response::new("").into());
+        assert!(matches!(
+            client.server_time(&EndPoint::CN_QINGDAO).await,
+            Err(crate::Error::NoFoundDate)
+        ));
+    }
+
+    #[test]
+    fn test_transfer_sizes() {
+        use super::{Client, MAX_PART_SIZE, MIN_PART_SIZE};
+        use crate::{
+            object::DOWNLOAD_PART_SIZE,
+            types::{Key, Secret},
+        };
+
+        let mut client = Client::new(Key::new("foo"), Secret::new("bar"));
+        assert_eq!(client.part_size(), 200 * 1024 * 1024);
+        assert_eq!(client.multipart_threshold(), 200 * 1024 * 1024);
+        assert_eq!(client.download_part_size(), DOWNLOAD_PART_SIZE);
+
+        client.set_part_size(MIN_PART_SIZE).unwrap();
+        client.set_multipart_threshold(1024);
+        client.set_download_part_size(MAX_PART_SIZE).unwrap();
+        assert_eq!(client.part_size(), MIN_PART_SIZE);
+        assert_eq!(client.multipart_threshold(), 1024);
+        assert_eq!(client.download_part_size(), MAX_PART_SIZE);
+
+        assert!(client.set_part_size(MIN_PART_SIZE - 1).is_err());
+        assert!(client.set_download_part_size(MAX_PART_SIZE + 1).is_err());
+        assert_eq!(client.part_size(), MIN_PART_SIZE);
+
+        let cloned = client.clone();
+        assert_eq!(cloned.part_size(), MIN_PART_SIZE);
+
+        client.set_part_size_mb(5120).unwrap();
+        assert_eq!(client.part_size(), MAX_PART_SIZE);
+        assert!(matches!(
+            client.set_part_size_mb(6000),
+            Err(crate::Error::InvalidPartSize)
+        ));
+        assert!(client.set_part_size_mb(0).is_err());
+        assert_eq!(client.part_size(), MAX_PART_SIZE);
+    }
+
+    #[test]
+    fn test_redirect_url() {
+        use super::redirect_url;
+        use url::Url;
+
+        let url = Url::parse("https://foo.oss-cn-qingdao.aliyuncs.com/a/b.txt?objectMeta").unwrap();
+        let url = redirect_url(&url, "oss-cn-hangzhou.aliyuncs.com").unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://foo.oss-cn-hangzhou.aliyuncs.com/a/b.txt?objectMeta"
+        );
+
+        let url = Url::parse("https://oss-cn-qingdao.aliyuncs.com/").unwrap();
+        let url = redirect_url(&url, "oss-cn-hangzhou.aliyuncs.com").unwrap();
+        assert_eq!(url.as_str(), "https://oss-cn-hangzhou.aliyuncs.com/");
+    }
+
+    #[test]
+    fn test_fallback_urls() {
+        use super::fallback_urls;
+        use url::Url;
+
+        let mut internal = EndPoint::CN_QINGDAO;
+        internal.set_internal(true);
+        let endpoints = [EndPoint::CN_QINGDAO, internal, EndPoint::CN_HANGZHOU];
+
+        let url = Url::parse("https://foo.oss-cn-qingdao-internal.aliyuncs.com/a.txt").unwrap();
+        let urls: Vec<_> = fallback_urls(&url, &endpoints)
+            .into_iter()
+            .map(String::from)
+            .collect();
+        assert_eq!(
+            urls,
+            [
+                "https://foo.oss-cn-qingdao.aliyuncs.com/a.txt",
+                "https://foo.oss-cn-hangzhou.aliyuncs.com/a.txt"
+            ]
+        );
+
+        let url = Url::parse("https://cdn.example.com/a.txt").unwrap();
+        assert!(fallback_urls(&url, &endpoints).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_with_failover() {
+        use super::with_failover;
+        use crate::error::OssError;
+        use std::sync::Mutex;
+        use url::Url;
+
+        // 端口未监听，连接会被拒绝
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+        let unreachable = Url::parse(&format!("http://{addr}/")).unwrap();
+        let fallback = Url::parse("https://foo.oss-cn-qingdao.aliyuncs.com/").unwrap();
+
+        let tried = Mutex::new(Vec::new());
+        let send = |url: Url| {
+            tried.lock().unwrap().push(url.clone());
+            async move {
+                if url.host_str() == Some("127.0.0.1") {
+                    reqwest::get(url).await?;
+                    unreachable!("the port is not listening")
+                }
+                Ok::<_, OssError>(url)
+            }
+        };
+
+        let res = with_failover(unreachable.clone(), vec![fallback.clone()], send).await;
+        assert_eq!(res.unwrap(), fallback);
+        assert_eq!(
+            *tried.lock().unwrap(),
+            [unreachable.clone(), fallback.clone()]
+        );
+
+        // 非连接错误不会切换
+        tried.lock().unwrap().clear();
+        let res = with_failover(unreachable.clone(), vec![fallback], |url| {
+            tried.lock().unwrap().push(url);
+            async { Err::<(), _>(OssError::NoFoundBucket) }
+        })
+        .await;
+        assert!(matches!(res, Err(OssError::NoFoundBucket)));
+        assert_eq!(*tried.lock().unwrap(), [unreachable]);
+    }
+
+    #[test]
+    fn test_redirect_error() {
+        use crate::error::OssError;
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Error>
+  <Code>PermanentRedirect</Code>
+  <Message>The bucket you are attempting to access must be addressed using the specified endpoint. Please send all future requests to this endpoint.</Message>
+  <RequestId>5C3D9175B6FC201293AD****</RequestId>
+  <HostId>foo.oss-cn-qingdao.aliyuncs.com</HostId>
+  <Bucket>foo</Bucket>
+  <Endpoint>oss-cn-hangzhou.aliyuncs.com</Endpoint>
+</Error>"#;
+        match OssError::from_status(reqwest::StatusCode::MOVED_PERMANENTLY, xml) {
+            OssError::Service(xml) => {
+                assert!(xml.is_redirect());
+                assert_eq!(xml.endpoint(), Some("oss-cn-hangzhou.aliyuncs.com"));
+            }
+            _ => panic!("should be service error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_follow_redirect() {
+        use super::Client;
+        use crate::types::{CanonicalizedResource, Key, Secret};
+        use reqwest::{
+            header::{HeaderMap, AUTHORIZATION},
+            Method,
+        };
+        use std::sync::{Arc, Mutex};
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Error>
+  <Code>PermanentRedirect</Code>
+  <Message>The bucket you are attempting to access must be addressed using the specified endpoint. Please send all future requests to this endpoint.</Message>
+  <RequestId>5C3D9175B6FC201293AD****</RequestId>
+  <HostId>foo.oss-cn-qingdao.aliyuncs.com</HostId>
+  <Bucket>foo</Bucket>
+  <Endpoint>oss-cn-hangzhou.aliyuncs.com</Endpoint>
+</Error>"#;
+
+        let mut client = Client::new(Key::new("foo"), Secret::new("bar"));
+        client.set_follow_redirect(true);
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        {
+            let requests = requests.clone();
+            client.set_mock(move |request| {
+                let host = request.url().host_str().unwrap().to_owned();
+                let signed = request.headers().contains_key(AUTHORIZATION);
+                requests.lock().unwrap().push((host.clone(), signed));
+                let response = if host.contains("oss-cn-qingdao") {
+                    http::Response::builder().status(301).body(xml)
+                } else {
+                    http::Response::builder().body("hello")
+                };
+                response.unwrap().into()
+            });
+        }
+
+        let response = client
+            .send(
+                Method::GET,
+                "https://foo.oss-cn-qingdao.aliyuncs.com/a.txt"
+                    .parse()
+                    .unwrap(),
+                CanonicalizedResource::new("/foo/a.txt".to_owned()),
+                HeaderMap::new(),
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.text().await.unwrap(), "hello");
+        assert_eq!(
+            *requests.lock().unwrap(),
+            [
+                ("foo.oss-cn-qingdao.aliyuncs.com".to_owned(), true),
+                ("foo.oss-cn-hangzhou.aliyuncs.com".to_owned(), true),
+            ]
+        );
+
+        // 关闭时直接返回重定向错误
+        client.set_follow_redirect(false);
+        requests.lock().unwrap().clear();
+        let res = client
+            .send(
+                Method::GET,
+                "https://foo.oss-cn-qingdao.aliyuncs.com/a.txt"
+                    .parse()
+                    .unwrap(),
+                CanonicalizedResource::new("/foo/a.txt".to_owned()),
+                HeaderMap::new(),
+                None,
+            )
+            .await;
+        assert!(matches!(res, Err(crate::error::OssError::Service(xml)) if xml.is_redirect()));
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    #[cfg(feature = "global")]
+    #[test]
+    fn test_global() {
+        use super::{get_or_try_init, Client};
+        use crate::types::{Key, Secret};
+        use std::sync::OnceLock;
+
+        // 使用独立的 OnceLock 和显式的凭证，不读写环境变量，不影响并行运行的其他测试
+        let cell = OnceLock::new();
+
+        let res = get_or_try_init(&cell, || Err("missing credentials"));
+        assert_eq!(res.unwrap_err(), "missing credentials");
+        assert!(cell.get().is_none());
+
+        let client1 = get_or_try_init(&cell, || {
+            Ok::<_, ()>(Client::new(Key::new("foo"), Secret::new("bar")))
+        })
+        .unwrap();
+        let client2 = get_or_try_init(&cell, || -> Result<Client, ()> {
+            panic!("the client is already initialized")
+        })
+        .unwrap();
+        assert!(std::ptr::eq(client1, client2));
+        assert!(std::sync::Arc::ptr_eq(&client1.auth, &client2.auth));
+    }
+
     #[tokio::test]
     async fn parse_xml() {
         use serde::Deserialize;
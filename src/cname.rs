@@ -0,0 +1,154 @@
+//! bucket 绑定的自定义域名（CNAME）
+
+use crate::{
+    bucket::{xml_escape, Bucket},
+    error::OssError,
+};
+
+/// 绑定自定义域名前，用于验证域名所有权的 token，需要添加到域名的 TXT 记录中
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CnameToken {
+    domain: String,
+    token: String,
+    expire_time: Option<String>,
+}
+
+impl CnameToken {
+    pub fn domain(&self) -> &str {
+        &self.domain
+    }
+
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// token 的过期时间，如 `Wed, 23 Feb 2022 21:16:37 GMT`
+    pub fn expire_time(&self) -> Option<&str> {
+        self.expire_time.as_deref()
+    }
+
+    pub(crate) fn from_xml(xml: &str) -> Result<Self, OssError> {
+        Ok(CnameToken {
+            domain: Bucket::parse_item(xml, "Cname")
+                .ok_or(OssError::InvalidCname)?
+                .to_owned(),
+            token: Bucket::parse_item(xml, "Token")
+                .ok_or(OssError::InvalidCname)?
+                .to_owned(),
+            expire_time: Bucket::parse_item(xml, "ExpireTime").map(|t| t.to_owned()),
+        })
+    }
+}
+
+/// bucket 已绑定的自定义域名
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CnameInfo {
+    domain: String,
+    status: String,
+    last_modified: Option<String>,
+}
+
+impl CnameInfo {
+    pub fn domain(&self) -> &str {
+        &self.domain
+    }
+
+    /// 域名的状态，如 `Enabled`，`Disabled`
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+
+    pub fn last_modified(&self) -> Option<&str> {
+        self.last_modified.as_deref()
+    }
+
+    pub(crate) fn parse_list_xml(xml: &str) -> Result<Vec<Self>, OssError> {
+        Bucket::parse_blocks(xml, "Cname")
+            .into_iter()
+            .map(|block| {
+                Ok(CnameInfo {
+                    domain: Bucket::parse_item(block, "Domain")
+                        .ok_or(OssError::InvalidCname)?
+                        .to_owned(),
+                    status: Bucket::parse_item(block, "Status")
+                        .unwrap_or_default()
+                        .to_owned(),
+                    last_modified: Bucket::parse_item(block, "LastModified").map(|t| t.to_owned()),
+                })
+            })
+            .collect()
+    }
+}
+
+/// 绑定，解绑域名及创建 token 的请求内容
+pub(crate) fn cname_xml(domain: &str) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?><BucketCnameConfiguration>\
+        <Cname><Domain>{}</Domain></Cname></BucketCnameConfiguration>",
+        xml_escape(domain)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cname_xml, CnameInfo, CnameToken};
+
+    #[test]
+    fn test_cname_xml() {
+        assert!(cname_xml("example.com").ends_with(
+            "<BucketCnameConfiguration><Cname><Domain>example.com</Domain></Cname>\
+            </BucketCnameConfiguration>"
+        ));
+    }
+
+    #[test]
+    fn test_token_from_xml() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <CnameToken>
+          <Bucket>examplebucket</Bucket>
+          <Cname>example.com</Cname>
+          <Token>be1d49d863dea9ffeff3df7d6455****</Token>
+          <ExpireTime>Wed, 23 Feb 2022 21:16:37 GMT</ExpireTime>
+        </CnameToken>"#;
+
+        let token = CnameToken::from_xml(xml).unwrap();
+        assert_eq!(token.domain(), "example.com");
+        assert_eq!(token.token(), "be1d49d863dea9ffeff3df7d6455****");
+        assert_eq!(token.expire_time(), Some("Wed, 23 Feb 2022 21:16:37 GMT"));
+
+        assert!(CnameToken::from_xml("<CnameToken></CnameToken>").is_err());
+    }
+
+    #[test]
+    fn test_parse_list_xml() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <ListCnameResult>
+          <Bucket>targetbucket</Bucket>
+          <Owner>testowner</Owner>
+          <Cname>
+            <Domain>example.com</Domain>
+            <LastModified>2021-09-15T02:35:07.000Z</LastModified>
+            <Status>Enabled</Status>
+          </Cname>
+          <Cname>
+            <Domain>example.org</Domain>
+            <LastModified>2021-09-15T02:34:58.000Z</LastModified>
+            <Status>Disabled</Status>
+          </Cname>
+        </ListCnameResult>"#;
+
+        let list = CnameInfo::parse_list_xml(xml).unwrap();
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[0].domain(), "example.com");
+        assert_eq!(list[0].status(), "Enabled");
+        assert_eq!(list[0].last_modified(), Some("2021-09-15T02:35:07.000Z"));
+        assert_eq!(list[1].domain(), "example.org");
+        assert_eq!(list[1].status(), "Disabled");
+
+        assert!(
+            CnameInfo::parse_list_xml("<ListCnameResult></ListCnameResult>")
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -4,22 +4,54 @@ use std::{
     num::ParseIntError,
 };
 
-use reqwest::header::{InvalidHeaderValue, ToStrError};
+use http::{
+    header::{HeaderMap, InvalidHeaderValue, ToStrError, CONTENT_RANGE},
+    StatusCode,
+};
 use serde::Deserialize;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum OssError {
-    Reqwest(#[from] reqwest::Error),
+    #[cfg(feature = "client")]
+    Reqwest(#[source] reqwest::Error),
+
+    #[cfg(feature = "client")]
+    /// 无法建立连接
+    Connect(#[source] reqwest::Error),
+
+    #[cfg(feature = "client")]
+    /// 请求超时
+    Timeout(#[source] reqwest::Error),
+
+    #[cfg(feature = "client")]
+    /// 域名解析失败
+    Dns(#[source] reqwest::Error),
+
+    /// 服务端返回了非 2xx 的状态码，且响应内容不是 OSS 的错误格式，如网关返回的 503
+    ServerStatus(StatusCode),
+
+    /// 请求的范围超出了文件大小，对应 416 状态码，附带从 `Content-Range: bytes */12345`
+    /// 中解析出的文件大小，可据此调整范围后重试
+    RangeNotSatisfiable(u64),
+
+    /// 请求中的条件（如 `If-Match`，`x-oss-copy-source-if-match`）不满足，对应 412 状态码
+    PreconditionFailed,
 
     HeaderValue(#[from] InvalidHeaderValue),
 
     Chrono(#[from] chrono::ParseError),
 
+    Url(#[from] url::ParseError),
+
     ToStrError(#[from] ToStrError),
 
     VarError(#[from] VarError),
 
+    Io(#[from] std::io::Error),
+
+    FromUtf8(#[from] std::string::FromUtf8Error),
+
     NoFoundCreationDate,
 
     NoFoundStorageClass,
@@ -30,35 +62,257 @@ pub enum OssError {
 
     NoFoundEtag,
 
+    EtagMismatch,
+
+    NoFoundCrc64,
+
+    /// 追加后服务端返回的 CRC64 值与本地计算的不一致
+    Crc64Mismatch,
+
+    /// 下载的内容与响应中的 `Content-MD5` 不一致
+    Md5Mismatch,
+
+    /// 下载完成后本地文件的大小与 OSS 中的文件大小不一致
+    SizeMismatch {
+        expected: u64,
+        actual: u64,
+    },
+
+    NoFoundNextAppendPosition,
+
     NoFoundLastModified,
 
+    /// 修改时间的格式不正确，附带原始内容
+    InvalidLastModified(Box<str>),
+
+    /// 调用方提供的 `Date` 不是 GMT 格式，附带原始内容
+    InvalidDate(Box<str>),
+
+    NoFoundDate,
+
+    /// 文件路径不合法，附带该路径
+    InvalidObjectPath(Box<str>),
+
+    NoFoundKey,
+
+    NoFoundSymlinkTarget,
+
     ParseIntError(#[from] ParseIntError),
 
     Service(ServiceXML),
 
     NoFoundBucket,
 
+    BucketNotEmpty,
+
     ParseXml(#[from] serde_xml_rs::Error),
 
+    #[cfg(feature = "client")]
+    Json(#[from] serde_json::Error),
+
+    InvalidSecret,
+
+    /// 创建 Client 时没有设置 key 或 secret
+    NoFoundCredentials,
+
     InvalidEndPoint,
 
     InvalidBucket,
 
-    InvalidOssError(String),
+    InvalidBucketName,
+
+    InvalidBucketNameLength,
+
+    InvalidBucketNameIp,
+
+    InvalidTagging,
+
+    InvalidGrant,
+
+    InvalidPayer,
+
+    InvalidTransferAcceleration,
+
+    /// 访问跟踪的状态不是 `Enabled` 或 `Disabled`
+    InvalidAccessMonitor,
+
+    /// 清单配置的内容不正确
+    InvalidInventory,
+
+    /// 合规保留策略的内容不正确
+    InvalidWorm,
+
+    /// 自定义域名的响应内容不正确
+    InvalidCname,
+
+    /// 图片样式的响应内容不正确
+    InvalidStyle,
+
+    /// 不是 base64 编码的 16 字节 MD5 值
+    InvalidContentMd5,
+
+    /// 下载时使用的字节范围为空或者起止位置颠倒，如 `0..0`，`10..5`
+    InvalidRange,
+
+    InvalidVersions,
+
+    InvalidPartSize,
+
+    NoFoundUploadId,
+
+    TooManyParts,
+
+    /// 分片写入时有分片上传失败，分片上传任务已取消，不能继续写入
+    WriterFailed,
 }
 
 impl OssError {
-    pub(crate) fn from_service(xml: &str) -> Self {
+    /// 解析非 2xx 响应的内容，内容不是 OSS 的错误格式时（如 HEAD 请求，网关错误页），
+    /// 返回 `ServerStatus`
+    pub(crate) fn from_status(status: StatusCode, xml: &str) -> Self {
+        if status == StatusCode::PRECONDITION_FAILED {
+            return Self::PreconditionFailed;
+        }
         match ServiceXML::new(xml) {
-            Ok(xml) => Self::Service(xml),
-            Err(e) => Self::InvalidOssError(e.to_string()),
+            Ok(mut xml) => {
+                xml.status = Some(status);
+                Self::Service(xml)
+            }
+            Err(_) => Self::ServerStatus(status),
+        }
+    }
+
+    /// 服务端返回的 HTTP 状态码，不是服务端返回的错误时为 `None`
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::Service(xml) => xml.status,
+            Self::ServerStatus(status) => Some(*status),
+            Self::RangeNotSatisfiable(_) => Some(StatusCode::RANGE_NOT_SATISFIABLE),
+            Self::PreconditionFailed => Some(StatusCode::PRECONDITION_FAILED),
+            _ => None,
+        }
+    }
+
+    /// 文件，bucket 或分片上传等资源不存在，如 `NoSuchKey`，`NoSuchBucket`，
+    /// 以及没有响应内容的 404（如 HEAD 请求）
+    pub fn is_not_found(&self) -> bool {
+        match self {
+            Self::Service(xml) => xml.code.starts_with("NoSuch"),
+            _ => self.status() == Some(StatusCode::NOT_FOUND),
+        }
+    }
+
+    /// 没有访问权限，签名不匹配不属于此类，参见 [`is_signature_mismatch`](Self::is_signature_mismatch)
+    pub fn is_access_denied(&self) -> bool {
+        match self {
+            Self::Service(xml) => xml.code == ACCESS_DENIED,
+            _ => self.status() == Some(StatusCode::FORBIDDEN),
+        }
+    }
+
+    /// 本地计算的签名与 OSS 计算的不一致，通常是 key secret 错误或签名路径不正确
+    pub fn is_signature_mismatch(&self) -> bool {
+        matches!(self, Self::Service(xml) if xml.code == SIGNATURE_DOES_NOT_MATCH)
+    }
+
+    /// 416 响应中带有 `Content-Range: bytes */<size>` 时，返回 `RangeNotSatisfiable`
+    pub(crate) fn from_range_not_satisfiable(
+        status: StatusCode,
+        headers: &HeaderMap,
+    ) -> Option<Self> {
+        if status != StatusCode::RANGE_NOT_SATISFIABLE {
+            return None;
+        }
+        let size = headers
+            .get(CONTENT_RANGE)?
+            .to_str()
+            .ok()?
+            .strip_prefix("bytes */")?
+            .parse()
+            .ok()?;
+        Some(Self::RangeNotSatisfiable(size))
+    }
+
+    /// 与 `from_status` 相同，当错误为签名不匹配时，会附带本地计算的签名路径和待签名字符串，
+    /// 方便与 OSS 返回的结果对比
+    pub(crate) fn from_service_with_sign(
+        status: StatusCode,
+        xml: &str,
+        resource: String,
+        string_to_sign: String,
+    ) -> Self {
+        match Self::from_status(status, xml) {
+            Self::Service(mut xml) if xml.code == SIGNATURE_DOES_NOT_MATCH => {
+                xml.local_sign = Some(Box::new(LocalSign {
+                    resource,
+                    string_to_sign,
+                }));
+                Self::Service(xml)
+            }
+            err => err,
+        }
+    }
+}
+
+#[cfg(feature = "client")]
+/// 根据 reqwest 的错误类型区分连接失败，超时和域名解析失败
+impl From<reqwest::Error> for OssError {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_timeout() {
+            Self::Timeout(err)
+        } else if err.is_connect() {
+            if is_dns_error(&err) {
+                Self::Dns(err)
+            } else {
+                Self::Connect(err)
+            }
+        } else {
+            Self::Reqwest(err)
+        }
+    }
+}
+
+/// 根据错误链最底层的 `io::Error` 判断是否为域名解析失败：建立连接失败时该错误来自系统调用，
+/// 带有系统错误码；域名解析失败时来自 getaddrinfo，没有系统错误码，也不是 TLS 等错误使用的 `Other`
+#[cfg(feature = "client")]
+fn is_dns_error(err: &(dyn std::error::Error + 'static)) -> bool {
+    use std::io::ErrorKind;
+
+    let mut io_error = None;
+    let mut source = Some(err);
+    while let Some(e) = source {
+        if let Some(e) = e.downcast_ref::<std::io::Error>() {
+            io_error = Some(e);
         }
+        source = e.source();
     }
+
+    io_error.is_some_and(|e| {
+        e.raw_os_error().is_none()
+            && !matches!(
+                e.kind(),
+                ErrorKind::Other | ErrorKind::TimedOut | ErrorKind::Interrupted
+            )
+    })
 }
 
+const SIGNATURE_DOES_NOT_MATCH: &str = "SignatureDoesNotMatch";
+const ACCESS_DENIED: &str = "AccessDenied";
+
 impl Display for OssError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        "oss error".fmt(f)
+        match self {
+            Self::Service(ServiceXML {
+                code,
+                local_sign: Some(sign),
+                ..
+            }) => write!(
+                f,
+                "oss error: {}, local canonicalized resource: {}, local string to sign: {:?}",
+                code, sign.resource, sign.string_to_sign
+            ),
+            _ => "oss error".fmt(f),
+        }
     }
 }
 
@@ -75,12 +329,301 @@ pub struct ServiceXML {
     #[serde(rename = "RequestId")]
     request_id: String,
 
-    #[serde(rename = "RecommendDoc")]
-    recommend_doc: String,
+    #[serde(rename = "RecommendDoc", default)]
+    recommend_doc: Box<str>,
+
+    #[serde(rename = "Endpoint")]
+    endpoint: Option<Box<str>>,
+
+    /// 签名不匹配时，本地计算的签名信息
+    #[serde(skip)]
+    local_sign: Option<Box<LocalSign>>,
+
+    /// 响应的状态码
+    #[serde(skip)]
+    status: Option<StatusCode>,
+}
+
+#[derive(Debug)]
+struct LocalSign {
+    resource: String,
+    string_to_sign: String,
 }
 impl ServiceXML {
     fn new(xml: &str) -> Result<Self, serde_xml_rs::Error> {
         //println!("{xml}");
         serde_xml_rs::from_str(xml)
     }
+
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn request_id(&self) -> &str {
+        &self.request_id
+    }
+
+    /// 区域重定向错误中，OSS 给出的正确 endpoint，如 `oss-cn-hangzhou.aliyuncs.com`
+    pub fn endpoint(&self) -> Option<&str> {
+        self.endpoint.as_deref()
+    }
+
+    /// 是否为访问 bucket 时使用了错误区域的 endpoint 导致的错误
+    pub fn is_redirect(&self) -> bool {
+        matches!(
+            self.code.as_str(),
+            "PermanentRedirect" | "TemporaryRedirect" | ACCESS_DENIED
+        ) && self.endpoint.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::StatusCode;
+
+    use super::OssError;
+
+    #[test]
+    fn test_source_chain() {
+        use std::error::Error;
+
+        let err: OssError = std::io::Error::other("disk full").into();
+        let source = err.source().unwrap();
+        assert_eq!(source.to_string(), "disk full");
+        assert!(source.downcast_ref::<std::io::Error>().is_some());
+
+        let err: OssError = "abc".parse::<u64>().unwrap_err().into();
+        assert!(err
+            .source()
+            .unwrap()
+            .downcast_ref::<std::num::ParseIntError>()
+            .is_some());
+
+        let err: OssError = String::from_utf8(vec![0xff]).unwrap_err().into();
+        assert!(err
+            .source()
+            .unwrap()
+            .downcast_ref::<std::string::FromUtf8Error>()
+            .is_some());
+
+        // 请求相关的错误同样保留原始的 reqwest 错误
+        #[cfg(feature = "client")]
+        {
+            let err: OssError = reqwest::Client::new()
+                .get("not a url")
+                .build()
+                .unwrap_err()
+                .into();
+            assert!(matches!(err, OssError::Reqwest(_)));
+            assert!(err
+                .source()
+                .unwrap()
+                .downcast_ref::<reqwest::Error>()
+                .is_some());
+        }
+
+        assert!(OssError::NoFoundEtag.source().is_none());
+    }
+
+    #[test]
+    fn test_signature_does_not_match() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Error>
+  <Code>SignatureDoesNotMatch</Code>
+  <Message>The request signature we calculated does not match the signature you provided. Check your key and signing method.</Message>
+  <RequestId>65FA6A5B8D9B7C3734C1****</RequestId>
+  <HostId>foo.oss-cn-qingdao.aliyuncs.com</HostId>
+  <OSSAccessKeyId>foo</OSSAccessKeyId>
+  <SignatureProvided>abc</SignatureProvided>
+  <StringToSign>GET\n\ntext/xml\nWed, 20 Mar 2024 08:00:00 GMT\n/foo/abc.txt</StringToSign>
+</Error>"#;
+        let err = OssError::from_service_with_sign(
+            StatusCode::FORBIDDEN,
+            xml,
+            "/foo/abc.txt".to_owned(),
+            "GET\n\ntext/xml\nWed, 20 Mar 2024 08:00:00 GMT\n/foo/abc.txt".to_owned(),
+        );
+        let message = err.to_string();
+        assert!(message.contains("SignatureDoesNotMatch"));
+        assert!(message.contains("local canonicalized resource: /foo/abc.txt"));
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Error>
+  <Code>NoSuchKey</Code>
+  <Message>The specified key does not exist.</Message>
+  <RequestId>65FA6A5B8D9B7C3734C1****</RequestId>
+</Error>"#;
+        let err = OssError::from_service_with_sign(
+            StatusCode::NOT_FOUND,
+            xml,
+            "/foo/abc.txt".to_owned(),
+            String::new(),
+        );
+        assert_eq!(err.to_string(), "oss error");
+    }
+
+    #[test]
+    fn test_predicates() {
+        let service = |status, code: &str| {
+            let xml = format!(
+                "<Error><Code>{}</Code><Message>m</Message><RequestId>1</RequestId></Error>",
+                code
+            );
+            OssError::from_status(status, &xml)
+        };
+
+        let err = service(StatusCode::NOT_FOUND, "NoSuchKey");
+        assert!(err.is_not_found());
+        assert!(!err.is_access_denied());
+        assert_eq!(err.status(), Some(StatusCode::NOT_FOUND));
+        assert!(service(StatusCode::NOT_FOUND, "NoSuchBucket").is_not_found());
+
+        let err = service(StatusCode::FORBIDDEN, "AccessDenied");
+        assert!(err.is_access_denied());
+        assert!(!err.is_signature_mismatch());
+        assert_eq!(err.status(), Some(StatusCode::FORBIDDEN));
+
+        let err = service(StatusCode::FORBIDDEN, "SignatureDoesNotMatch");
+        assert!(err.is_signature_mismatch());
+        assert!(!err.is_access_denied());
+        assert!(!err.is_not_found());
+
+        let err = OssError::from_status(StatusCode::NOT_FOUND, "");
+        assert!(err.is_not_found());
+        assert_eq!(err.status(), Some(StatusCode::NOT_FOUND));
+        assert!(OssError::from_status(StatusCode::FORBIDDEN, "").is_access_denied());
+
+        assert_eq!(
+            OssError::PreconditionFailed.status(),
+            Some(StatusCode::PRECONDITION_FAILED)
+        );
+        assert_eq!(OssError::NoFoundEtag.status(), None);
+        assert!(!OssError::NoFoundEtag.is_not_found());
+    }
+
+    #[test]
+    fn test_server_status() {
+        let err = OssError::from_status(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "<html><body>503 Service Temporarily Unavailable</body></html>",
+        );
+        assert!(matches!(
+            err,
+            OssError::ServerStatus(StatusCode::SERVICE_UNAVAILABLE)
+        ));
+
+        let err = OssError::from_status(StatusCode::NOT_FOUND, "");
+        assert!(matches!(err, OssError::ServerStatus(StatusCode::NOT_FOUND)));
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Error>
+  <Code>PreconditionFailed</Code>
+  <Message>At least one of the pre-conditions you specified did not hold.</Message>
+  <RequestId>5C3D9175B6FC201293AD****</RequestId>
+</Error>"#;
+        let err = OssError::from_status(StatusCode::PRECONDITION_FAILED, xml);
+        assert!(matches!(err, OssError::PreconditionFailed));
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_invalid_last_modified() {
+        use crate::ObjectInfo;
+
+        let err = ObjectInfo::parse_last_modified("2022-06-26 09:53").unwrap_err();
+        assert!(matches!(err, OssError::InvalidLastModified(s) if &*s == "2022-06-26 09:53"));
+    }
+
+    #[test]
+    fn test_range_not_satisfiable() {
+        use http::header::{HeaderMap, CONTENT_RANGE};
+
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_RANGE, "bytes */12345".parse().unwrap());
+        let err = OssError::from_range_not_satisfiable(StatusCode::RANGE_NOT_SATISFIABLE, &headers);
+        assert!(matches!(err, Some(OssError::RangeNotSatisfiable(12345))));
+
+        assert!(OssError::from_range_not_satisfiable(StatusCode::NOT_FOUND, &headers).is_none());
+        assert!(OssError::from_range_not_satisfiable(
+            StatusCode::RANGE_NOT_SATISFIABLE,
+            &HeaderMap::new()
+        )
+        .is_none());
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_is_dns_error() {
+        use super::is_dns_error;
+        use std::{fmt, io, net::ToSocketAddrs};
+
+        /// 模拟 reqwest，hyper 对底层错误的包装
+        #[derive(Debug)]
+        struct Wrapper(io::Error);
+        impl fmt::Display for Wrapper {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("error sending request")
+            }
+        }
+        impl std::error::Error for Wrapper {
+            fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+                Some(&self.0)
+            }
+        }
+
+        // getaddrinfo 返回的错误
+        let lookup = "oss-rs.invalid:80".to_socket_addrs().unwrap_err();
+        assert!(is_dns_error(&Wrapper(lookup)));
+
+        let refused = io::Error::from_raw_os_error(111);
+        assert!(!is_dns_error(&Wrapper(refused)));
+        assert!(!is_dns_error(&Wrapper(io::Error::other("tls handshake"))));
+        assert!(!is_dns_error(&Wrapper(io::ErrorKind::TimedOut.into())));
+        assert!(!is_dns_error(&"abc".parse::<u64>().unwrap_err()));
+    }
+
+    #[cfg(feature = "client")]
+    #[tokio::test]
+    async fn test_network_errors() {
+        use std::time::Duration;
+
+        let http = reqwest::Client::new();
+
+        // 端口未监听，连接会被拒绝
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+        let err: OssError = http
+            .get(format!("http://{addr}"))
+            .send()
+            .await
+            .unwrap_err()
+            .into();
+        assert!(matches!(err, OssError::Connect(_)));
+
+        // 接受连接但不响应
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let err: OssError = http
+            .get(format!("http://{addr}"))
+            .timeout(Duration::from_millis(50))
+            .send()
+            .await
+            .unwrap_err()
+            .into();
+        assert!(matches!(err, OssError::Timeout(_)));
+        drop(listener);
+
+        let err: OssError = http
+            .get("http://oss-rs.invalid")
+            .send()
+            .await
+            .unwrap_err()
+            .into();
+        assert!(matches!(err, OssError::Dns(_)));
+    }
 }
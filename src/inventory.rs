@@ -0,0 +1,435 @@
+//! bucket 的清单配置，定期导出 bucket 中文件的列表
+
+use std::str::FromStr;
+
+use crate::{
+    bucket::{xml_escape, xml_unescape, Bucket},
+    error::OssError,
+};
+
+/// 目标 bucket 在清单配置中的前缀
+const BUCKET_ARN_PREFIX: &str = "acs:oss:::";
+
+/// 清单配置，按设置的频率定期将 bucket 中文件的列表导出到目标 bucket
+/// ```
+/// # use aliyun_oss_client::inventory::*;
+/// let destination = InventoryDestination::new(
+///     "dest-bucket",
+///     "100000000000000",
+///     "acs:ram::100000000000000:role/AliyunOSSRole",
+/// )
+/// .prefix("inventory/");
+/// let config = InventoryConfiguration::new("report1", destination)
+///     .prefix("log/")
+///     .frequency(InventoryFrequency::Weekly)
+///     .field(InventoryField::Size)
+///     .field(InventoryField::ETag);
+/// assert_eq!(config.id(), "report1");
+/// assert_eq!(config.fields(), [InventoryField::Size, InventoryField::ETag]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InventoryConfiguration {
+    id: String,
+    enabled: bool,
+    prefix: Option<String>,
+    destination: InventoryDestination,
+    frequency: InventoryFrequency,
+    included_versions: IncludedObjectVersions,
+    fields: Vec<InventoryField>,
+}
+
+impl InventoryConfiguration {
+    /// 创建一个导出到 `destination` 的清单配置，默认启用，每天导出一次，只包含当前版本
+    pub fn new<I: Into<String>>(id: I, destination: InventoryDestination) -> Self {
+        InventoryConfiguration {
+            id: id.into(),
+            enabled: true,
+            prefix: None,
+            destination,
+            frequency: InventoryFrequency::default(),
+            included_versions: IncludedObjectVersions::default(),
+            fields: Vec::new(),
+        }
+    }
+
+    /// 清单是否启用，默认启用
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// 只导出 `prefix` 下的文件
+    pub fn prefix<P: Into<String>>(mut self, prefix: P) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// 导出的频率，默认每天
+    pub fn frequency(mut self, frequency: InventoryFrequency) -> Self {
+        self.frequency = frequency;
+        self
+    }
+
+    /// 是否导出文件的所有版本，默认只导出当前版本
+    pub fn included_versions(mut self, versions: IncludedObjectVersions) -> Self {
+        self.included_versions = versions;
+        self
+    }
+
+    /// 清单中额外包含的文件信息，可以设置多个
+    pub fn field(mut self, field: InventoryField) -> Self {
+        self.fields.push(field);
+        self
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn get_prefix(&self) -> Option<&str> {
+        self.prefix.as_deref()
+    }
+
+    pub fn destination(&self) -> &InventoryDestination {
+        &self.destination
+    }
+
+    pub fn get_frequency(&self) -> InventoryFrequency {
+        self.frequency
+    }
+
+    pub fn get_included_versions(&self) -> IncludedObjectVersions {
+        self.included_versions
+    }
+
+    pub fn fields(&self) -> &[InventoryField] {
+        &self.fields
+    }
+
+    pub(crate) fn to_xml(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>");
+        xml += &self.to_block();
+        xml
+    }
+
+    fn to_block(&self) -> String {
+        let mut xml = format!(
+            "<InventoryConfiguration><Id>{}</Id><IsEnabled>{}</IsEnabled>",
+            xml_escape(&self.id),
+            self.enabled
+        );
+        if let Some(prefix) = &self.prefix {
+            xml += &format!("<Filter><Prefix>{}</Prefix></Filter>", xml_escape(prefix));
+        }
+        xml += &self.destination.to_xml();
+        xml += &format!(
+            "<Schedule><Frequency>{}</Frequency></Schedule>\
+            <IncludedObjectVersions>{}</IncludedObjectVersions>",
+            self.frequency.as_ref(),
+            self.included_versions.as_ref()
+        );
+        if !self.fields.is_empty() {
+            xml += "<OptionalFields>";
+            for field in &self.fields {
+                xml += &format!("<Field>{}</Field>", field.as_ref());
+            }
+            xml += "</OptionalFields>";
+        }
+        xml += "</InventoryConfiguration>";
+        xml
+    }
+
+    pub(crate) fn from_xml(xml: &str) -> Result<Self, OssError> {
+        let destination = Bucket::parse_blocks(xml, "OSSBucketDestination");
+        let destination = destination.first().ok_or(OssError::InvalidInventory)?;
+        let prefix = Bucket::parse_blocks(xml, "Filter")
+            .first()
+            .and_then(|filter| Bucket::parse_item(filter, "Prefix"))
+            .map(xml_unescape);
+        let fields = Bucket::parse_blocks(xml, "OptionalFields")
+            .first()
+            .map(|block| {
+                Bucket::parse_blocks(block, "Field")
+                    .into_iter()
+                    .map(str::parse)
+                    .collect::<Result<_, _>>()
+            })
+            .transpose()?
+            .unwrap_or_default();
+
+        Ok(InventoryConfiguration {
+            id: xml_unescape(Bucket::parse_item(xml, "Id").ok_or(OssError::InvalidInventory)?),
+            enabled: Bucket::parse_item(xml, "IsEnabled") == Some("true"),
+            prefix,
+            destination: InventoryDestination::from_xml(destination)?,
+            frequency: Bucket::parse_item(xml, "Frequency")
+                .ok_or(OssError::InvalidInventory)?
+                .parse()?,
+            included_versions: Bucket::parse_item(xml, "IncludedObjectVersions")
+                .ok_or(OssError::InvalidInventory)?
+                .parse()?,
+            fields,
+        })
+    }
+
+    /// 解析列举清单配置的结果，同时返回用于翻页的 `NextContinuationToken`
+    pub(crate) fn parse_list_xml(xml: &str) -> Result<(Vec<Self>, Option<String>), OssError> {
+        let list = Bucket::parse_blocks(xml, "InventoryConfiguration")
+            .into_iter()
+            .map(Self::from_xml)
+            .collect::<Result<_, _>>()?;
+        let token = match Bucket::parse_item(xml, "IsTruncated") {
+            Some("true") => Bucket::parse_item(xml, "NextContinuationToken").map(|t| t.to_owned()),
+            _ => None,
+        };
+
+        Ok((list, token))
+    }
+}
+
+/// 清单文件的存放位置，目前只支持 CSV 格式
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InventoryDestination {
+    bucket: String,
+    account_id: String,
+    role_arn: String,
+    prefix: Option<String>,
+}
+
+impl InventoryDestination {
+    /// `bucket` 为存放清单文件的 bucket 名称，`account_id` 为 bucket 拥有者的账号 ID，
+    /// `role_arn` 为具有读取源 bucket 和写入目标 bucket 权限的角色
+    pub fn new<B, A, R>(bucket: B, account_id: A, role_arn: R) -> Self
+    where
+        B: Into<String>,
+        A: Into<String>,
+        R: Into<String>,
+    {
+        InventoryDestination {
+            bucket: bucket.into(),
+            account_id: account_id.into(),
+            role_arn: role_arn.into(),
+            prefix: None,
+        }
+    }
+
+    /// 清单文件在目标 bucket 中的前缀
+    pub fn prefix<P: Into<String>>(mut self, prefix: P) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    pub fn bucket(&self) -> &str {
+        &self.bucket
+    }
+
+    pub fn account_id(&self) -> &str {
+        &self.account_id
+    }
+
+    pub fn role_arn(&self) -> &str {
+        &self.role_arn
+    }
+
+    pub fn get_prefix(&self) -> Option<&str> {
+        self.prefix.as_deref()
+    }
+
+    fn to_xml(&self) -> String {
+        let mut xml = format!(
+            "<Destination><OSSBucketDestination><Format>CSV</Format>\
+            <AccountId>{}</AccountId><RoleArn>{}</RoleArn><Bucket>{}{}</Bucket>",
+            xml_escape(&self.account_id),
+            xml_escape(&self.role_arn),
+            BUCKET_ARN_PREFIX,
+            xml_escape(&self.bucket)
+        );
+        if let Some(prefix) = &self.prefix {
+            xml += &format!("<Prefix>{}</Prefix>", xml_escape(prefix));
+        }
+        xml += "</OSSBucketDestination></Destination>";
+        xml
+    }
+
+    fn from_xml(xml: &str) -> Result<Self, OssError> {
+        let bucket = Bucket::parse_item(xml, "Bucket").ok_or(OssError::InvalidInventory)?;
+        let item = |field| Bucket::parse_item(xml, field).map(xml_unescape);
+
+        Ok(InventoryDestination {
+            bucket: xml_unescape(bucket.strip_prefix(BUCKET_ARN_PREFIX).unwrap_or(bucket)),
+            account_id: item("AccountId").ok_or(OssError::InvalidInventory)?,
+            role_arn: item("RoleArn").ok_or(OssError::InvalidInventory)?,
+            prefix: item("Prefix"),
+        })
+    }
+}
+
+/// 清单导出的频率
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InventoryFrequency {
+    #[default]
+    Daily,
+    Weekly,
+}
+
+impl FromStr for InventoryFrequency {
+    type Err = OssError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Daily" => Ok(InventoryFrequency::Daily),
+            "Weekly" => Ok(InventoryFrequency::Weekly),
+            _ => Err(OssError::InvalidInventory),
+        }
+    }
+}
+
+impl AsRef<str> for InventoryFrequency {
+    fn as_ref(&self) -> &str {
+        match self {
+            InventoryFrequency::Daily => "Daily",
+            InventoryFrequency::Weekly => "Weekly",
+        }
+    }
+}
+
+/// 清单是否包含文件的所有版本
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IncludedObjectVersions {
+    All,
+    #[default]
+    Current,
+}
+
+impl FromStr for IncludedObjectVersions {
+    type Err = OssError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "All" => Ok(IncludedObjectVersions::All),
+            "Current" => Ok(IncludedObjectVersions::Current),
+            _ => Err(OssError::InvalidInventory),
+        }
+    }
+}
+
+impl AsRef<str> for IncludedObjectVersions {
+    fn as_ref(&self) -> &str {
+        match self {
+            IncludedObjectVersions::All => "All",
+            IncludedObjectVersions::Current => "Current",
+        }
+    }
+}
+
+/// 清单中可以额外包含的文件信息
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InventoryField {
+    Size,
+    LastModifiedDate,
+    ETag,
+    StorageClass,
+    IsMultipartUploaded,
+    EncryptionStatus,
+}
+
+impl FromStr for InventoryField {
+    type Err = OssError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Size" => Ok(InventoryField::Size),
+            "LastModifiedDate" => Ok(InventoryField::LastModifiedDate),
+            "ETag" => Ok(InventoryField::ETag),
+            "StorageClass" => Ok(InventoryField::StorageClass),
+            "IsMultipartUploaded" => Ok(InventoryField::IsMultipartUploaded),
+            "EncryptionStatus" => Ok(InventoryField::EncryptionStatus),
+            _ => Err(OssError::InvalidInventory),
+        }
+    }
+}
+
+impl AsRef<str> for InventoryField {
+    fn as_ref(&self) -> &str {
+        match self {
+            InventoryField::Size => "Size",
+            InventoryField::LastModifiedDate => "LastModifiedDate",
+            InventoryField::ETag => "ETag",
+            InventoryField::StorageClass => "StorageClass",
+            InventoryField::IsMultipartUploaded => "IsMultipartUploaded",
+            InventoryField::EncryptionStatus => "EncryptionStatus",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> InventoryConfiguration {
+        let destination = InventoryDestination::new(
+            "dest-bucket",
+            "100000000000000",
+            "acs:ram::100000000000000:role/AliyunOSSRole",
+        )
+        .prefix("inventory/");
+        InventoryConfiguration::new("report1", destination)
+            .prefix("log/")
+            .frequency(InventoryFrequency::Weekly)
+            .included_versions(IncludedObjectVersions::All)
+            .field(InventoryField::Size)
+            .field(InventoryField::LastModifiedDate)
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let config = config();
+        let xml = config.to_xml();
+        assert!(xml.contains(
+            "<Destination><OSSBucketDestination><Format>CSV</Format>\
+            <AccountId>100000000000000</AccountId>\
+            <RoleArn>acs:ram::100000000000000:role/AliyunOSSRole</RoleArn>\
+            <Bucket>acs:oss:::dest-bucket</Bucket><Prefix>inventory/</Prefix>\
+            </OSSBucketDestination></Destination>"
+        ));
+        assert_eq!(InventoryConfiguration::from_xml(&xml).unwrap(), config);
+
+        // 含有 XML 特殊字符的 id 和前缀
+        let special =
+            InventoryConfiguration::new("a&b<c>", config.destination().clone()).prefix("log/&<'\"");
+        assert_eq!(
+            InventoryConfiguration::from_xml(&special.to_xml()).unwrap(),
+            special
+        );
+
+        let minimal =
+            InventoryConfiguration::new("report2", config.destination().clone()).enabled(false);
+        let parsed = InventoryConfiguration::from_xml(&minimal.to_xml()).unwrap();
+        assert_eq!(parsed, minimal);
+        assert_eq!(parsed.get_prefix(), None);
+        assert!(parsed.fields().is_empty());
+    }
+
+    #[test]
+    fn test_parse_list_xml() {
+        let xml = format!(
+            "<ListInventoryConfigurationsResult>{}<IsTruncated>true</IsTruncated>\
+            <NextContinuationToken>report1</NextContinuationToken>\
+            </ListInventoryConfigurationsResult>",
+            config().to_block()
+        );
+        let (list, token) = InventoryConfiguration::parse_list_xml(&xml).unwrap();
+        assert_eq!(list, vec![config()]);
+        assert_eq!(token.as_deref(), Some("report1"));
+
+        let xml = "<ListInventoryConfigurationsResult><IsTruncated>false</IsTruncated>\
+            </ListInventoryConfigurationsResult>";
+        let (list, token) = InventoryConfiguration::parse_list_xml(xml).unwrap();
+        assert!(list.is_empty());
+        assert_eq!(token, None);
+    }
+}
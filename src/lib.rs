@@ -1,14 +1,42 @@
+// 只使用签名工具时，部分供 Client 使用的内部函数不会被调用
+#![cfg_attr(not(feature = "client"), allow(dead_code))]
+
+pub mod auth;
+#[cfg(feature = "client")]
 pub mod bucket;
+#[cfg(feature = "client")]
+pub mod cache;
+#[cfg(feature = "client")]
 pub mod client;
+#[cfg(feature = "client")]
+pub mod cname;
 pub mod error;
+#[cfg(feature = "client")]
+pub mod inventory;
+#[cfg(feature = "client")]
+pub mod lifecycle;
+#[cfg(feature = "client")]
+pub mod multipart;
+#[cfg(feature = "client")]
 pub mod object;
+#[cfg(feature = "client")]
+pub mod style;
 pub mod types;
+#[cfg(feature = "client")]
+pub mod worm;
 
-pub use bucket::Bucket;
-pub use bucket::BucketInfo;
-pub use client::Client;
+pub use auth::Auth;
 pub use error::OssError as Error;
-pub use object::Object;
-pub use object::ObjectInfo;
-pub use object::Objects;
-pub use types::{EndPoint, Key, Secret};
+pub use types::{ContentMd5, EndPoint, Key, Secret};
+
+#[cfg(feature = "client")]
+pub use bucket::{Bucket, BucketInfo};
+#[cfg(feature = "client")]
+pub use cache::DownloadCache;
+#[cfg(feature = "client")]
+pub use client::{Client, ClientBuilder};
+#[cfg(feature = "client")]
+pub use object::{
+    Appender, DeleteOutcome, ImageInfo, Object, ObjectInfo, ObjectInfos, ObjectReader, Objects,
+    SyncPlan, UploadResult, UploadStats,
+};
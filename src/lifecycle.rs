@@ -0,0 +1,425 @@
+//! bucket 的生命周期规则
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{
+    bucket::{xml_escape, xml_unescape, Bucket},
+    error::OssError,
+    types::StorageClass,
+};
+
+/// 生命周期规则，按前缀，标签和文件大小筛选文件，到期后删除文件或转换存储类型
+/// ```
+/// # use aliyun_oss_client::{lifecycle::LifecycleRule, types::StorageClass};
+/// let rule = LifecycleRule::new("log/")
+///     .id("expire-logs")
+///     .tag("type", "log")
+///     .object_size_greater_than(1024)
+///     .transition(30, StorageClass::IA)
+///     .expiration_days(180);
+/// assert_eq!(rule.prefix(), "log/");
+/// assert_eq!(rule.tags(), [("type".to_owned(), "log".to_owned())]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LifecycleRule {
+    id: Option<String>,
+    prefix: String,
+    enabled: bool,
+    tags: Vec<(String, String)>,
+    object_size_greater_than: Option<u64>,
+    object_size_less_than: Option<u64>,
+    expiration_days: Option<u32>,
+    transition: Option<(u32, StorageClass)>,
+}
+
+impl LifecycleRule {
+    /// 创建一个对 `prefix` 下的文件生效的规则，`prefix` 为空时对整个 bucket 生效
+    pub fn new<P: Into<String>>(prefix: P) -> LifecycleRule {
+        LifecycleRule {
+            id: None,
+            prefix: prefix.into(),
+            enabled: true,
+            tags: Vec::new(),
+            object_size_greater_than: None,
+            object_size_less_than: None,
+            expiration_days: None,
+            transition: None,
+        }
+    }
+
+    /// 规则的 ID，不设置时由 OSS 生成
+    pub fn id<I: Into<String>>(mut self, id: I) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// 规则是否启用，默认启用
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// 只对带有该标签的文件生效，可以设置多个，需要同时满足
+    pub fn tag<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.tags.push((key.into(), value.into()));
+        self
+    }
+
+    /// 只对大于 `size` 字节的文件生效
+    pub fn object_size_greater_than(mut self, size: u64) -> Self {
+        self.object_size_greater_than = Some(size);
+        self
+    }
+
+    /// 只对小于 `size` 字节的文件生效
+    pub fn object_size_less_than(mut self, size: u64) -> Self {
+        self.object_size_less_than = Some(size);
+        self
+    }
+
+    /// 文件最后修改 `days` 天后删除
+    pub fn expiration_days(mut self, days: u32) -> Self {
+        self.expiration_days = Some(days);
+        self
+    }
+
+    /// 文件最后修改 `days` 天后转换为 `storage_class` 存储类型
+    pub fn transition(mut self, days: u32, storage_class: StorageClass) -> Self {
+        self.transition = Some((days, storage_class));
+        self
+    }
+
+    pub fn get_id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn tags(&self) -> &[(String, String)] {
+        &self.tags
+    }
+
+    pub fn get_object_size_greater_than(&self) -> Option<u64> {
+        self.object_size_greater_than
+    }
+
+    pub fn get_object_size_less_than(&self) -> Option<u64> {
+        self.object_size_less_than
+    }
+
+    pub fn get_expiration_days(&self) -> Option<u32> {
+        self.expiration_days
+    }
+
+    pub fn get_transition(&self) -> Option<(u32, StorageClass)> {
+        self.transition
+    }
+
+    /// 规则是否启用，且对路径为 `key`，大小为 `size` 的文件生效，标签条件无法在本地判断，会被忽略
+    pub fn matches(&self, key: &str, size: u64) -> bool {
+        self.enabled
+            && key.starts_with(&self.prefix)
+            && self.object_size_greater_than.is_none_or(|min| size > min)
+            && self.object_size_less_than.is_none_or(|max| size < max)
+    }
+
+    /// 最后修改时间为 `last_modified` 的文件被删除的时间，规则没有设置过期删除时为 `None`
+    pub fn expiration_date(&self, last_modified: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.expiration_days
+            .map(|days| last_modified + Duration::days(days.into()))
+    }
+
+    /// 最后修改时间为 `last_modified` 的文件转换存储类型的时间，以及转换后的存储类型
+    pub fn transition_date(
+        &self,
+        last_modified: DateTime<Utc>,
+    ) -> Option<(DateTime<Utc>, StorageClass)> {
+        self.transition.map(|(days, storage_class)| {
+            (last_modified + Duration::days(days.into()), storage_class)
+        })
+    }
+
+    fn to_xml(&self) -> String {
+        let mut xml = String::from("<Rule>");
+        if let Some(id) = &self.id {
+            xml += &format!("<ID>{}</ID>", xml_escape(id));
+        }
+        xml += &format!("<Prefix>{}</Prefix>", xml_escape(&self.prefix));
+        xml += if self.enabled {
+            "<Status>Enabled</Status>"
+        } else {
+            "<Status>Disabled</Status>"
+        };
+        if self.object_size_greater_than.is_some() || self.object_size_less_than.is_some() {
+            xml += "<Filter>";
+            if let Some(size) = self.object_size_greater_than {
+                xml += &format!("<ObjectSizeGreaterThan>{}</ObjectSizeGreaterThan>", size);
+            }
+            if let Some(size) = self.object_size_less_than {
+                xml += &format!("<ObjectSizeLessThan>{}</ObjectSizeLessThan>", size);
+            }
+            xml += "</Filter>";
+        }
+        for (key, value) in &self.tags {
+            xml += &format!(
+                "<Tag><Key>{}</Key><Value>{}</Value></Tag>",
+                xml_escape(key),
+                xml_escape(value)
+            );
+        }
+        if let Some(days) = self.expiration_days {
+            xml += &format!("<Expiration><Days>{}</Days></Expiration>", days);
+        }
+        if let Some((days, storage_class)) = self.transition {
+            xml += &format!(
+                "<Transition><Days>{}</Days><StorageClass>{}</StorageClass></Transition>",
+                days,
+                storage_class.as_ref()
+            );
+        }
+        xml += "</Rule>";
+        xml
+    }
+
+    fn from_xml(xml: &str) -> Result<LifecycleRule, OssError> {
+        let filter = Bucket::parse_blocks(xml, "Filter");
+        let filter = filter.first().copied().unwrap_or_default();
+        // `<Filter>` 中的 `<Not>` 也可能包含 `<Prefix>` 和 `<Tag>`，先去掉 `<Filter>` 再解析
+        const FILTER_END: &str = "</Filter>";
+        let rule = match (xml.find("<Filter>"), xml.find(FILTER_END)) {
+            (Some(start), Some(end)) => {
+                format!("{}{}", &xml[..start], &xml[end + FILTER_END.len()..])
+            }
+            _ => xml.to_owned(),
+        };
+
+        let tags = Bucket::parse_blocks(&rule, "Tag")
+            .into_iter()
+            .filter_map(|tag| {
+                Some((
+                    xml_unescape(Bucket::parse_item(tag, "Key")?),
+                    xml_unescape(Bucket::parse_item(tag, "Value")?),
+                ))
+            })
+            .collect();
+        let expiration_days = match Bucket::parse_blocks(&rule, "Expiration").first() {
+            Some(block) => Bucket::parse_item(block, "Days")
+                .map(str::parse)
+                .transpose()?,
+            None => None,
+        };
+        let transition = match Bucket::parse_blocks(&rule, "Transition").first() {
+            Some(block) => {
+                let days = Bucket::parse_item(block, "Days")
+                    .map(str::parse)
+                    .transpose()?;
+                let storage_class =
+                    Bucket::parse_item(block, "StorageClass").and_then(StorageClass::new);
+                days.zip(storage_class)
+            }
+            None => None,
+        };
+
+        Ok(LifecycleRule {
+            id: Bucket::parse_item(&rule, "ID").map(xml_unescape),
+            prefix: xml_unescape(Bucket::parse_item(&rule, "Prefix").unwrap_or_default()),
+            enabled: Bucket::parse_item(&rule, "Status") == Some("Enabled"),
+            tags,
+            object_size_greater_than: Bucket::parse_item(filter, "ObjectSizeGreaterThan")
+                .map(str::parse)
+                .transpose()?,
+            object_size_less_than: Bucket::parse_item(filter, "ObjectSizeLessThan")
+                .map(str::parse)
+                .transpose()?,
+            expiration_days,
+            transition,
+        })
+    }
+}
+
+/// bucket 的生命周期配置，包含多条规则
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LifecycleConfiguration {
+    rules: Vec<LifecycleRule>,
+}
+
+impl LifecycleConfiguration {
+    pub fn new(rules: Vec<LifecycleRule>) -> LifecycleConfiguration {
+        LifecycleConfiguration { rules }
+    }
+
+    pub fn rules(&self) -> &[LifecycleRule] {
+        &self.rules
+    }
+
+    /// 根据对文件生效的规则，计算文件最早被删除的时间，参见 [`LifecycleRule::matches`]
+    ///
+    /// OSS 执行规则可能有延迟，实际删除时间会晚于该时间
+    /// ```
+    /// # use aliyun_oss_client::lifecycle::{LifecycleConfiguration, LifecycleRule};
+    /// use chrono::{TimeZone, Utc};
+    /// let config = LifecycleConfiguration::new(vec![LifecycleRule::new("log/").expiration_days(30)]);
+    /// let last_modified = Utc.with_ymd_and_hms(2022, 6, 26, 9, 53, 21).unwrap();
+    /// assert_eq!(
+    ///     config.expiration_for("log/a.log", 10, last_modified),
+    ///     Some(Utc.with_ymd_and_hms(2022, 7, 26, 9, 53, 21).unwrap())
+    /// );
+    /// assert_eq!(config.expiration_for("img/a.jpg", 10, last_modified), None);
+    /// ```
+    pub fn expiration_for(
+        &self,
+        key: &str,
+        size: u64,
+        last_modified: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        self.rules
+            .iter()
+            .filter(|rule| rule.matches(key, size))
+            .filter_map(|rule| rule.expiration_date(last_modified))
+            .min()
+    }
+
+    /// 根据对文件生效的规则，计算文件最早转换存储类型的时间，以及转换后的存储类型
+    pub fn transition_for(
+        &self,
+        key: &str,
+        size: u64,
+        last_modified: DateTime<Utc>,
+    ) -> Option<(DateTime<Utc>, StorageClass)> {
+        self.rules
+            .iter()
+            .filter(|rule| rule.matches(key, size))
+            .filter_map(|rule| rule.transition_date(last_modified))
+            .min_by_key(|(date, _)| *date)
+    }
+
+    pub(crate) fn to_xml(&self) -> String {
+        let mut xml =
+            String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?><LifecycleConfiguration>");
+        for rule in &self.rules {
+            xml += &rule.to_xml();
+        }
+        xml += "</LifecycleConfiguration>";
+        xml
+    }
+
+    pub(crate) fn from_xml(xml: &str) -> Result<LifecycleConfiguration, OssError> {
+        let rules = Bucket::parse_blocks(xml, "Rule")
+            .into_iter()
+            .map(LifecycleRule::from_xml)
+            .collect::<Result<_, _>>()?;
+
+        Ok(LifecycleConfiguration { rules })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LifecycleConfiguration, LifecycleRule};
+    use crate::types::StorageClass;
+
+    #[test]
+    fn test_round_trip() {
+        let config = LifecycleConfiguration::new(vec![
+            LifecycleRule::new("log/")
+                .id("rule1")
+                .tag("type", "log")
+                .object_size_greater_than(1024)
+                .expiration_days(30),
+            LifecycleRule::new("")
+                .enabled(false)
+                .object_size_less_than(64)
+                .transition(60, StorageClass::ARCHIVE),
+        ]);
+
+        let xml = config.to_xml();
+        assert!(xml.contains(
+            "<Rule><ID>rule1</ID><Prefix>log/</Prefix><Status>Enabled</Status>\
+            <Filter><ObjectSizeGreaterThan>1024</ObjectSizeGreaterThan></Filter>\
+            <Tag><Key>type</Key><Value>log</Value></Tag>\
+            <Expiration><Days>30</Days></Expiration></Rule>"
+        ));
+        assert_eq!(LifecycleConfiguration::from_xml(&xml).unwrap(), config);
+
+        // 含有 XML 特殊字符的 id，前缀和标签
+        let config = LifecycleConfiguration::new(vec![LifecycleRule::new("a&b<c>/")
+            .id("rule&1")
+            .tag("k&<", "v>'\"")
+            .expiration_days(1)]);
+        assert_eq!(
+            LifecycleConfiguration::from_xml(&config.to_xml()).unwrap(),
+            config
+        );
+    }
+
+    #[test]
+    fn test_expiration_for() {
+        use chrono::{TimeZone, Utc};
+
+        let config = LifecycleConfiguration::new(vec![
+            LifecycleRule::new("log/").expiration_days(30),
+            LifecycleRule::new("log/debug/").expiration_days(7),
+            LifecycleRule::new("log/")
+                .object_size_greater_than(1024)
+                .transition(10, StorageClass::IA),
+            LifecycleRule::new("").enabled(false).expiration_days(1),
+        ]);
+        let last_modified = Utc.with_ymd_and_hms(2022, 6, 26, 9, 53, 21).unwrap();
+
+        assert_eq!(
+            config.expiration_for("log/a.log", 10, last_modified),
+            Some(Utc.with_ymd_and_hms(2022, 7, 26, 9, 53, 21).unwrap())
+        );
+        assert_eq!(
+            config.expiration_for("log/debug/a.log", 10, last_modified),
+            Some(Utc.with_ymd_and_hms(2022, 7, 3, 9, 53, 21).unwrap())
+        );
+        assert_eq!(config.expiration_for("img/a.jpg", 10, last_modified), None);
+
+        assert_eq!(config.transition_for("log/a.log", 10, last_modified), None);
+        assert_eq!(
+            config.transition_for("log/a.log", 2048, last_modified),
+            Some((
+                Utc.with_ymd_and_hms(2022, 7, 6, 9, 53, 21).unwrap(),
+                StorageClass::IA
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_with_not_filter() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <LifecycleConfiguration>
+          <Rule>
+            <ID>rule</ID>
+            <Prefix>doc/</Prefix>
+            <Status>Enabled</Status>
+            <Filter>
+              <ObjectSizeGreaterThan>500</ObjectSizeGreaterThan>
+              <Not>
+                <Prefix>doc/keep/</Prefix>
+                <Tag><Key>keep</Key><Value>true</Value></Tag>
+              </Not>
+            </Filter>
+            <Tag><Key>env</Key><Value>dev</Value></Tag>
+            <Transition><Days>30</Days><StorageClass>IA</StorageClass></Transition>
+          </Rule>
+        </LifecycleConfiguration>"#;
+
+        let config = LifecycleConfiguration::from_xml(xml).unwrap();
+        let rule = &config.rules()[0];
+        assert_eq!(rule.get_id(), Some("rule"));
+        assert_eq!(rule.prefix(), "doc/");
+        assert_eq!(rule.tags(), [("env".to_owned(), "dev".to_owned())]);
+        assert_eq!(rule.get_object_size_greater_than(), Some(500));
+        assert_eq!(rule.get_object_size_less_than(), None);
+        assert_eq!(rule.get_expiration_days(), None);
+        assert_eq!(rule.get_transition(), Some((30, StorageClass::IA)));
+    }
+}
@@ -0,0 +1,752 @@
+//! 分片上传
+
+use reqwest::{header::HeaderMap, Method, Response};
+use url::Url;
+
+use crate::{
+    auth::CONTENT_MD5,
+    bucket::Bucket,
+    client::Client,
+    error::OssError,
+    object::{Object, UploadResult},
+    types::{CanonicalizedResource, ContentMd5, StorageClass},
+};
+
+/// 分片上传的最大分片数量
+pub const MAX_PARTS: u32 = 10000;
+
+/// 一页分片上传任务，以及下一页的 `key-marker` 和 `upload-id-marker`
+type UploadsPage = (Vec<MultipartUpload>, Option<(String, String)>);
+
+/// 一次分片上传任务，通过 [`MultipartUpload::init`] 创建，依次上传分片后调用
+/// [`complete`](Self::complete) 合并，或者调用 [`abort`](Self::abort) 取消
+#[derive(Debug, Clone)]
+pub struct MultipartUpload {
+    object: Object,
+    upload_id: String,
+    parts: Vec<(u32, String)>,
+}
+
+impl MultipartUpload {
+    /// 初始化分片上传任务，`headers` 中可以携带 `Content-Type`，`x-oss-` 开头的 header 等
+    /// 目标文件的元信息
+    pub async fn init(
+        object: &Object,
+        headers: HeaderMap,
+        client: &Client,
+    ) -> Result<MultipartUpload, OssError> {
+        let content = send(object, Method::POST, "uploads", headers, None, client)
+            .await?
+            .text()
+            .await?;
+
+        let upload_id =
+            Bucket::parse_item(&content, "UploadId").ok_or(OssError::NoFoundUploadId)?;
+
+        Ok(MultipartUpload {
+            object: object.clone(),
+            upload_id: upload_id.to_owned(),
+            parts: Vec::new(),
+        })
+    }
+
+    /// 初始化分片上传任务，并通过 `x-oss-storage-class` 指定目标文件的存储类型
+    pub async fn init_with_storage_class(
+        object: &Object,
+        storage_class: StorageClass,
+        headers: HeaderMap,
+        client: &Client,
+    ) -> Result<MultipartUpload, OssError> {
+        let headers = with_storage_class(headers, storage_class)?;
+
+        Self::init(object, headers, client).await
+    }
+
+    pub fn object(&self) -> &Object {
+        &self.object
+    }
+
+    pub fn upload_id(&self) -> &str {
+        &self.upload_id
+    }
+
+    /// 已上传的分片编号及对应的 etag
+    pub fn parts(&self) -> &[(u32, String)] {
+        &self.parts
+    }
+
+    /// 上传下一个分片，分片编号从 1 开始自动递增，除最后一个分片外，每个分片不能小于 100KB
+    ///
+    /// 每个分片都会携带 `Content-MD5`，内容在传输中损坏时 OSS 会直接拒绝该分片
+    pub async fn upload_part(&mut self, content: Vec<u8>, client: &Client) -> Result<(), OssError> {
+        let part_number = self.parts.len() as u32 + 1;
+        if part_number > MAX_PARTS {
+            return Err(OssError::TooManyParts);
+        }
+
+        let query = format!("partNumber={}&uploadId={}", part_number, self.upload_id);
+        let headers = part_headers(&content)?;
+        let response = send(
+            &self.object,
+            Method::PUT,
+            &query,
+            headers,
+            Some(content),
+            client,
+        )
+        .await?;
+
+        let etag = response
+            .headers()
+            .get("etag")
+            .ok_or(OssError::NoFoundEtag)?
+            .to_str()?
+            .to_owned();
+        self.parts.push((part_number, etag));
+
+        Ok(())
+    }
+
+    /// 从 `source` 复制 `range`（闭区间）范围的内容作为下一个分片，`source` 的格式与
+    /// [`Object::copy_from`] 相同
+    pub async fn upload_part_copy(
+        &mut self,
+        source: &Object,
+        range: (u64, u64),
+        client: &Client,
+    ) -> Result<(), OssError> {
+        let part_number = self.parts.len() as u32 + 1;
+        if part_number > MAX_PARTS {
+            return Err(OssError::TooManyParts);
+        }
+
+        let query = format!("partNumber={}&uploadId={}", part_number, self.upload_id);
+        let content = send(
+            &self.object,
+            Method::PUT,
+            &query,
+            copy_part_headers(source, range)?,
+            None,
+            client,
+        )
+        .await?
+        .text()
+        .await?;
+
+        let etag = Bucket::parse_item(&content, "ETag").ok_or(OssError::NoFoundEtag)?;
+        self.parts.push((part_number, etag.to_owned()));
+
+        Ok(())
+    }
+
+    /// 合并已上传的分片，完成上传
+    pub async fn complete(self, client: &Client) -> Result<UploadResult, OssError> {
+        let query = format!("uploadId={}", self.upload_id);
+        let body = complete_body(&self.parts);
+
+        let response = send(
+            &self.object,
+            Method::POST,
+            &query,
+            HeaderMap::new(),
+            Some(body.into_bytes()),
+            client,
+        )
+        .await?;
+
+        let mut headers = response.headers().clone();
+        let content = response.text().await?;
+        if !headers.contains_key("etag") {
+            let etag = Bucket::parse_item(&content, "ETag").ok_or(OssError::NoFoundEtag)?;
+            headers.insert("etag", etag.try_into()?);
+        }
+
+        UploadResult::from_headers(&headers)
+    }
+
+    /// 解析列举分片上传任务的结果，返回任务列表，以及还有下一页时的 `key-marker` 和
+    /// `upload-id-marker`，列举出的任务中不包含已上传的分片
+    pub(crate) fn parse_list_xml(xml: &str) -> Result<UploadsPage, OssError> {
+        let list = Bucket::parse_blocks(xml, "Upload")
+            .into_iter()
+            .map(|block| {
+                Ok(MultipartUpload {
+                    object: Object::new(
+                        Bucket::parse_item(block, "Key").ok_or(OssError::NoFoundKey)?,
+                    ),
+                    upload_id: Bucket::parse_item(block, "UploadId")
+                        .ok_or(OssError::NoFoundUploadId)?
+                        .to_owned(),
+                    parts: Vec::new(),
+                })
+            })
+            .collect::<Result<_, OssError>>()?;
+
+        let next = match Bucket::parse_item(xml, "IsTruncated") {
+            Some("true") => Some((
+                Bucket::parse_item(xml, "NextKeyMarker")
+                    .ok_or(OssError::NoFoundKey)?
+                    .to_owned(),
+                Bucket::parse_item(xml, "NextUploadIdMarker")
+                    .ok_or(OssError::NoFoundUploadId)?
+                    .to_owned(),
+            )),
+            _ => None,
+        };
+
+        Ok((list, next))
+    }
+
+    /// 取消分片上传任务，已上传的分片会被删除
+    pub async fn abort(self, client: &Client) -> Result<(), OssError> {
+        let query = format!("uploadId={}", self.upload_id);
+        send(
+            &self.object,
+            Method::DELETE,
+            &query,
+            HeaderMap::new(),
+            None,
+            client,
+        )
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// 分片写入文件，调用方可以分多次写入内容，适用于内容是逐步生成的场景
+///
+/// 默认写入的内容都缓存在内存中，直到调用 [`flush`](Self::flush) 时才上传已满的分片；
+/// 开启 [`auto_flush`](Self::auto_flush) 后，每当缓存的内容达到 [`Client::part_size`]
+/// 时立即上传，内存中只保留当前不足一个分片的内容
+///
+/// 最后调用 [`finish`](Self::finish) 上传剩余内容并合并分片，内容不足一个分片且没有上传过
+/// 分片时使用普通上传
+///
+/// 有分片上传失败后，之后的写入，上传和合并都会返回 [`OssError::WriterFailed`]
+///
+/// 完成上传后可以通过 [`reset_for`](Self::reset_for) 切换到下一个文件继续使用
+#[derive(Debug)]
+pub struct MultipartWriter {
+    object: Object,
+    upload: Option<MultipartUpload>,
+    buffer: Vec<u8>,
+    part_size: usize,
+    auto_flush: bool,
+    failed: bool,
+}
+
+impl MultipartWriter {
+    /// 创建写入 `object` 的 writer，分片大小取自 [`Client::part_size`]
+    pub fn new(object: Object, client: &Client) -> MultipartWriter {
+        MultipartWriter {
+            object,
+            upload: None,
+            buffer: Vec::new(),
+            part_size: client.part_size() as usize,
+            auto_flush: false,
+            failed: false,
+        }
+    }
+
+    /// 是否在缓存的内容达到一个分片时立即上传，默认为 false
+    pub fn auto_flush(mut self, auto_flush: bool) -> Self {
+        self.auto_flush = auto_flush;
+        self
+    }
+
+    /// 已上传的分片数量
+    pub fn uploaded_parts(&self) -> usize {
+        self.upload
+            .as_ref()
+            .map_or(0, |upload| upload.parts().len())
+    }
+
+    /// 缓存在内存中，还没有上传的字节数
+    pub fn buffered_len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// 写入内容，开启 `auto_flush` 时会上传已满的分片
+    pub async fn write(&mut self, content: &[u8], client: &Client) -> Result<(), OssError> {
+        self.check_failed()?;
+        self.buffer.extend_from_slice(content);
+        if self.auto_flush {
+            self.flush(client).await?;
+        }
+
+        Ok(())
+    }
+
+    /// 上传缓存中所有已满的分片，不足一个分片的内容继续保留在缓存中
+    ///
+    /// 上传失败时会取消分片上传任务，之后不能再继续写入
+    pub async fn flush(&mut self, client: &Client) -> Result<(), OssError> {
+        self.check_failed()?;
+        for part in take_full_parts(&mut self.buffer, self.part_size) {
+            self.upload_part(part, client).await?;
+        }
+
+        Ok(())
+    }
+
+    /// 上传剩余的内容，完成上传，完成后缓存为空
+    pub async fn finish(&mut self, client: &Client) -> Result<UploadResult, OssError> {
+        self.check_failed()?;
+        if self.upload.is_none() && self.buffer.len() < self.part_size {
+            let content = std::mem::take(&mut self.buffer);
+            return self.object.upload_full(content, client).await;
+        }
+
+        self.flush(client).await?;
+        if !self.buffer.is_empty() {
+            let last = std::mem::take(&mut self.buffer);
+            self.upload_part(last, client).await?;
+        }
+
+        self.upload
+            .take()
+            .ok_or(OssError::NoFoundUploadId)?
+            .complete(client)
+            .await
+    }
+
+    /// 切换到写入 `object`，清空缓存中的内容和失败状态，适用于在循环中依次写入多个文件
+    ///
+    /// 还有未完成的分片上传任务时会先取消该任务，取消失败时仍然会切换到新的文件，并返回该错误
+    pub async fn reset_for(&mut self, object: Object, client: &Client) -> Result<(), OssError> {
+        self.object = object;
+        self.buffer.clear();
+        self.failed = false;
+        match self.upload.take() {
+            Some(upload) => upload.abort(client).await,
+            None => Ok(()),
+        }
+    }
+
+    fn check_failed(&self) -> Result<(), OssError> {
+        if self.failed {
+            Err(OssError::WriterFailed)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// 上传一个分片，失败时取消分片上传任务，取出的分片内容已经不在缓存中，
+    /// 因此之后不能再继续写入，否则会得到缺少内容的文件
+    async fn upload_part(&mut self, part: Vec<u8>, client: &Client) -> Result<(), OssError> {
+        let mut upload = match self.upload.take() {
+            Some(upload) => upload,
+            None => match MultipartUpload::init(&self.object, HeaderMap::new(), client).await {
+                Ok(upload) => upload,
+                Err(e) => {
+                    self.failed = true;
+                    return Err(e);
+                }
+            },
+        };
+        match upload.upload_part(part, client).await {
+            Ok(()) => {
+                self.upload = Some(upload);
+                Ok(())
+            }
+            Err(e) => {
+                self.failed = true;
+                // 以上传过程中的错误为准，取消任务失败时，未完成的分片会由生命周期规则清理
+                let _ = upload.abort(client).await;
+                Err(e)
+            }
+        }
+    }
+}
+
+/// 从缓存的开头取出所有已满的分片
+fn take_full_parts(buffer: &mut Vec<u8>, part_size: usize) -> Vec<Vec<u8>> {
+    let full = buffer.len() / part_size * part_size;
+    let rest = buffer.split_off(full);
+    let full = std::mem::replace(buffer, rest);
+
+    full.chunks(part_size).map(|part| part.to_vec()).collect()
+}
+
+async fn send(
+    object: &Object,
+    method: Method,
+    query: &str,
+    headers: HeaderMap,
+    body: Option<Vec<u8>>,
+    client: &Client,
+) -> Result<Response, OssError> {
+    let bucket = client.bucket().ok_or(OssError::NoFoundBucket)?;
+    let url = to_url(object, bucket, query);
+    let resource = CanonicalizedResource::new(format!(
+        "/{}/{}?{}",
+        bucket.as_str(),
+        object.get_path(),
+        query
+    ));
+
+    client.send(method, url, resource, headers, body).await
+}
+
+fn to_url(object: &Object, bucket: &Bucket, query: &str) -> Url {
+    let mut url = object.to_url(bucket);
+    url.set_query(Some(query));
+    url
+}
+
+fn with_storage_class(
+    mut headers: HeaderMap,
+    storage_class: StorageClass,
+) -> Result<HeaderMap, OssError> {
+    headers.insert("x-oss-storage-class", storage_class.as_ref().try_into()?);
+    Ok(headers)
+}
+
+fn part_headers(content: &[u8]) -> Result<HeaderMap, OssError> {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        CONTENT_MD5,
+        ContentMd5::from_bytes(content).as_str().try_into()?,
+    );
+    Ok(headers)
+}
+
+fn copy_part_headers(source: &Object, (start, end): (u64, u64)) -> Result<HeaderMap, OssError> {
+    let mut headers = HeaderMap::new();
+    headers.insert("x-oss-copy-source", source.get_path().try_into()?);
+    headers.insert(
+        "x-oss-copy-source-range",
+        format!("bytes={}-{}", start, end).try_into()?,
+    );
+    Ok(headers)
+}
+
+fn complete_body(parts: &[(u32, String)]) -> String {
+    let mut body = String::from("<CompleteMultipartUpload>");
+    for (number, etag) in parts {
+        body += &format!(
+            "<Part><PartNumber>{}</PartNumber><ETag>{}</ETag></Part>",
+            number, etag
+        );
+    }
+    body += "</CompleteMultipartUpload>";
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        complete_body, copy_part_headers, part_headers, take_full_parts, to_url,
+        with_storage_class, MultipartUpload, MultipartWriter,
+    };
+    use crate::{
+        bucket::Bucket,
+        client::{Client, MIN_PART_SIZE},
+        error::OssError,
+        object::Object,
+        types::{EndPoint, Key, Secret, StorageClass},
+    };
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_to_url() {
+        let bucket = Bucket::new("foo", EndPoint::CN_QINGDAO);
+        let url = to_url(
+            &Object::new("abc.txt"),
+            &bucket,
+            "partNumber=1&uploadId=0004B9",
+        );
+        assert_eq!(
+            url.as_str(),
+            "https://foo.oss-cn-qingdao.aliyuncs.com/abc.txt?partNumber=1&uploadId=0004B9"
+        );
+    }
+
+    #[test]
+    fn test_with_storage_class() {
+        use reqwest::header::{HeaderMap, CONTENT_TYPE};
+
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, "text/plain".parse().unwrap());
+        let headers = with_storage_class(headers, StorageClass::ARCHIVE).unwrap();
+        assert_eq!(headers.get("x-oss-storage-class").unwrap(), "Archive");
+        assert_eq!(headers.get(CONTENT_TYPE).unwrap(), "text/plain");
+    }
+
+    #[test]
+    fn test_part_headers() {
+        let headers = part_headers(b"aaa").unwrap();
+        assert_eq!(headers["content-md5"], "R7zlx09Yn0hn29V+nKn4CA==");
+
+        let part = vec![1u8; 1024];
+        let headers = part_headers(&part).unwrap();
+        assert_eq!(
+            headers["content-md5"],
+            crate::ContentMd5::from_bytes(&part).as_str()
+        );
+    }
+
+    #[test]
+    fn test_copy_part_headers() {
+        let headers = copy_part_headers(&Object::new("/foo/abc.zip"), (0, 99)).unwrap();
+        assert_eq!(headers.get("x-oss-copy-source").unwrap(), "/foo/abc.zip");
+        assert_eq!(
+            headers.get("x-oss-copy-source-range").unwrap(),
+            "bytes=0-99"
+        );
+    }
+
+    #[test]
+    fn test_complete_body() {
+        let parts = vec![
+            (1, "\"3349DC70\"".to_owned()),
+            (2, "\"8EFDA8BE\"".to_owned()),
+        ];
+        assert_eq!(
+            complete_body(&parts),
+            "<CompleteMultipartUpload>\
+            <Part><PartNumber>1</PartNumber><ETag>\"3349DC70\"</ETag></Part>\
+            <Part><PartNumber>2</PartNumber><ETag>\"8EFDA8BE\"</ETag></Part>\
+            </CompleteMultipartUpload>"
+        );
+    }
+
+    #[test]
+    fn test_parse_list_xml() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <ListMultipartUploadsResult>
+          <Bucket>oss-example</Bucket>
+          <KeyMarker></KeyMarker>
+          <UploadIdMarker></UploadIdMarker>
+          <NextKeyMarker>oss.avi</NextKeyMarker>
+          <NextUploadIdMarker>0004B99B8E707874FC2D692FA5D77D3F</NextUploadIdMarker>
+          <Delimiter></Delimiter>
+          <Prefix></Prefix>
+          <MaxUploads>2</MaxUploads>
+          <IsTruncated>true</IsTruncated>
+          <Upload>
+            <Key>multipart.data</Key>
+            <UploadId>0004B999EF518A1FE585B0C9360DC4C8</UploadId>
+            <Initiated>2012-02-23T04:18:23.000Z</Initiated>
+          </Upload>
+          <Upload>
+            <Key>oss.avi</Key>
+            <UploadId>0004B99B8E707874FC2D692FA5D77D3F</UploadId>
+            <Initiated>2012-02-23T06:14:27.000Z</Initiated>
+          </Upload>
+        </ListMultipartUploadsResult>"#;
+
+        let (list, next) = MultipartUpload::parse_list_xml(xml).unwrap();
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[0].object(), &Object::new("multipart.data"));
+        assert_eq!(list[0].upload_id(), "0004B999EF518A1FE585B0C9360DC4C8");
+        assert!(list[0].parts().is_empty());
+        assert_eq!(
+            next,
+            Some((
+                "oss.avi".to_owned(),
+                "0004B99B8E707874FC2D692FA5D77D3F".to_owned()
+            ))
+        );
+
+        let xml = xml.replace(
+            "<IsTruncated>true</IsTruncated>",
+            "<IsTruncated>false</IsTruncated>",
+        );
+        let (_, next) = MultipartUpload::parse_list_xml(&xml).unwrap();
+        assert_eq!(next, None);
+    }
+
+    #[test]
+    fn test_take_full_parts() {
+        let mut buffer: Vec<u8> = (0..25).collect();
+        let parts = take_full_parts(&mut buffer, 10);
+        assert_eq!(
+            parts,
+            vec![(0..10).collect::<Vec<u8>>(), (10..20).collect()]
+        );
+        assert_eq!(buffer, (20..25).collect::<Vec<u8>>());
+
+        let parts = take_full_parts(&mut buffer, 10);
+        assert!(parts.is_empty());
+        assert_eq!(buffer.len(), 5);
+    }
+
+    /// 每个请求的 `METHOD path query` 和请求内容
+    type Requests = Arc<Mutex<Vec<(String, Vec<u8>)>>>;
+
+    /// 记录每个请求，`status` 根据 `METHOD path query` 决定响应的状态码
+    fn mock_client(status: fn(&str) -> u16) -> (Client, Requests) {
+        let mut client = Client::new(Key::new("foo"), Secret::new("bar"));
+        client.set_bucket(Bucket::new("foo", EndPoint::CN_QINGDAO));
+        client.set_part_size(MIN_PART_SIZE).unwrap();
+
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let log = requests.clone();
+        client.set_mock(move |request| {
+            let line = format!(
+                "{} {} {}",
+                request.method(),
+                request.url().path(),
+                request.url().query().unwrap_or_default()
+            );
+            let body = request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .unwrap_or_default()
+                .to_vec();
+            let status = status(&line);
+            log.lock().unwrap().push((line, body));
+            http::Response::builder()
+                .status(status)
+                .header("etag", "\"mock\"")
+                .body("<UploadId>mock</UploadId>")
+                .unwrap()
+                .into()
+        });
+
+        (client, requests)
+    }
+
+    #[tokio::test]
+    async fn test_writer_auto_flush() {
+        let (client, requests) = mock_client(|_| 200);
+        let object = Object::new("writer.bin");
+
+        let chunk_len = MIN_PART_SIZE as usize / 2 + 1;
+        let mut content = Vec::new();
+        let mut writer = MultipartWriter::new(object, &client).auto_flush(true);
+        for i in 1..=6 {
+            let chunk = vec![i as u8; chunk_len];
+            writer.write(&chunk, &client).await.unwrap();
+            content.extend_from_slice(&chunk);
+            // 每写入两块就凑满了一个分片，写入后立即上传，缓存中不超过一个分片
+            assert_eq!(writer.uploaded_parts(), i / 2);
+            assert!(writer.buffered_len() < MIN_PART_SIZE as usize);
+        }
+        writer.finish(&client).await.unwrap();
+
+        let requests = requests.lock().unwrap();
+        let lines: Vec<_> = requests.iter().map(|(line, _)| line.as_str()).collect();
+        assert_eq!(
+            lines,
+            [
+                "POST /writer.bin uploads",
+                "PUT /writer.bin partNumber=1&uploadId=mock",
+                "PUT /writer.bin partNumber=2&uploadId=mock",
+                "PUT /writer.bin partNumber=3&uploadId=mock",
+                "PUT /writer.bin partNumber=4&uploadId=mock",
+                "POST /writer.bin uploadId=mock",
+            ]
+        );
+
+        // 按顺序拼接各分片的内容即为写入的内容
+        let parts = &requests[1..5];
+        for (i, (_, body)) in parts[..3].iter().enumerate() {
+            let start = i * MIN_PART_SIZE as usize;
+            assert_eq!(body[..], content[start..start + MIN_PART_SIZE as usize]);
+        }
+        assert_eq!(parts[3].1[..], content[MIN_PART_SIZE as usize * 3..]);
+
+        let complete = String::from_utf8(requests[5].1.clone()).unwrap();
+        assert_eq!(complete.matches("<Part>").count(), 4);
+        assert!(complete.contains("<PartNumber>4</PartNumber>"));
+    }
+
+    #[tokio::test]
+    async fn test_writer_reset_for() {
+        let (client, requests) = mock_client(|_| 200);
+
+        let mut writer = MultipartWriter::new(Object::new("a.txt"), &client);
+        writer.write(b"aaa", &client).await.unwrap();
+        writer.finish(&client).await.unwrap();
+        assert_eq!(writer.buffered_len(), 0);
+
+        // 没有未完成的分片上传任务时不发送请求
+        writer
+            .reset_for(Object::new("b.txt"), &client)
+            .await
+            .unwrap();
+        writer.write(b"bbb", &client).await.unwrap();
+        writer.finish(&client).await.unwrap();
+
+        // 切换时取消未完成的分片上传任务，并丢弃缓存中的内容
+        writer
+            .write(&vec![1u8; MIN_PART_SIZE as usize + 1], &client)
+            .await
+            .unwrap();
+        writer.flush(&client).await.unwrap();
+        writer
+            .reset_for(Object::new("c.txt"), &client)
+            .await
+            .unwrap();
+        assert_eq!(writer.uploaded_parts(), 0);
+        assert_eq!(writer.buffered_len(), 0);
+        writer.write(b"ccc", &client).await.unwrap();
+        writer.finish(&client).await.unwrap();
+
+        let requests = requests.lock().unwrap();
+        let lines: Vec<_> = requests.iter().map(|(line, _)| line.as_str()).collect();
+        assert_eq!(
+            lines,
+            [
+                "PUT /a.txt ",
+                "PUT /b.txt ",
+                "POST /b.txt uploads",
+                "PUT /b.txt partNumber=1&uploadId=mock",
+                "DELETE /b.txt uploadId=mock",
+                "PUT /c.txt ",
+            ]
+        );
+        assert_eq!(requests[0].1, b"aaa");
+        assert_eq!(requests[1].1, b"bbb");
+        assert_eq!(requests[5].1, b"ccc");
+    }
+
+    #[tokio::test]
+    async fn test_writer_failed_part() {
+        let (client, requests) = mock_client(|line| {
+            if line.contains(" partNumber=") {
+                500
+            } else {
+                200
+            }
+        });
+
+        let mut writer = MultipartWriter::new(Object::new("writer.bin"), &client);
+        writer
+            .write(&vec![1u8; MIN_PART_SIZE as usize + 10], &client)
+            .await
+            .unwrap();
+        assert!(matches!(
+            writer.flush(&client).await,
+            Err(OssError::ServerStatus(_))
+        ));
+
+        // 失败后不能继续写入，也不能用剩余的内容完成上传
+        assert!(matches!(
+            writer.write(b"abc", &client).await,
+            Err(OssError::WriterFailed)
+        ));
+        assert!(matches!(
+            writer.flush(&client).await,
+            Err(OssError::WriterFailed)
+        ));
+        assert!(matches!(
+            writer.finish(&client).await,
+            Err(OssError::WriterFailed)
+        ));
+
+        let requests = requests.lock().unwrap();
+        let lines: Vec<_> = requests.iter().map(|(line, _)| line.as_str()).collect();
+        assert_eq!(
+            lines,
+            [
+                "POST /writer.bin uploads",
+                "PUT /writer.bin partNumber=1&uploadId=mock",
+                "DELETE /writer.bin uploadId=mock",
+            ]
+        );
+    }
+}
@@ -1,29 +1,107 @@
-use std::ops::{Index, IndexMut};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt, fs,
+    io::{Read, SeekFrom},
+    ops::{Index, IndexMut, Range},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
 
 use chrono::{DateTime, Utc};
+use futures_util::{
+    io::{AllowStdIo, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    stream, Future, StreamExt, TryStreamExt,
+};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
 use reqwest::{
-    header::{HeaderMap, CONTENT_LENGTH},
-    Method,
+    header::{
+        HeaderMap, HeaderValue, CACHE_CONTROL, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE,
+        IF_RANGE, RANGE,
+    },
+    Method, Response, StatusCode,
 };
 use url::Url;
 
 use crate::{
-    client::Client,
+    auth::CONTENT_MD5,
+    bucket::Grant,
+    cache::DownloadCache,
+    client::{Client, SignedRequest, MAX_PART_SIZE},
     error::OssError,
-    types::{CanonicalizedResource, ObjectQuery},
+    multipart::{MultipartUpload, MAX_PARTS},
+    types::{
+        to_gmt, CanonicalizedResource, ContentMd5, ContentRange, ObjectQuery, Owner, StorageClass,
+    },
     Bucket,
 };
 
-#[derive(Debug)]
+/// 对象列表，比较时会同时比较 bucket，查询条件和分页 token
+#[derive(Debug, PartialEq, Eq)]
 pub struct Objects {
-    //bucket: Bucket,
+    bucket: Option<Bucket>,
     list: Vec<Object>,
+    /// 使用 `delimiter` 查询时返回的公共前缀（目录）
+    common_prefixes: Vec<String>,
+    /// 之前各页已经返回过的公共前缀，翻页时用于去重
+    previous_prefixes: HashSet<String>,
+    /// OSS 返回的 `<Delimiter>`
+    delimiter: Option<String>,
     next_token: Option<String>,
+    /// 获取当前页时使用的查询条件
+    query: ObjectQuery,
 }
 
 impl Objects {
     pub fn new(list: Vec<Object>, next_token: Option<String>) -> Objects {
-        Objects { list, next_token }
+        Objects {
+            bucket: None,
+            list,
+            common_prefixes: Vec::new(),
+            previous_prefixes: HashSet::new(),
+            delimiter: None,
+            next_token,
+            query: ObjectQuery::new(),
+        }
+    }
+
+    /// 设置公共前缀，重复的前缀只保留第一个，之前各页返回过的前缀会被去掉
+    pub(crate) fn with_common_prefixes(mut self, common_prefixes: Vec<String>) -> Objects {
+        self.set_common_prefixes(common_prefixes);
+        self
+    }
+
+    fn set_common_prefixes(&mut self, common_prefixes: Vec<String>) {
+        let mut seen = HashSet::new();
+        let previous = &self.previous_prefixes;
+        self.common_prefixes = common_prefixes
+            .into_iter()
+            .filter(|p| !previous.contains(p) && seen.insert(p.clone()))
+            .collect();
+    }
+
+    /// 作为 `previous` 的下一页，记录之前各页返回过的公共前缀，并从当前页中去掉这些前缀
+    fn after_page(mut self, previous: &Objects) -> Objects {
+        self.previous_prefixes = previous
+            .previous_prefixes
+            .iter()
+            .chain(&previous.common_prefixes)
+            .cloned()
+            .collect();
+        let common_prefixes = std::mem::take(&mut self.common_prefixes);
+        self.with_common_prefixes(common_prefixes)
+    }
+
+    /// 设置 OSS 返回的 `delimiter`，空字符串视为没有设置
+    pub(crate) fn with_delimiter(mut self, delimiter: Option<String>) -> Objects {
+        self.delimiter = delimiter.filter(|d| !d.is_empty());
+        self
+    }
+
+    /// 记录获取当前页所用的 bucket 和查询条件，用于重新获取当前页
+    pub(crate) fn with_search(mut self, bucket: Bucket, query: ObjectQuery) -> Objects {
+        self.bucket = Some(bucket);
+        self.query = query;
+        self
     }
 
     pub fn next_token(&self) -> Option<&String> {
@@ -42,20 +120,246 @@ impl Objects {
         &self.list
     }
 
+    /// 取出当前页的文件列表
+    pub fn into_vec(self) -> Vec<Object> {
+        self.list
+    }
+
+    /// 公共前缀（目录）列表，包含查询时使用的 `prefix`，通过 [`next_page`](Self::next_page)
+    /// 或 [`next_list`](Self::next_list) 翻页时，不包含之前各页已经返回过的前缀
+    pub fn common_prefixes(&self) -> &[String] {
+        &self.common_prefixes
+    }
+
+    /// OSS 在列举结果中返回的 `delimiter`，可用于确认服务端使用的分隔符与请求时一致
+    pub fn delimiter(&self) -> Option<&str> {
+        self.delimiter.as_deref()
+    }
+
+    /// 获取当前页时使用的 `prefix`，没有设置或者为空字符串时返回 `None`
+    pub fn prefix(&self) -> Option<&str> {
+        self.query
+            .get(ObjectQuery::PREFIX)
+            .map(String::as_str)
+            .filter(|p| !p.is_empty())
+    }
+
+    /// 去掉查询时使用的 `prefix` 后的公共前缀列表，例如以 `prefix=fun/` 查询时，
+    /// `fun/movie/` 会返回 `movie/`
+    pub fn relative_common_prefixes(&self) -> impl Iterator<Item = &str> {
+        let prefix = self.prefix().unwrap_or_default();
+        self.common_prefixes
+            .iter()
+            .map(move |p| p.strip_prefix(prefix).unwrap_or(p))
+    }
+
+    /// 筛选出指定扩展名的文件，扩展名可以带 `.` 也可以不带，不区分大小写
+    /// ```rust
+    /// # use aliyun_oss_client::{Object, Objects};
+    /// let list = Objects::new(
+    ///     vec![
+    ///         Object::new("foo.jpg"),
+    ///         Object::new("path/bar.JPG"),
+    ///         Object::new("path/jpg"),
+    ///         Object::new("baz.png"),
+    ///     ],
+    ///     None,
+    /// );
+    /// let paths: Vec<_> = list.filter_by_extension(".jpg").map(|o| o.get_path()).collect();
+    /// assert_eq!(paths, ["foo.jpg", "path/bar.JPG"]);
+    /// ```
+    pub fn filter_by_extension<'a>(&'a self, ext: &'a str) -> impl Iterator<Item = &'a Object> {
+        let ext = ext.trim_start_matches('.');
+        self.list.iter().filter(move |obj| {
+            let file_name = obj.path.rsplit('/').next().unwrap_or_default();
+            match file_name.rsplit_once('.') {
+                Some((_, e)) => e.eq_ignore_ascii_case(ext),
+                None => false,
+            }
+        })
+    }
+
+    /// 筛选出指定前缀的文件
+    /// ```rust
+    /// # use aliyun_oss_client::{Object, Objects};
+    /// let list = Objects::new(
+    ///     vec![
+    ///         Object::new("foo.jpg"),
+    ///         Object::new("path/bar.jpg"),
+    ///         Object::new("path2/baz.png"),
+    ///     ],
+    ///     None,
+    /// );
+    /// let paths: Vec<_> = list.filter_by_prefix("path/").map(|o| o.get_path()).collect();
+    /// assert_eq!(paths, ["path/bar.jpg"]);
+    /// ```
+    pub fn filter_by_prefix<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = &'a Object> {
+        self.list
+            .iter()
+            .filter(move |obj| obj.path.starts_with(prefix))
+    }
+
+    /// 筛选出路径符合 glob 模式的文件，见 [`Object::matches_glob`]
+    /// ```rust
+    /// # use aliyun_oss_client::{Object, Objects};
+    /// let list = Objects::new(
+    ///     vec![
+    ///         Object::new("logs/2024-01/a.gz"),
+    ///         Object::new("logs/2024-01/a.txt"),
+    ///         Object::new("logs/2023-12/b.gz"),
+    ///         Object::new("logs/2024-02/sub/c.gz"),
+    ///     ],
+    ///     None,
+    /// );
+    /// let paths: Vec<_> = list.filter_glob("logs/2024-*/*.gz").map(|o| o.get_path()).collect();
+    /// assert_eq!(paths, ["logs/2024-01/a.gz"]);
+    /// ```
+    pub fn filter_glob<'a>(&'a self, pattern: &'a str) -> impl Iterator<Item = &'a Object> {
+        self.list
+            .iter()
+            .filter(move |obj| obj.matches_glob(pattern))
+    }
+
     pub async fn next_list(
-        self,
+        mut self,
         query: &ObjectQuery,
         client: &Client,
     ) -> Result<Objects, OssError> {
         let mut q = query.clone();
-        if let Some(token) = self.next_token {
-            q.insert(ObjectQuery::CONTINUATION_TOKEN, token);
+        if let Some(token) = self.next_token.take() {
+            q.insert_next_token(token);
         }
         match client.bucket() {
-            Some(bucket) => bucket.get_objects(&q, client).await,
+            Some(bucket) => Ok(bucket.get_objects(&q, client).await?.after_page(&self)),
             None => Err(OssError::NoFoundBucket),
         }
     }
+
+    /// 使用获取当前页时的查询条件（如 `prefix`，`max-keys`，`delimiter`）获取下一页，
+    /// 没有下一页时返回 `None`
+    ///
+    /// 优先使用获取当前页时的 bucket，没有时使用 client 的默认 bucket
+    pub async fn next_page(&self, client: &Client) -> Result<Option<Objects>, OssError> {
+        let query = match self.next_query() {
+            Some(query) => query,
+            None => return Ok(None),
+        };
+        let bucket = self
+            .bucket
+            .as_ref()
+            .or(client.bucket())
+            .ok_or(OssError::NoFoundBucket)?;
+
+        let page = bucket.get_objects(&query, client).await?;
+        Ok(Some(page.after_page(self)))
+    }
+
+    /// 获取下一页时使用的查询条件，在当前页的查询条件上替换分页 token
+    fn next_query(&self) -> Option<ObjectQuery> {
+        let token = self.next_token.clone()?;
+        let mut query = self.query.clone();
+        query.insert_next_token(token);
+        Some(query)
+    }
+
+    /// 使用获取当前页时的查询条件重新获取当前页，并替换当前的内容，
+    /// 适用于上传或删除文件后刷新列表
+    ///
+    /// 优先使用获取当前页时的 bucket，没有时使用 client 的默认 bucket
+    pub async fn refresh(&mut self, client: &Client) -> Result<(), OssError> {
+        let bucket = self
+            .bucket
+            .as_ref()
+            .or(client.bucket())
+            .ok_or(OssError::NoFoundBucket)?;
+
+        let Objects {
+            list,
+            common_prefixes,
+            delimiter,
+            next_token,
+            ..
+        } = bucket.get_objects(&self.query, client).await?;
+        self.list = list;
+        self.set_common_prefixes(common_prefixes);
+        self.delimiter = delimiter;
+        self.next_token = next_token;
+
+        Ok(())
+    }
+
+    /// 并发下载当前页的所有文件到 `dest_dir`，本地目录结构与文件路径一致，最多同时下载
+    /// [`DOWNLOAD_ALL_CONCURRENCY`] 个文件，返回每个文件及写入的字节数
+    ///
+    /// 文件从 client 的默认 bucket 下载，以 `/` 结尾的目录占位文件会被跳过
+    pub async fn download_all<P: AsRef<Path>>(
+        &self,
+        dest_dir: P,
+        client: &Client,
+    ) -> Result<Vec<(Object, u64)>, OssError> {
+        let dest_dir = dest_dir.as_ref();
+
+        stream::iter(&self.list)
+            .filter_map(|obj| async move { local_path(dest_dir, &obj.path).map(|p| (obj, p)) })
+            .map(|(obj, path)| async move {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                let content = obj.download(client).await?;
+                fs::write(&path, &content)?;
+                Ok((obj.clone(), content.len() as u64))
+            })
+            .buffered(DOWNLOAD_ALL_CONCURRENCY)
+            .try_collect()
+            .await
+    }
+}
+
+/// [`Objects::download_all`] 同时下载的最大文件数
+pub const DOWNLOAD_ALL_CONCURRENCY: usize = 8;
+
+/// 文件路径在 `dir` 下对应的本地路径，忽略 `.`，`..` 及空的路径片段，避免写到 `dir` 之外，
+/// 目录占位文件（以 `/` 结尾）返回 `None`
+fn local_path(dir: &Path, key: &str) -> Option<PathBuf> {
+    if key.ends_with('/') {
+        return None;
+    }
+    let mut path = dir.to_path_buf();
+    let mut pushed = false;
+    for segment in key.split('/') {
+        if segment.is_empty() || segment == "." || segment == ".." {
+            continue;
+        }
+        path.push(segment);
+        pushed = true;
+    }
+    pushed.then_some(path)
+}
+
+/// 逐段匹配 glob 模式，`**` 可以匹配零个或多个段
+fn glob_segments(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|i| glob_segments(rest, &path[i..])),
+        Some((segment, rest)) => match path.split_first() {
+            Some((name, path)) => {
+                let segment: Vec<_> = segment.chars().collect();
+                let name: Vec<_> = name.chars().collect();
+                glob_segment(&segment, &name) && glob_segments(rest, path)
+            }
+            None => false,
+        },
+    }
+}
+
+/// 匹配一段中的 `*` 和 `?`
+fn glob_segment(pattern: &[char], name: &[char]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some(('*', rest)) => (0..=name.len()).any(|i| glob_segment(rest, &name[i..])),
+        Some(('?', rest)) => !name.is_empty() && glob_segment(rest, &name[1..]),
+        Some((c, rest)) => name.first() == Some(c) && glob_segment(rest, &name[1..]),
+    }
 }
 
 impl Index<usize> for Objects {
@@ -71,16 +375,209 @@ impl IndexMut<usize> for Objects {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+/// 带有详细信息的对象列表，由 [`Bucket::list_objects`] 返回
+#[derive(Debug)]
+pub struct ObjectInfos {
+    list: Vec<(Object, ObjectInfo)>,
+    next_token: Option<String>,
+}
+
+impl ObjectInfos {
+    pub(crate) fn new(list: Vec<(Object, ObjectInfo)>, next_token: Option<String>) -> Self {
+        ObjectInfos { list, next_token }
+    }
+
+    /// 获取下一页时使用的 `continuation-token`，没有下一页时为 `None`
+    pub fn next_token(&self) -> Option<&str> {
+        self.next_token.as_deref()
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &(Object, ObjectInfo)> {
+        self.list.iter()
+    }
+
+    /// 当前页所有文件大小的总和
+    pub fn total_size(&self) -> u64 {
+        self.list.iter().map(|(_, info)| info.size()).sum()
+    }
+
+    pub fn into_vec(self) -> Vec<(Object, ObjectInfo)> {
+        self.list
+    }
+
+    /// 转换为以文件路径为 key 的 `HashMap`，便于按路径查找，例如与本地文件对比
+    pub fn into_map(self) -> HashMap<Object, ObjectInfo> {
+        self.list.into_iter().collect()
+    }
+
+    /// 与本地目录 `dir` 对比，文件路径与本地路径的对应关系与 [`Objects::download_all`] 相同
+    ///
+    /// 普通上传的文件 etag 为内容的 MD5，大小一致时再对比 MD5；分片上传的文件 etag 不是 MD5，
+    /// 只对比大小。目录占位文件（以 `/` 结尾）会被忽略
+    pub fn diff_local<P: AsRef<Path>>(&self, dir: P) -> Result<SyncPlan, OssError> {
+        let dir = dir.as_ref();
+        let mut local = HashMap::new();
+        if dir.exists() {
+            walk_dir(dir, &mut local)?;
+        }
+
+        let mut plan = SyncPlan::default();
+        for (object, info) in &self.list {
+            let path = match local_path(dir, object.get_path()) {
+                Some(path) => path,
+                None => continue,
+            };
+            match local.remove(&path) {
+                None => plan.missing_local.push(object.clone()),
+                Some(size) if size != info.size() => plan.changed.push(object.clone()),
+                Some(_) if is_multipart_etag(info.etag()) => {}
+                Some(_) => {
+                    if !Client::verify_etag(&fs::read(&path)?, info.etag()) {
+                        plan.changed.push(object.clone());
+                    }
+                }
+            }
+        }
+
+        plan.missing_remote = local
+            .into_keys()
+            .filter_map(|path| {
+                let relative = path.strip_prefix(dir).ok()?;
+                let segments: Vec<_> = relative
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect();
+                Some(Object::new(segments.join("/")))
+            })
+            .collect();
+        plan.missing_local.sort();
+        plan.missing_remote.sort();
+        plan.changed.sort();
+
+        Ok(plan)
+    }
+}
+
+/// [`ObjectInfos::diff_local`] 的对比结果，各列表都按路径排序
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncPlan {
+    missing_local: Vec<Object>,
+    missing_remote: Vec<Object>,
+    changed: Vec<Object>,
+}
+
+impl SyncPlan {
+    /// 只存在于 OSS 中，本地不存在的文件
+    pub fn missing_local(&self) -> &[Object] {
+        &self.missing_local
+    }
+
+    /// 只存在于本地，OSS 中不存在的文件，路径为相对于本地目录的路径
+    pub fn missing_remote(&self) -> &[Object] {
+        &self.missing_remote
+    }
+
+    /// 两边都存在，但大小或内容不一致的文件
+    pub fn changed(&self) -> &[Object] {
+        &self.changed
+    }
+
+    /// 两边完全一致
+    pub fn is_empty(&self) -> bool {
+        self.missing_local.is_empty() && self.missing_remote.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// 分片上传的文件 etag 形如 `"XXX-3"`，不是内容的 MD5
+fn is_multipart_etag(etag: &str) -> bool {
+    etag.contains('-')
+}
+
+/// 递归列出目录下的所有文件及其大小
+fn walk_dir(dir: &Path, files: &mut HashMap<PathBuf, u64>) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let meta = entry.metadata()?;
+        if meta.is_dir() {
+            walk_dir(&entry.path(), files)?;
+        } else {
+            files.insert(entry.path(), meta.len());
+        }
+    }
+    Ok(())
+}
+
+impl Index<usize> for ObjectInfos {
+    type Output = (Object, ObjectInfo);
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.list[index]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Object {
     path: String,
 }
 
+/// 文件在 bucket 中的 key，不包含开头的 `/`
+/// ```rust
+/// # use aliyun_oss_client::Object;
+/// let obj = Object::new("/a/b.txt");
+/// assert_eq!(obj.as_ref(), "a/b.txt");
+/// assert_eq!(obj.to_string(), "a/b.txt");
+/// ```
+impl AsRef<str> for Object {
+    fn as_ref(&self) -> &str {
+        self.path.trim_start_matches('/')
+    }
+}
+
+impl fmt::Display for Object {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_ref())
+    }
+}
+
 impl Object {
     pub fn new<P: Into<String>>(path: P) -> Object {
         Object { path: path.into() }
     }
 
+    /// 批量创建文件，路径不能为空，不能超过 1023 字节，不能以 `\` 开头，
+    /// 遇到第一个不合法的路径时返回错误，错误中附带该路径
+    /// ```rust
+    /// # use aliyun_oss_client::{Error, Object};
+    /// let list = Object::try_from_iter(["a.txt", "path/b.txt"]).unwrap();
+    /// assert_eq!(list, vec![Object::new("a.txt"), Object::new("path/b.txt")]);
+    ///
+    /// let res = Object::try_from_iter(["a.txt", "", "c.txt"]);
+    /// assert!(matches!(res, Err(Error::InvalidObjectPath(p)) if p.is_empty()));
+    /// ```
+    pub fn try_from_iter<I, S>(iter: I) -> Result<Vec<Object>, OssError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        iter.into_iter()
+            .map(|path| {
+                let path = path.into();
+                if is_valid_path(&path) {
+                    Ok(Object { path })
+                } else {
+                    Err(OssError::InvalidObjectPath(path.into()))
+                }
+            })
+            .collect()
+    }
+
     /// 确认文件是否在目录里面
     ///
     /// ```rust
@@ -95,6 +592,35 @@ impl Object {
         self.path.find('/').is_some()
     }
 
+    /// 路径以 `/` 结尾，即控制台创建的目录占位文件
+    ///
+    /// ```rust
+    /// # use aliyun_oss_client::Object;
+    /// assert!(Object::new("path/").is_dir());
+    /// assert!(!Object::new("path/foo.txt").is_dir());
+    /// ```
+    pub fn is_dir(&self) -> bool {
+        self.path.ends_with('/')
+    }
+
+    /// 路径是否符合 glob 模式，模式和路径都按 `/` 分段匹配：`*` 匹配段内任意个字符，
+    /// `?` 匹配段内一个字符，单独成段的 `**` 匹配任意层目录（包括零层），其他字符按原样匹配
+    ///
+    /// ```rust
+    /// # use aliyun_oss_client::Object;
+    /// let obj = Object::new("logs/2024-01/app.gz");
+    /// assert!(obj.matches_glob("logs/2024-*/*.gz"));
+    /// assert!(obj.matches_glob("logs/**/app.?z"));
+    /// assert!(obj.matches_glob("logs/2024-01/app.gz"));
+    /// assert!(!obj.matches_glob("logs/*.gz"));
+    /// ```
+    pub fn matches_glob(&self, pattern: &str) -> bool {
+        let pattern: Vec<_> = pattern.split('/').collect();
+        let path: Vec<_> = self.path.split('/').collect();
+
+        glob_segments(&pattern, &path)
+    }
+
     /// 获取文件的各级目录
     /// ```rust
     /// # use aliyun_oss_client::Object;
@@ -151,12 +677,52 @@ impl Object {
         &self.path
     }
 
+    /// 经过 url 编码的文件路径，`/` 不会被编码，不包含开头的 `/`
+    /// ```rust
+    /// # use aliyun_oss_client::Object;
+    /// let obj = Object::new("/path/foo bar.txt");
+    /// assert_eq!(obj.to_uri_encoded(), "path/foo%20bar.txt");
+    /// ```
+    pub fn to_uri_encoded(&self) -> String {
+        utf8_percent_encode(self.as_ref(), PATH_ENCODE_SET).to_string()
+    }
+
     pub fn to_url(&self, bucket: &Bucket) -> Url {
-        let mut url = bucket.to_url();
+        let mut url = bucket.to_data_url();
         url.set_path(&self.path);
         url
     }
 
+    /// 文件在 client 默认 bucket 中的访问链接（不带签名），endpoint 为内网时是内网链接
+    /// ```
+    /// # use aliyun_oss_client::{Bucket, Client, EndPoint, Key, Object, Secret};
+    /// let mut client = Client::new(Key::new("foo"), Secret::new("bar"));
+    /// let mut endpoint = EndPoint::CN_QINGDAO;
+    /// endpoint.set_internal(true);
+    /// client.set_bucket(Bucket::new("abc", endpoint));
+    ///
+    /// let object = Object::new("path/foo bar.txt");
+    /// assert_eq!(
+    ///     object.url(&client).unwrap().as_str(),
+    ///     "https://abc.oss-cn-qingdao-internal.aliyuncs.com/path/foo%20bar.txt"
+    /// );
+    /// assert_eq!(
+    ///     object.public_url(&client).unwrap().as_str(),
+    ///     "https://abc.oss-cn-qingdao.aliyuncs.com/path/foo%20bar.txt"
+    /// );
+    /// ```
+    pub fn url(&self, client: &Client) -> Result<Url, OssError> {
+        let bucket = client.bucket().ok_or(OssError::NoFoundBucket)?;
+        Ok(self.to_url(bucket))
+    }
+
+    /// 文件的外网访问链接（不带签名），endpoint 为内网时也会返回外网链接，适用于分享给外部使用，
+    /// 文件需要是公共读的才能直接访问
+    pub fn public_url(&self, client: &Client) -> Result<Url, OssError> {
+        let bucket = client.bucket().ok_or(OssError::NoFoundBucket)?;
+        Ok(self.to_url(&bucket.to_public()))
+    }
+
     /// 获取 object 的 meta 信息
     pub async fn get_info(&self, client: &Client) -> Result<ObjectInfo, OssError> {
         let bucket = client.bucket().ok_or(OssError::NoFoundBucket)?;
@@ -166,244 +732,3998 @@ impl Object {
         let resource =
             CanonicalizedResource::new(format!("/{}/{}?objectMeta", bucket.as_str(), self.path));
 
-        let header_map = client.authorization(&method, resource)?;
-
-        let response = reqwest::Client::new()
-            .request(method, url)
-            .headers(header_map)
-            .send()
+        let response = client
+            .send(method, url, resource, HeaderMap::new(), None)
             .await?;
 
-        let headers = response.headers();
-
-        let content_length = headers
-            .get(CONTENT_LENGTH)
-            .ok_or(OssError::NoFoundContentLength)?;
-        let etag = headers.get("etag").ok_or(OssError::NoFoundEtag)?;
-
-        let date = DateTime::parse_from_rfc2822(
-            headers
-                .get("last-modified")
-                .ok_or(OssError::NoFoundLastModified)?
-                .to_str()?,
-        )?;
-        Ok(ObjectInfo {
-            last_modified: date.with_timezone(&Utc),
-            etag: etag.to_str()?.to_string(),
-            size: content_length.to_str()?.parse()?,
-        })
+        ObjectInfo::from_headers(response.headers())
     }
 
     /// 上传文件
     pub async fn upload(&self, content: Vec<u8>, client: &Client) -> Result<(), OssError> {
-        let bucket = client.bucket().ok_or(OssError::NoFoundBucket)?;
-        let url = self.to_url(bucket);
-        let method = Method::PUT;
-        let resource = CanonicalizedResource::from_object(bucket, self);
-
-        let mut header_map = client.authorization(&method, resource)?;
-        if content.is_empty() {
-            header_map.insert(CONTENT_LENGTH, 0.into());
-        }
+        self.upload_with_header(content, HeaderMap::new(), client)
+            .await
+    }
 
-        let response = reqwest::Client::new()
-            .request(method, url)
-            .headers(header_map)
-            .body(content)
-            .send()
-            .await?;
+    /// 上传文件，根据内容大小自动选择上传方式
+    ///
+    /// 超过 [`Client::multipart_threshold`] 的内容按 [`Client::part_size`] 分片上传，上传失败时会
+    /// 取消分片上传任务，否则使用一次 PUT 请求上传。两种方式都会返回最终文件的 etag 和 version-id
+    pub async fn upload_auto(
+        &self,
+        content: Vec<u8>,
+        client: &Client,
+    ) -> Result<UploadResult, OssError> {
+        let parts = match multipart_ranges(
+            content.len(),
+            client.multipart_threshold(),
+            client.part_size(),
+        ) {
+            Some(parts) => parts,
+            None => return self.upload_full(content, client).await,
+        };
 
-        if response.status().is_success() {
-            Ok(())
-        } else {
-            let body = response.text().await?;
-            Err(OssError::from_service(&body))
+        let mut upload = MultipartUpload::init(self, HeaderMap::new(), client).await?;
+        for range in parts {
+            if let Err(e) = upload.upload_part(content[range].to_vec(), client).await {
+                // 以上传过程中的错误为准，取消任务失败时，未完成的分片会由生命周期规则清理
+                let _ = upload.abort(client).await;
+                return Err(e);
+            }
         }
+
+        upload.complete(client).await
     }
 
-    /// 下载文件
-    pub async fn download(&self, client: &Client) -> Result<Vec<u8>, OssError> {
-        let bucket = client.bucket().ok_or(OssError::NoFoundBucket)?;
-        let url = self.to_url(bucket);
-        let method = Method::GET;
-        let resource = CanonicalizedResource::from_object(bucket, self);
+    /// 与 [`upload_auto`](Self::upload_auto) 相同，并在结果中附带上传的字节数和耗时，
+    /// 参见 [`UploadResult::stats`]
+    pub async fn upload_with_stats(
+        &self,
+        content: Vec<u8>,
+        client: &Client,
+    ) -> Result<UploadResult, OssError> {
+        let bytes = content.len() as u64;
+        let start = Instant::now();
+        let mut result = self.upload_auto(content, client).await?;
+        result.stats = Some(UploadStats::new(bytes, start.elapsed()));
 
-        let header_map = client.authorization(&method, resource)?;
+        Ok(result)
+    }
 
-        let response = reqwest::Client::new()
-            .request(method, url)
-            .headers(header_map)
-            .send()
-            .await?
-            .bytes()
+    /// 从 reader 中读取全部内容并上传，返回上传的字节数
+    ///
+    /// 与 [`upload_stream`](Self::upload_stream) 相同，按 [`Client::part_size`] 分段读取，
+    /// 内容超过一个分片时使用分片上传
+    pub async fn upload_from_reader<R: Read>(
+        &self,
+        reader: R,
+        client: &Client,
+    ) -> Result<u64, OssError> {
+        let (_, size) = self
+            .upload_reader(&mut AllowStdIo::new(reader), client)
             .await?;
 
-        Ok(response.into())
+        Ok(size)
     }
 
-    /// 复制文件
-    pub async fn copy_from(&self, client: &Client, source: &Object) -> Result<(), OssError> {
-        let bucket = client.bucket().ok_or(OssError::NoFoundBucket)?;
-        let url = self.to_url(bucket);
-        let method = Method::PUT;
-        let resource = CanonicalizedResource::from_object(bucket, self);
+    /// 上传长度未知的内容（如管道，实时生成的数据），按 [`Client::part_size`] 分段读取
+    ///
+    /// 读取到的内容不足一个分片时，使用普通上传，否则自动切换为分片上传，上传失败时会取消
+    /// 分片上传任务。分片数量有 [`MAX_PARTS`] 的限制，每上传 [`PARTS_PER_SIZE_STEP`] 个分片，
+    /// 分片大小翻倍（不超过 [`MAX_PART_SIZE`]），使很大的内容也不会超过分片数量的限制。
+    /// OSS 的 PutObject 虽然支持 chunked 编码的请求，但这里统一使用分片上传，以便限制内存占用
+    ///
+    /// 两种方式都会返回最终文件的 etag 和 version-id，分片上传时为合并后文件的 etag
+    pub async fn upload_stream<R: AsyncRead + Unpin>(
+        &self,
+        mut reader: R,
+        client: &Client,
+    ) -> Result<UploadResult, OssError> {
+        let (result, _) = self.upload_reader(&mut reader, client).await?;
+        Ok(result)
+    }
+
+    /// 分段读取并上传 reader 中的内容，返回上传结果和上传的字节数
+    async fn upload_reader<R: AsyncRead + Unpin>(
+        &self,
+        reader: &mut R,
+        client: &Client,
+    ) -> Result<(UploadResult, u64), OssError> {
+        let part_size = client.part_size() as usize;
+        let first = read_part(reader, part_size).await?;
+        let mut size = first.len() as u64;
+        if first.len() < part_size {
+            return Ok((self.upload_full(first, client).await?, size));
+        }
+
+        let mut upload = MultipartUpload::init(self, HeaderMap::new(), client).await?;
+        let mut part = first;
+        let mut part_number = 1;
+        loop {
+            part_number += 1;
+            let next_size = stream_part_size(part_size as u64, part_number) as usize;
+            let res = match upload.upload_part(part, client).await {
+                Ok(()) => read_part(reader, next_size).await.map_err(OssError::from),
+                Err(e) => Err(e),
+            };
+            part = match res {
+                Ok(part) => part,
+                Err(e) => {
+                    // 以上传过程中的错误为准，取消任务失败时，未完成的分片会由生命周期规则清理
+                    let _ = upload.abort(client).await;
+                    return Err(e);
+                }
+            };
+            if part.is_empty() {
+                break;
+            }
+            size += part.len() as u64;
+        }
+
+        Ok((upload.complete(client).await?, size))
+    }
 
+    /// 上传文件，并同时设置对象标签（`x-oss-tagging`）
+    ///
+    /// 标签最多 10 个，key 最长 128 个字符，value 最长 256 个字符
+    pub async fn upload_with_tags<K: AsRef<str>, V: AsRef<str>>(
+        &self,
+        content: Vec<u8>,
+        tags: &[(K, V)],
+        client: &Client,
+    ) -> Result<(), OssError> {
         let mut headers = HeaderMap::new();
-        let Object { path } = source;
-        headers.insert("x-oss-copy-source", path.try_into()?);
+        headers.insert("x-oss-tagging", tagging_header(tags)?);
 
-        let header_map = client.authorization_header(&method, resource, headers)?;
+        self.upload_with_header(content, headers, client).await
+    }
 
-        let response = reqwest::Client::new()
-            .request(method, url)
-            .headers(header_map)
-            .send()
-            .await?;
+    /// 上传文件，并设置 `Cache-Control`，下载文件时 OSS 及 CDN 会返回该 header
+    pub async fn upload_with_cache_control(
+        &self,
+        content: Vec<u8>,
+        cache_control: CacheControl,
+        client: &Client,
+    ) -> Result<(), OssError> {
+        let mut headers = HeaderMap::new();
+        headers.insert(CACHE_CONTROL, cache_control.to_string().try_into()?);
 
-        if response.status().is_success() {
-            Ok(())
-        } else {
-            let body = response.text().await?;
-            Err(OssError::from_service(&body))
-        }
+        self.upload_with_header(content, headers, client).await
     }
 
-    /// 删除文件
-    pub async fn delete(&self, client: &Client) -> Result<(), OssError> {
-        let bucket = client.bucket().ok_or(OssError::NoFoundBucket)?;
-        let url = self.to_url(bucket);
-        let method = Method::DELETE;
-        let resource = CanonicalizedResource::from_object(bucket, self);
+    /// 上传文件，根据文件路径的扩展名自动设置 `Content-Type`，无法识别时使用
+    /// `application/octet-stream`
+    pub async fn upload_auto_content_type(
+        &self,
+        content: Vec<u8>,
+        client: &Client,
+    ) -> Result<(), OssError> {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static(content_type_from_path(&self.path)),
+        );
 
-        let header_map = client.authorization(&method, resource)?;
+        self.upload_with_header(content, headers, client).await
+    }
+
+    /// 上传文件，可以附带自定义的 header，其中 `x-oss-` 开头的 header 会参与签名
+    pub async fn upload_with_header(
+        &self,
+        content: Vec<u8>,
+        headers: HeaderMap,
+        client: &Client,
+    ) -> Result<(), OssError> {
+        self.upload_response(content, headers, client)
+            .await
+            .map(|_| ())
+    }
 
-        let response = reqwest::Client::new()
-            .request(method, url)
-            .headers(header_map)
-            .send()
+    /// 上传文件，并返回 OSS 保存后的 etag，version-id 和 crc64 信息
+    pub async fn upload_full(
+        &self,
+        content: Vec<u8>,
+        client: &Client,
+    ) -> Result<UploadResult, OssError> {
+        let response = self
+            .upload_response(content, HeaderMap::new(), client)
             .await?;
 
-        if response.status().is_success() {
-            Ok(())
-        } else {
-            let body = response.text().await?;
-            Err(OssError::from_service(&body))
-        }
+        UploadResult::from_headers(response.headers())
     }
-}
 
-#[derive(Debug)]
-pub struct ObjectInfo {
-    last_modified: DateTime<Utc>,
+    /// 上传文件，并使用调用方提供的 `Content-MD5`（例如从清单文件中读取），不会重新计算，
+    /// 内容与 MD5 不一致时 OSS 会拒绝上传
+    pub async fn upload_with_md5(
+        &self,
+        content: Vec<u8>,
+        md5: &ContentMd5,
+        client: &Client,
+    ) -> Result<UploadResult, OssError> {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_MD5, md5.as_str().try_into()?);
+
+        let response = self.upload_response(content, headers, client).await?;
+
+        UploadResult::from_headers(response.headers())
+    }
+
+    /// 上传文件，并按 `algorithm` 校验内容的完整性，参见 [`ChecksumAlgorithm`]
+    pub async fn upload_with_checksum(
+        &self,
+        content: Vec<u8>,
+        algorithm: ChecksumAlgorithm,
+        client: &Client,
+    ) -> Result<UploadResult, OssError> {
+        let headers = algorithm.headers(&content)?;
+        let crc = (algorithm == ChecksumAlgorithm::Crc64).then(|| crc64_ecma(0, &content));
+
+        let response = self.upload_response(content, headers, client).await?;
+        let result = UploadResult::from_headers(response.headers())?;
+        if let Some(crc) = crc {
+            verify_crc64(crc, &result)?;
+        }
+
+        Ok(result)
+    }
+
+    /// 上传文件，并使用本地计算的 MD5 校验 OSS 返回的 etag，不一致时返回 `EtagMismatch` 错误
+    ///
+    /// 只适用于普通上传（非分片上传）的文件
+    pub async fn upload_verified(
+        &self,
+        content: Vec<u8>,
+        client: &Client,
+    ) -> Result<UploadResult, OssError> {
+        let digest = md5::compute(&content);
+        let result = self.upload_full(content, client).await?;
+
+        if !result
+            .etag()
+            .trim_matches('"')
+            .eq_ignore_ascii_case(&format!("{:X}", digest))
+        {
+            return Err(OssError::EtagMismatch);
+        }
+
+        Ok(result)
+    }
+
+    /// 上传文件，并携带内容的 `Content-MD5`，网络错误时最多重试 `retries` 次
+    ///
+    /// 每次重试都使用同一个 `Content-MD5`，OSS 会拒绝与之不一致的内容，保证多次重试时
+    /// 最终保存的是同一份内容
+    pub async fn upload_idempotent(
+        &self,
+        content: Vec<u8>,
+        retries: usize,
+        client: &Client,
+    ) -> Result<UploadResult, OssError> {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            CONTENT_MD5,
+            ContentMd5::from_bytes(&content).as_str().try_into()?,
+        );
+
+        let mut attempt = 0;
+        loop {
+            match self
+                .upload_response(content.clone(), headers.clone(), client)
+                .await
+            {
+                Err(
+                    OssError::Reqwest(_)
+                    | OssError::Connect(_)
+                    | OssError::Timeout(_)
+                    | OssError::Dns(_),
+                ) if attempt < retries => attempt += 1,
+                res => return UploadResult::from_headers(res?.headers()),
+            }
+        }
+    }
+
+    /// 计算上传 `content_length` 字节内容时将要发送的请求（url，签名资源和签名后的 header），
+    /// 但不发送请求，可用于在命令行工具中展示实际会发送的内容
+    pub fn upload_dry_run(
+        &self,
+        content_length: u64,
+        mut headers: HeaderMap,
+        client: &Client,
+    ) -> Result<SignedRequest, OssError> {
+        let bucket = client.bucket().ok_or(OssError::NoFoundBucket)?;
+        let url = self.to_url(bucket);
+        let resource = CanonicalizedResource::from_object(bucket, self);
+        headers.insert(CONTENT_LENGTH, content_length.into());
+
+        client.dry_run(Method::PUT, url, resource, headers)
+    }
+
+    async fn upload_response(
+        &self,
+        content: Vec<u8>,
+        headers: HeaderMap,
+        client: &Client,
+    ) -> Result<Response, OssError> {
+        let bucket = client.bucket().ok_or(OssError::NoFoundBucket)?;
+        let url = self.to_url(bucket);
+        let method = Method::PUT;
+        let resource = CanonicalizedResource::from_object(bucket, self);
+
+        client
+            .send(method, url, resource, headers, Some(content))
+            .await
+    }
+
+    /// 下载文件，响应中带有 `Content-MD5`（非分片上传的小文件）时会校验下载的内容，
+    /// 不一致时返回 `Md5Mismatch` 错误
+    pub async fn download(&self, client: &Client) -> Result<Vec<u8>, OssError> {
+        let response = self.download_response(client).await?;
+        let headers = response.headers().clone();
+        let content = response.bytes().await?;
+        verify_content_md5(&headers, &content)?;
+
+        Ok(content.into())
+    }
+
+    /// 下载文件，并使用 `cache` 缓存文件内容，文件没有变化时（304）直接返回缓存的内容
+    pub async fn download_cached(
+        &self,
+        cache: &DownloadCache,
+        client: &Client,
+    ) -> Result<Vec<u8>, OssError> {
+        let bucket = client.bucket().ok_or(OssError::NoFoundBucket)?;
+        let url = self.to_url(bucket);
+        let resource = CanonicalizedResource::from_object(bucket, self);
+
+        let headers = cache.headers(bucket.as_str(), &self.path)?;
+        let response = client
+            .send_unchecked(Method::GET, url, resource, headers, None)
+            .await?;
+
+        cache
+            .read_response(bucket.as_str(), &self.path, response)
+            .await
+    }
+
+    /// 下载文件，同时返回响应 header 中的文件信息（`Content-Type`，etag，大小，修改时间，
+    /// version-id），不需要再单独请求文件的 meta 信息
+    pub async fn download_with_info(
+        &self,
+        client: &Client,
+    ) -> Result<(Vec<u8>, ObjectInfo), OssError> {
+        let response = self.download_response(client).await?;
+        let info = ObjectInfo::from_headers(response.headers())?;
+        let headers = response.headers().clone();
+        let content = response.bytes().await?;
+        verify_content_md5(&headers, &content)?;
+
+        Ok((content.into(), info))
+    }
+
+    /// 获取软链接指向的目标文件及其所在的 bucket
+    ///
+    /// 指向其他 bucket 的软链接（参见 [`put_symlink_to_bucket`](Self::put_symlink_to_bucket)）
+    /// 返回目标 bucket，其 endpoint 与当前 bucket 相同，否则返回当前 bucket
+    pub async fn get_symlink(&self, client: &Client) -> Result<(Bucket, Object), OssError> {
+        let bucket = client.bucket().ok_or(OssError::NoFoundBucket)?;
+        let mut url = self.to_url(bucket);
+        url.set_query(Some(SYMLINK));
+        let resource =
+            CanonicalizedResource::new(format!("/{}/{}?{}", bucket.as_str(), self.path, SYMLINK));
+
+        let response = client
+            .send(Method::GET, url, resource, HeaderMap::new(), None)
+            .await?;
+
+        let (target_bucket, target) = symlink_target(response.headers())?;
+        let target_bucket = match target_bucket {
+            Some(name) if name != bucket.as_str() => Bucket::new(name, bucket.endpoint().clone()),
+            _ => bucket.clone(),
+        };
+
+        Ok((target_bucket, target))
+    }
+
+    /// 创建指向同一 bucket 中 `target` 的软链接，已存在的同名文件会被覆盖
+    pub async fn put_symlink(&self, target: &Object, client: &Client) -> Result<(), OssError> {
+        let headers = self.symlink_headers(None, target)?;
+        self.send_put_symlink(headers, client).await
+    }
+
+    /// 创建指向 `bucket` 中 `target` 的软链接，`bucket` 需要和当前 bucket 属于同一个账号
+    pub async fn put_symlink_to_bucket(
+        &self,
+        bucket: &Bucket,
+        target: &Object,
+        client: &Client,
+    ) -> Result<(), OssError> {
+        let headers = self.symlink_headers(Some(bucket), target)?;
+        self.send_put_symlink(headers, client).await
+    }
+
+    async fn send_put_symlink(&self, headers: HeaderMap, client: &Client) -> Result<(), OssError> {
+        let bucket = client.bucket().ok_or(OssError::NoFoundBucket)?;
+        let mut url = self.to_url(bucket);
+        url.set_query(Some(SYMLINK));
+        let resource =
+            CanonicalizedResource::new(format!("/{}/{}?{}", bucket.as_str(), self.path, SYMLINK));
+
+        client
+            .send(Method::PUT, url, resource, headers, None)
+            .await?;
+
+        Ok(())
+    }
+
+    /// 软链接和目标文件的路径都需要合法，跨 bucket 时目标为 `/{bucket}/{path}`
+    fn symlink_headers(
+        &self,
+        bucket: Option<&Bucket>,
+        target: &Object,
+    ) -> Result<HeaderMap, OssError> {
+        for path in [&self.path, &target.path] {
+            if !is_valid_path(path) {
+                return Err(OssError::InvalidObjectPath(path.as_str().into()));
+            }
+        }
+        let target = match bucket {
+            Some(bucket) => format!("/{}/{}", bucket.as_str(), target.to_uri_encoded()),
+            None => target.to_uri_encoded(),
+        };
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-oss-symlink-target", target.try_into()?);
+        Ok(headers)
+    }
+
+    /// 获取图片的格式，宽高及文件大小，使用图片处理的 `image/info`，处理参数会参与签名
+    pub async fn get_image_info(&self, client: &Client) -> Result<ImageInfo, OssError> {
+        const IMAGE_INFO: &str = "x-oss-process=image/info";
+
+        let bucket = client.bucket().ok_or(OssError::NoFoundBucket)?;
+        let mut url = self.to_url(bucket);
+        url.set_query(Some(IMAGE_INFO));
+        let resource = CanonicalizedResource::new(format!(
+            "/{}/{}?{}",
+            bucket.as_str(),
+            self.path,
+            IMAGE_INFO
+        ));
+
+        let content = client
+            .send(Method::GET, url, resource, HeaderMap::new(), None)
+            .await?
+            .text()
+            .await?;
+
+        ImageInfo::from_json(&content)
+    }
+
+    /// 先解析软链接指向的目标文件，再从目标文件所在的 bucket 下载，返回目标 bucket，
+    /// 目标文件及其内容
+    ///
+    /// 直接下载软链接时 OSS 也会返回目标文件的内容，需要知道目标文件路径时使用该方法
+    pub async fn download_follow_symlink(
+        &self,
+        client: &Client,
+    ) -> Result<(Bucket, Object, Vec<u8>), OssError> {
+        let (bucket, target) = self.get_symlink(client).await?;
+        let content = target.download(&client.with_bucket(bucket.clone())).await?;
+
+        Ok((bucket, target, content))
+    }
+
+    /// 下载文件，当文件以 `Content-Encoding: gzip` 存储时，返回解压后的原始内容，
+    /// 开启 [`Client::set_decompress_by_ext`] 时，没有 `Content-Encoding` 的 `.gz`，`.zst`
+    /// （需要开启 `zstd` feature）文件也会被解压
+    ///
+    /// 其他情况与 [`download`](Self::download) 一致，需要压缩后内容的场景请使用 `download`
+    pub async fn download_decompressed(&self, client: &Client) -> Result<Vec<u8>, OssError> {
+        let response = self.download_response(client).await?;
+        let encoding = response
+            .headers()
+            .get(CONTENT_ENCODING)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_owned());
+        let content = response.bytes().await?;
+
+        match encoding {
+            None if client.decompress_by_ext() => decode_by_ext(&self.path, content.into()),
+            encoding => decode_content(encoding.as_deref(), content.into()),
+        }
+    }
+
+    /// 下载文件并保存到本地
+    pub async fn download_to_file<P: AsRef<Path>>(
+        &self,
+        path: P,
+        client: &Client,
+    ) -> Result<(), OssError> {
+        let content = self.download(client).await?;
+        fs::write(path, content)?;
+
+        Ok(())
+    }
+
+    /// 下载文件并保存到本地，本地文件已存在且只写入了一部分时（如上次下载中断），只下载剩余的
+    /// 部分并追加到文件末尾，不需要重新下载整个文件。本地文件比 OSS 中的文件大时会重新下载
+    ///
+    /// 下载完成后校验本地文件的大小，返回文件大小。续传前文件在 OSS 中被修改时无法检测到，
+    /// 需要时请自行对比 etag
+    pub async fn download_to_file_resume<P: AsRef<Path>>(
+        &self,
+        path: P,
+        client: &Client,
+    ) -> Result<u64, OssError> {
+        let path = path.as_ref();
+        let size = self.get_info(client).await?.size();
+        let existing = match fs::metadata(path) {
+            Ok(meta) => meta.len(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => 0,
+            Err(e) => return Err(e.into()),
+        };
+        let start = if existing > size { 0 } else { existing };
+
+        let content = if start < size {
+            self.download_content_range(start.., client).await?
+        } else {
+            Vec::new()
+        };
+        let actual = write_at(path, start, &content)?;
+        if actual != size {
+            return Err(OssError::SizeMismatch {
+                expected: size,
+                actual,
+            });
+        }
+
+        Ok(actual)
+    }
+
+    /// 下载文件并保存到本地，当本地路径没有扩展名时，会根据文件内容（magic bytes）或响应的
+    /// `Content-Type` 推断扩展名并追加到路径上，无法推断时保持原路径
+    ///
+    /// 返回实际写入的文件路径
+    pub async fn download_to_file_infer_ext<P: AsRef<Path>>(
+        &self,
+        path: P,
+        client: &Client,
+    ) -> Result<PathBuf, OssError> {
+        let response = self.download_response(client).await?;
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_owned());
+        let content = response.bytes().await?;
+
+        let path = with_inferred_ext(path.as_ref(), &content, content_type.as_deref());
+        fs::write(&path, content)?;
+
+        Ok(path)
+    }
+
+    /// 下载文件的指定字节范围，`start` 和 `end` 都包含在内
+    pub async fn download_range(
+        &self,
+        start: u64,
+        end: u64,
+        client: &Client,
+    ) -> Result<Vec<u8>, OssError> {
+        self.download_content_range(start..=end, client).await
+    }
+
+    /// 下载文件的指定字节范围，如 `10..=20`，`..=20`，`10..`，参见 [`ContentRange`]
+    pub async fn download_content_range<R: Into<ContentRange>>(
+        &self,
+        range: R,
+        client: &Client,
+    ) -> Result<Vec<u8>, OssError> {
+        let bucket = client.bucket().ok_or(OssError::NoFoundBucket)?;
+        let url = self.to_url(bucket);
+        let method = Method::GET;
+        let resource = CanonicalizedResource::from_object(bucket, self);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(RANGE, range.into().to_string().try_into()?);
+
+        let content = client
+            .send(method, url, resource, headers, None)
+            .await?
+            .bytes()
+            .await?;
+
+        Ok(content.into())
+    }
+
+    /// 带 `If-Range` 的范围下载，`if_range` 为之前获取的 etag 或 GMT 格式的修改时间，
+    /// 文件未改变时返回指定范围，已改变时 OSS 返回整个文件，适用于断点续传
+    pub async fn download_range_if<R: Into<ContentRange>>(
+        &self,
+        range: R,
+        if_range: &str,
+        client: &Client,
+    ) -> Result<RangeDownload, OssError> {
+        let bucket = client.bucket().ok_or(OssError::NoFoundBucket)?;
+        let url = self.to_url(bucket);
+        let resource = CanonicalizedResource::from_object(bucket, self);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(RANGE, range.into().to_string().try_into()?);
+        headers.insert(IF_RANGE, if_range.try_into()?);
+
+        let response = client
+            .send(Method::GET, url, resource, headers, None)
+            .await?;
+        let status = response.status();
+        let content = response.bytes().await?;
+
+        Ok(RangeDownload::new(status, content.into()))
+    }
+
+    /// 下载文件，根据文件大小自动选择下载方式
+    ///
+    /// 不超过 [`Client::download_part_size`] 的文件使用一次 GET 请求下载，更大的文件会按该大小
+    /// 分段，最多同时发送 [`DOWNLOAD_AUTO_CONCURRENCY`] 个 Range 请求，最后按顺序拼接
+    pub async fn download_auto(&self, client: &Client) -> Result<Vec<u8>, OssError> {
+        let size = self.get_info(client).await?.size();
+        let part_size = client.download_part_size();
+        if size <= part_size {
+            return self.download(client).await;
+        }
+
+        let parts = split_ranges(size, part_size)
+            .into_iter()
+            .map(|(start, end)| self.download_range(start, end, client));
+        let mut content = Vec::with_capacity(size as usize);
+        write_in_order(parts, DOWNLOAD_AUTO_CONCURRENCY, &mut content).await?;
+
+        Ok(content)
+    }
+
+    /// 按 `chunk_size` 分段并发下载文件，同时最多发送 `concurrency` 个 Range 请求，
+    /// 各段按顺序写入 `writer`，内存中最多缓存 `concurrency` 段，返回写入的字节数
+    pub async fn download_parallel_to_writer<W: AsyncWrite + Unpin>(
+        &self,
+        chunk_size: u64,
+        concurrency: usize,
+        writer: &mut W,
+        client: &Client,
+    ) -> Result<u64, OssError> {
+        if chunk_size == 0 {
+            return Err(OssError::InvalidPartSize);
+        }
+        let size = self.get_info(client).await?.size();
+        let parts = split_ranges(size, chunk_size)
+            .into_iter()
+            .map(|(start, end)| self.download_range(start, end, client));
+
+        write_in_order(parts, concurrency, writer).await
+    }
+
+    /// 与 [`download_auto`](Self::download_auto) 相同，下载后保存到本地
+    pub async fn download_auto_to_file<P: AsRef<Path>>(
+        &self,
+        path: P,
+        client: &Client,
+    ) -> Result<(), OssError> {
+        let content = self.download_auto(client).await?;
+        fs::write(path, content)?;
+
+        Ok(())
+    }
+
+    async fn download_response(&self, client: &Client) -> Result<Response, OssError> {
+        let bucket = client.bucket().ok_or(OssError::NoFoundBucket)?;
+        let url = self.to_url(bucket);
+        let method = Method::GET;
+        let resource = CanonicalizedResource::from_object(bucket, self);
+
+        client
+            .send(method, url, resource, HeaderMap::new(), None)
+            .await
+    }
+
+    /// 复制文件
+    pub async fn copy_from(&self, client: &Client, source: &Object) -> Result<(), OssError> {
+        self.copy_with_directive(client, source, MetadataDirective::Copy, HeaderMap::new())
+            .await
+    }
+
+    /// 复制文件，并指定元信息的处理方式
+    ///
+    /// 为 [`MetadataDirective::Replace`] 时，使用 `headers`（如 `Content-Type`，`x-oss-meta-*`）
+    /// 作为目标文件的元信息，复制到自身时可用于修改文件的元信息；为
+    /// [`MetadataDirective::Copy`] 时沿用源文件的元信息，`headers` 中只保留
+    /// `x-oss-copy-source-if-*` 条件 header
+    pub async fn copy_with_directive(
+        &self,
+        client: &Client,
+        source: &Object,
+        directive: MetadataDirective,
+        headers: HeaderMap,
+    ) -> Result<(), OssError> {
+        self.copy_with_storage_class(client, source, directive, None, headers)
+            .await
+    }
+
+    /// 复制文件，并指定目标文件的存储类型
+    ///
+    /// `storage_class` 为 `None` 时沿用 bucket 的默认存储类型，复制到自身并指定存储类型，
+    /// 可用于转换文件的存储类型，`directive` 与 `headers` 同 [`copy_with_directive`](Self::copy_with_directive)
+    pub async fn copy_with_storage_class(
+        &self,
+        client: &Client,
+        source: &Object,
+        directive: MetadataDirective,
+        storage_class: Option<StorageClass>,
+        headers: HeaderMap,
+    ) -> Result<(), OssError> {
+        let bucket = client.bucket().ok_or(OssError::NoFoundBucket)?;
+        let url = self.to_url(bucket);
+        let method = Method::PUT;
+        let resource = CanonicalizedResource::from_object(bucket, self);
+
+        let headers = copy_headers(source, directive, storage_class, headers)?;
+
+        client.send(method, url, resource, headers, None).await?;
+
+        Ok(())
+    }
+
+    /// 复制同一个 bucket 中的文件，源文件超过 [`MAX_COPY_SIZE`] 时自动使用分片复制，
+    /// 分片大小为 [`Client::part_size`]
+    ///
+    /// 与 [`copy_from`](Self::copy_from) 不同，`source` 为源文件在当前 bucket 中的路径，
+    /// 会先通过它获取源文件的大小
+    pub async fn copy_auto(&self, client: &Client, source: &Object) -> Result<(), OssError> {
+        let bucket = client.bucket().ok_or(OssError::NoFoundBucket)?;
+        let copy_source = Object::new(format!("/{}/{}", bucket.as_str(), source.path));
+
+        let size = source.get_info(client).await?.size();
+        let ranges = match copy_ranges(size, client.part_size()) {
+            Some(ranges) => ranges,
+            None => return self.copy_from(client, &copy_source).await,
+        };
+
+        let mut upload = MultipartUpload::init(self, HeaderMap::new(), client).await?;
+        for range in ranges {
+            if let Err(e) = upload.upload_part_copy(&copy_source, range, client).await {
+                let _ = upload.abort(client).await;
+                return Err(e);
+            }
+        }
+        upload.complete(client).await?;
+
+        Ok(())
+    }
+
+    /// 复制文件，只有源文件满足 `condition` 时才会复制，否则返回 `PreconditionFailed` 错误
+    pub async fn copy_with_condition(
+        &self,
+        client: &Client,
+        source: &Object,
+        condition: &CopyCondition,
+    ) -> Result<(), OssError> {
+        let mut headers = HeaderMap::new();
+        condition.apply(&mut headers)?;
+
+        self.copy_with_directive(client, source, MetadataDirective::Copy, headers)
+            .await
+    }
+
+    /// 删除文件
+    pub async fn delete(&self, client: &Client) -> Result<(), OssError> {
+        let bucket = client.bucket().ok_or(OssError::NoFoundBucket)?;
+        let url = self.to_url(bucket);
+        let method = Method::DELETE;
+        let resource = CanonicalizedResource::from_object(bucket, self);
+
+        client
+            .send(method, url, resource, HeaderMap::new(), None)
+            .await?;
+
+        Ok(())
+    }
+
+    /// 删除文件，同时取消该文件所有进行中的分片上传任务，返回取消的任务数量
+    ///
+    /// 先取消分片上传任务再删除文件，避免之后合并分片时重新创建该文件；只取消 key 与文件完全相同的任务
+    pub async fn delete_with_uploads(&self, client: &Client) -> Result<usize, OssError> {
+        let bucket = client.bucket().ok_or(OssError::NoFoundBucket)?;
+        let uploads = bucket.list_multipart_uploads(&self.path, client).await?;
+        let uploads = uploads_of(self, uploads);
+        let count = uploads.len();
+        for upload in uploads {
+            upload.abort(client).await?;
+        }
+        self.delete(client).await?;
+
+        Ok(count)
+    }
+
+    /// 删除文件，并返回删除结果
+    ///
+    /// 开启了版本控制的 bucket 中，不指定版本删除文件时不会真正删除，而是创建一个删除标记，
+    /// 此时返回的 [`DeleteOutcome`] 包含删除标记的 version id
+    pub async fn delete_versioned(&self, client: &Client) -> Result<DeleteOutcome, OssError> {
+        let bucket = client.bucket().ok_or(OssError::NoFoundBucket)?;
+        let url = self.to_url(bucket);
+        let method = Method::DELETE;
+        let resource = CanonicalizedResource::from_object(bucket, self);
+
+        let response = client
+            .send(method, url, resource, HeaderMap::new(), None)
+            .await?;
+
+        DeleteOutcome::from_headers(response.headers())
+    }
+
+    /// 解冻归档或冷归档类型的文件，`days` 为解冻后保持可读的天数
+    ///
+    /// 文件已解冻，正在解冻中或者不需要解冻时，不会返回错误，参见 [`RestoreStatus`]
+    pub async fn restore(&self, days: u32, client: &Client) -> Result<RestoreStatus, OssError> {
+        let bucket = client.bucket().ok_or(OssError::NoFoundBucket)?;
+        let mut url = self.to_url(bucket);
+        url.set_query(Some(RESTORE));
+        let method = Method::POST;
+        let resource =
+            CanonicalizedResource::new(format!("/{}/{}?{}", bucket.as_str(), self.path, RESTORE));
+        let body = format!("<RestoreRequest><Days>{}</Days></RestoreRequest>", days);
+
+        let res = client
+            .send(
+                method,
+                url,
+                resource,
+                HeaderMap::new(),
+                Some(body.into_bytes()),
+            )
+            .await;
+
+        restore_status(res)
+    }
+
+    /// 下载文件的指定版本，用于开启了版本控制的 bucket
+    pub async fn download_version(
+        &self,
+        version_id: &str,
+        client: &Client,
+    ) -> Result<Vec<u8>, OssError> {
+        let bucket = client.bucket().ok_or(OssError::NoFoundBucket)?;
+        let url = self.to_version_url(bucket, version_id);
+        let method = Method::GET;
+        let resource = CanonicalizedResource::from_object_version(bucket, self, version_id);
+
+        let content = client
+            .send(method, url, resource, HeaderMap::new(), None)
+            .await?
+            .bytes()
+            .await?;
+
+        Ok(content.into())
+    }
+
+    /// 删除文件的指定版本，用于开启了版本控制的 bucket，删除后无法恢复
+    pub async fn delete_version(&self, version_id: &str, client: &Client) -> Result<(), OssError> {
+        let bucket = client.bucket().ok_or(OssError::NoFoundBucket)?;
+        let url = self.to_version_url(bucket, version_id);
+        let method = Method::DELETE;
+        let resource = CanonicalizedResource::from_object_version(bucket, self, version_id);
+
+        client
+            .send(method, url, resource, HeaderMap::new(), None)
+            .await?;
+
+        Ok(())
+    }
+
+    /// 追加内容到可追加类型（Appendable）的文件末尾，`position` 为追加的起始位置，
+    /// 文件不存在时，使用 0 作为起始位置会创建该文件
+    ///
+    /// 返回下一次追加的起始位置
+    pub async fn append(
+        &self,
+        content: Vec<u8>,
+        position: u64,
+        client: &Client,
+    ) -> Result<u64, OssError> {
+        let response = self
+            .append_response(content, position, HeaderMap::new(), client)
+            .await?;
+
+        next_append_position(response.headers())
+    }
+
+    /// 追加内容，并校验追加后整个文件的 CRC64 值
+    ///
+    /// `crc` 为追加前文件的 CRC64 值（即上一次追加返回的值，新文件为 0），会通过
+    /// `x-oss-hash-crc64ecma` 传给 OSS，服务端返回的值与本地计算的不一致时返回
+    /// `Crc64Mismatch` 错误
+    ///
+    /// 返回下一次追加的起始位置和追加后文件的 CRC64 值
+    pub async fn append_with_crc(
+        &self,
+        content: Vec<u8>,
+        position: u64,
+        crc: u64,
+        client: &Client,
+    ) -> Result<(u64, u64), OssError> {
+        let expected = crc64_ecma(crc, &content);
+
+        let response = self
+            .append_response(
+                content,
+                position,
+                append_crc_headers(position, crc)?,
+                client,
+            )
+            .await?;
+
+        let headers = response.headers();
+        let actual = hash_crc64ecma(headers)?;
+        if actual != expected {
+            return Err(OssError::Crc64Mismatch);
+        }
+
+        Ok((next_append_position(headers)?, actual))
+    }
+
+    async fn append_response(
+        &self,
+        content: Vec<u8>,
+        position: u64,
+        headers: HeaderMap,
+        client: &Client,
+    ) -> Result<Response, OssError> {
+        let bucket = client.bucket().ok_or(OssError::NoFoundBucket)?;
+        let url = self.to_append_url(bucket, position);
+        let method = Method::POST;
+        let resource = CanonicalizedResource::new(format!(
+            "/{}/{}?append&position={}",
+            bucket.as_str(),
+            self.path,
+            position
+        ));
+
+        client
+            .send(method, url, resource, headers, Some(content))
+            .await
+    }
+
+    /// 获取可追加文件下一次追加的起始位置，文件不存在时返回 0
+    pub async fn append_position(&self, client: &Client) -> Result<u64, OssError> {
+        match self.append_head(client).await? {
+            Some(headers) => next_append_position(&headers),
+            None => Ok(0),
+        }
+    }
+
+    /// 获取可追加文件下一次追加的起始位置和当前的 CRC64 值，文件不存在时都为 0
+    async fn append_state(&self, client: &Client) -> Result<(u64, u64), OssError> {
+        match self.append_head(client).await? {
+            Some(headers) => Ok((next_append_position(&headers)?, hash_crc64ecma(&headers)?)),
+            None => Ok((0, 0)),
+        }
+    }
+
+    /// 读取可追加文件的响应 header，文件不存在时返回 `None`
+    async fn append_head(&self, client: &Client) -> Result<Option<HeaderMap>, OssError> {
+        let bucket = client.bucket().ok_or(OssError::NoFoundBucket)?;
+        let url = self.to_url(bucket);
+        let method = Method::HEAD;
+        let resource = CanonicalizedResource::from_object(bucket, self);
+
+        let response = client
+            .send_unchecked(method, url, resource, HeaderMap::new(), None)
+            .await?;
+
+        match response.status() {
+            StatusCode::NOT_FOUND => Ok(None),
+            status if status.is_success() => Ok(Some(response.headers().clone())),
+            status => Err(OssError::from_status(status, &response.text().await?)),
+        }
+    }
+
+    /// 追加一行 UTF-8 文本到可追加文件中，适合直接在 OSS 中记录简单的日志
+    ///
+    /// 会自动补全行尾的换行符，并先读取文件当前的长度作为追加位置，文件不存在时会自动创建
+    pub async fn append_line(&self, line: &str, client: &Client) -> Result<u64, OssError> {
+        let position = self.append_position(client).await?;
+
+        self.append(to_log_line(line), position, client).await
+    }
+
+    fn to_append_url(&self, bucket: &Bucket, position: u64) -> Url {
+        let mut url = self.to_url(bucket);
+        url.set_query(Some(&format!("append&position={}", position)));
+        url
+    }
+
+    fn to_version_url(&self, bucket: &Bucket, version_id: &str) -> Url {
+        let mut url = self.to_url(bucket);
+        url.query_pairs_mut().append_pair(VERSION_ID, version_id);
+        url
+    }
+}
+
+/// 随机读取 OSS 上的文件，`seek` 只修改本地的位置，每次 `read` 从当前位置发送一次 Range 请求
+#[derive(Debug, Clone)]
+pub struct ObjectReader {
+    object: Object,
+    size: u64,
+    position: u64,
+}
+
+impl ObjectReader {
+    /// 读取一次文件的大小，位置从 0 开始
+    pub async fn open(object: Object, client: &Client) -> Result<ObjectReader, OssError> {
+        let size = object.get_info(client).await?.size();
+
+        Ok(ObjectReader {
+            object,
+            size,
+            position: 0,
+        })
+    }
+
+    pub fn object(&self) -> &Object {
+        &self.object
+    }
+
+    /// 打开时文件的大小
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// 下一次读取的位置
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// 与 [`std::io::Seek`] 相同，可以移动到文件末尾之后（之后的读取返回 0），
+    /// 移动到负数位置时返回错误，返回新的位置
+    pub fn seek(&mut self, pos: SeekFrom) -> Result<u64, OssError> {
+        self.position = seek_position(self.position, self.size, pos)?;
+        Ok(self.position)
+    }
+
+    /// 从当前位置读取最多 `buf.len()` 字节，并前移位置，返回读取的字节数，
+    /// 到达文件末尾时返回 0
+    pub async fn read(&mut self, buf: &mut [u8], client: &Client) -> Result<usize, OssError> {
+        if buf.is_empty() || self.position >= self.size {
+            return Ok(0);
+        }
+        let end = (self.position + buf.len() as u64).min(self.size) - 1;
+        let content = self
+            .object
+            .download_range(self.position, end, client)
+            .await?;
+
+        let len = content.len().min(buf.len());
+        buf[..len].copy_from_slice(&content[..len]);
+        self.position += len as u64;
+
+        Ok(len)
+    }
+}
+
+fn seek_position(current: u64, size: u64, pos: SeekFrom) -> Result<u64, OssError> {
+    let (base, offset) = match pos {
+        SeekFrom::Start(n) => return Ok(n),
+        SeekFrom::End(n) => (size, n),
+        SeekFrom::Current(n) => (current, n),
+    };
+    base.checked_add_signed(offset).ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "invalid seek to a negative or overflowing position",
+        )
+        .into()
+    })
+}
+
+/// 绑定到一个可追加文件，缓存下一次追加的位置和 CRC64 值，连续追加时不需要每次都
+/// 读取文件的长度
+///
+/// 第一次写入时读取一次文件的状态（文件不存在时会创建），之后每次写入只有一次追加请求；
+/// 文件被其他程序追加导致位置不一致时，会重新读取文件的状态并重试一次
+#[derive(Debug, Clone)]
+pub struct Appender {
+    object: Object,
+    state: Option<(u64, u64)>,
+}
+
+impl Appender {
+    pub fn new(object: Object) -> Appender {
+        Appender {
+            object,
+            state: None,
+        }
+    }
+
+    /// 使用已知的追加位置和 CRC64 值（如上一次追加的结果）创建，第一次写入时不再读取文件状态
+    pub fn resume(object: Object, position: u64, crc: u64) -> Appender {
+        Appender {
+            object,
+            state: Some((position, crc)),
+        }
+    }
+
+    pub fn object(&self) -> &Object {
+        &self.object
+    }
+
+    /// 下一次追加的位置，还没有读取文件状态时为 `None`
+    pub fn position(&self) -> Option<u64> {
+        self.state.map(|(position, _)| position)
+    }
+
+    /// 当前文件的 CRC64 值，还没有读取文件状态时为 `None`
+    pub fn crc64(&self) -> Option<u64> {
+        self.state.map(|(_, crc)| crc)
+    }
+
+    /// 追加内容，并返回下一次追加的位置
+    pub async fn write(&mut self, content: &[u8], client: &Client) -> Result<u64, OssError> {
+        let (position, crc) = match self.state {
+            Some(state) => state,
+            None => self.object.append_state(client).await?,
+        };
+
+        let result = match self
+            .object
+            .append_with_crc(content.to_vec(), position, crc, client)
+            .await
+        {
+            Err(OssError::Service(e)) if e.code() == POSITION_NOT_EQUAL_TO_LENGTH => {
+                let (position, crc) = self.object.append_state(client).await?;
+                self.object
+                    .append_with_crc(content.to_vec(), position, crc, client)
+                    .await
+            }
+            result => result,
+        };
+
+        match result {
+            Ok(state) => {
+                self.state = Some(state);
+                Ok(state.0)
+            }
+            Err(e) => {
+                self.state = None;
+                Err(e)
+            }
+        }
+    }
+}
+
+const POSITION_NOT_EQUAL_TO_LENGTH: &str = "PositionNotEqualToLength";
+
+const RESTORE: &str = "restore";
+
+const SYMLINK: &str = "symlink";
+
+/// 从响应 header 中解析软链接的目标文件，目标路径是经过 url 编码的，指向其他 bucket 时
+/// 目标为 `/{bucket}/{path}`，同时返回 bucket 名称
+fn symlink_target(headers: &HeaderMap) -> Result<(Option<String>, Object), OssError> {
+    let target = headers
+        .get("x-oss-symlink-target")
+        .ok_or(OssError::NoFoundSymlinkTarget)?
+        .to_str()?;
+
+    let (bucket, path) = match target
+        .strip_prefix('/')
+        .and_then(|rest| rest.split_once('/'))
+    {
+        Some((bucket, path)) => (Some(bucket.to_owned()), path),
+        None => (None, target),
+    };
+
+    Ok((
+        bucket,
+        Object::new(percent_decode_str(path).decode_utf8_lossy()),
+    ))
+}
+
+/// 解冻请求的结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestoreStatus {
+    /// 已提交解冻请求（202）
+    Started,
+    /// 文件已解冻，解冻的有效期会被延长（200）
+    AlreadyRestored,
+    /// 文件正在解冻中（409）
+    InProgress,
+    /// 文件不是归档或冷归档类型，不需要解冻
+    NotArchived,
+}
+
+fn restore_status(res: Result<Response, OssError>) -> Result<RestoreStatus, OssError> {
+    match res {
+        Ok(response) if response.status() == StatusCode::ACCEPTED => Ok(RestoreStatus::Started),
+        Ok(_) => Ok(RestoreStatus::AlreadyRestored),
+        Err(OssError::Service(xml)) if xml.code() == "RestoreAlreadyInProgress" => {
+            Ok(RestoreStatus::InProgress)
+        }
+        Err(OssError::Service(xml)) if xml.code() == "OperationNotSupported" => {
+            Ok(RestoreStatus::NotArchived)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// 内容大小超过 `threshold` 时，返回每个分片在内容中的范围，不需要分片上传时返回 `None`
+fn multipart_ranges(len: usize, threshold: u64, part_size: u64) -> Option<Vec<Range<usize>>> {
+    if len as u64 <= threshold {
+        return None;
+    }
+    let part_size = part_size as usize;
+
+    Some(
+        (0..len)
+            .step_by(part_size)
+            .map(|start| start..len.min(start + part_size))
+            .collect(),
+    )
+}
+
+/// 上传长度未知的内容时，每上传这么多个分片，分片大小翻倍
+pub const PARTS_PER_SIZE_STEP: u32 = 1000;
+
+/// 上传长度未知的内容时，第 `part_number` 个分片（从 1 开始）的大小
+fn stream_part_size(base: u64, part_number: u32) -> u64 {
+    let step = part_number.saturating_sub(1) / PARTS_PER_SIZE_STEP;
+    base.saturating_mul(1 << step.min(MAX_PARTS / PARTS_PER_SIZE_STEP))
+        .min(MAX_PART_SIZE)
+}
+
+/// 从 reader 中读取最多 `size` 个字节，只有读到末尾时才会少于 `size`
+///
+/// 缓存随读到的内容增长，不会预先分配整个分片的大小
+async fn read_part<R: AsyncRead + Unpin>(reader: &mut R, size: usize) -> std::io::Result<Vec<u8>> {
+    let mut part = Vec::new();
+    reader.take(size as u64).read_to_end(&mut part).await?;
+    Ok(part)
+}
+
+const COPY_SOURCE_IF: &str = "x-oss-copy-source-if-";
+
+/// 复制文件时对源文件的条件限制，条件不满足时 OSS 返回 412
+/// ```
+/// # use aliyun_oss_client::object::CopyCondition;
+/// let condition = CopyCondition::new().if_match("\"5B3C1A2E053D763E1B002CC607C5A0FE\"");
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CopyCondition {
+    if_match: Option<String>,
+    if_none_match: Option<String>,
+    if_modified_since: Option<DateTime<Utc>>,
+    if_unmodified_since: Option<DateTime<Utc>>,
+}
+
+impl CopyCondition {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 源文件的 etag 与之相同时才复制
+    pub fn if_match<E: Into<String>>(mut self, etag: E) -> Self {
+        self.if_match = Some(etag.into());
+        self
+    }
+
+    /// 源文件的 etag 与之不同时才复制
+    pub fn if_none_match<E: Into<String>>(mut self, etag: E) -> Self {
+        self.if_none_match = Some(etag.into());
+        self
+    }
+
+    /// 源文件在该时间之后修改过才复制
+    pub fn if_modified_since(mut self, time: DateTime<Utc>) -> Self {
+        self.if_modified_since = Some(time);
+        self
+    }
+
+    /// 源文件在该时间之后没有修改过才复制
+    pub fn if_unmodified_since(mut self, time: DateTime<Utc>) -> Self {
+        self.if_unmodified_since = Some(time);
+        self
+    }
+
+    fn apply(&self, headers: &mut HeaderMap) -> Result<(), OssError> {
+        if let Some(etag) = &self.if_match {
+            headers.insert("x-oss-copy-source-if-match", etag.try_into()?);
+        }
+        if let Some(etag) = &self.if_none_match {
+            headers.insert("x-oss-copy-source-if-none-match", etag.try_into()?);
+        }
+        if let Some(time) = &self.if_modified_since {
+            headers.insert(
+                "x-oss-copy-source-if-modified-since",
+                to_gmt(time).try_into()?,
+            );
+        }
+        if let Some(time) = &self.if_unmodified_since {
+            headers.insert(
+                "x-oss-copy-source-if-unmodified-since",
+                to_gmt(time).try_into()?,
+            );
+        }
+        Ok(())
+    }
+}
+
+/// 常用的缓存策略，上传时作为 `Cache-Control` 的值
+/// ```
+/// # use aliyun_oss_client::object::CacheControl;
+/// assert_eq!(CacheControl::Public { max_age: 3600 }.to_string(), "public, max-age=3600");
+/// assert_eq!(CacheControl::NoStore.to_string(), "no-store");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheControl {
+    /// 允许 CDN 等共享缓存保存 `max_age` 秒
+    Public { max_age: u64 },
+    /// 只允许浏览器缓存 `max_age` 秒
+    Private { max_age: u64 },
+    /// 可以缓存，但每次使用前都需要向源站确认
+    NoCache,
+    /// 不允许缓存
+    NoStore,
+}
+
+impl fmt::Display for CacheControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CacheControl::Public { max_age } => write!(f, "public, max-age={}", max_age),
+            CacheControl::Private { max_age } => write!(f, "private, max-age={}", max_age),
+            CacheControl::NoCache => f.write_str("no-cache"),
+            CacheControl::NoStore => f.write_str("no-store"),
+        }
+    }
+}
+
+/// 复制文件时，目标文件元信息的处理方式
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MetadataDirective {
+    /// 沿用源文件的元信息
+    #[default]
+    Copy,
+    /// 使用请求中指定的元信息
+    Replace,
+}
+
+impl AsRef<str> for MetadataDirective {
+    fn as_ref(&self) -> &str {
+        match self {
+            MetadataDirective::Copy => "COPY",
+            MetadataDirective::Replace => "REPLACE",
+        }
+    }
+}
+
+/// 上传文件时校验内容完整性的方式
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumAlgorithm {
+    /// 使用响应中的 `x-oss-hash-crc64ecma` 与本地计算的 CRC64 比较，
+    /// 不一致时返回 `Crc64Mismatch` 错误
+    #[default]
+    Crc64,
+    /// 发送 `Content-MD5`，由 OSS 校验，不一致时拒绝上传
+    Md5,
+    /// 不校验
+    None,
+}
+
+impl ChecksumAlgorithm {
+    /// 上传请求需要额外携带的 header
+    fn headers(self, content: &[u8]) -> Result<HeaderMap, OssError> {
+        let mut headers = HeaderMap::new();
+        if self == ChecksumAlgorithm::Md5 {
+            headers.insert(
+                CONTENT_MD5,
+                ContentMd5::from_bytes(content).as_str().try_into()?,
+            );
+        }
+        Ok(headers)
+    }
+}
+
+fn verify_crc64(crc: u64, result: &UploadResult) -> Result<(), OssError> {
+    match result.crc64() {
+        Some(actual) if actual == crc => Ok(()),
+        Some(_) => Err(OssError::Crc64Mismatch),
+        None => Err(OssError::NoFoundCrc64),
+    }
+}
+
+fn copy_headers(
+    source: &Object,
+    directive: MetadataDirective,
+    storage_class: Option<StorageClass>,
+    headers: HeaderMap,
+) -> Result<HeaderMap, OssError> {
+    let mut headers = match directive {
+        MetadataDirective::Copy => headers
+            .into_iter()
+            .filter_map(|(k, v)| k.map(|k| (k, v)))
+            .filter(|(k, _)| k.as_str().starts_with(COPY_SOURCE_IF))
+            .collect(),
+        MetadataDirective::Replace => headers,
+    };
+    headers.insert("x-oss-copy-source", source.path.as_str().try_into()?);
+    headers.insert("x-oss-metadata-directive", directive.as_ref().try_into()?);
+    if let Some(storage_class) = storage_class {
+        headers.insert(STORAGE_CLASS, storage_class.as_ref().try_into()?);
+    }
+
+    Ok(headers)
+}
+
+const VERSION_ID: &str = "versionId";
+
+/// 按前缀列举出的分片上传任务中，只保留目标文件为 `object` 的
+fn uploads_of(object: &Object, uploads: Vec<MultipartUpload>) -> Vec<MultipartUpload> {
+    uploads
+        .into_iter()
+        .filter(|upload| upload.object() == object)
+        .collect()
+}
+
+/// 简单复制支持的最大文件大小，超过该大小需要使用分片复制
+pub const MAX_COPY_SIZE: u64 = 1024 * 1024 * 1024;
+
+/// 文件大小超过 [`MAX_COPY_SIZE`] 时，返回分片复制的各个范围
+fn copy_ranges(size: u64, part_size: u64) -> Option<Vec<(u64, u64)>> {
+    if size > MAX_COPY_SIZE {
+        Some(split_ranges(size, part_size))
+    } else {
+        None
+    }
+}
+
+/// 分段下载时，每一段的默认大小，不超过该大小的文件不分段
+pub const DOWNLOAD_PART_SIZE: u64 = 8 * 1024 * 1024;
+
+/// [`Object::download_auto`] 同时发送的 Range 请求数量上限
+pub const DOWNLOAD_AUTO_CONCURRENCY: usize = 8;
+
+/// [`Object::download_range_if`] 的结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RangeDownload {
+    /// 文件未改变，返回了指定范围的内容（206）
+    Partial(Vec<u8>),
+    /// 文件已改变，返回了整个文件（200），之前下载的内容需要丢弃
+    Full(Vec<u8>),
+}
+
+impl RangeDownload {
+    fn new(status: StatusCode, content: Vec<u8>) -> Self {
+        if status == StatusCode::PARTIAL_CONTENT {
+            RangeDownload::Partial(content)
+        } else {
+            RangeDownload::Full(content)
+        }
+    }
+
+    pub fn is_partial(&self) -> bool {
+        matches!(self, RangeDownload::Partial(_))
+    }
+
+    pub fn into_content(self) -> Vec<u8> {
+        match self {
+            RangeDownload::Partial(content) | RangeDownload::Full(content) => content,
+        }
+    }
+}
+
+/// 将 `0..size` 按 `part_size` 切分为多个闭区间
+fn split_ranges(size: u64, part_size: u64) -> Vec<(u64, u64)> {
+    (0..size)
+        .step_by(part_size as usize)
+        .map(|start| (start, (start + part_size).min(size) - 1))
+        .collect()
+}
+
+/// 并发执行 `parts`，先完成的段会等待前面的段，按原有顺序写入 `writer`
+async fn write_in_order<I, F, W>(
+    parts: I,
+    concurrency: usize,
+    writer: &mut W,
+) -> Result<u64, OssError>
+where
+    I: IntoIterator<Item = F>,
+    F: Future<Output = Result<Vec<u8>, OssError>>,
+    W: AsyncWrite + Unpin,
+{
+    let mut parts = stream::iter(parts).buffered(concurrency.max(1));
+    let mut written = 0;
+    while let Some(part) = parts.try_next().await? {
+        writer.write_all(&part).await?;
+        written += part.len() as u64;
+    }
+    writer.flush().await?;
+
+    Ok(written)
+}
+
+const NEXT_APPEND_POSITION: &str = "x-oss-next-append-position";
+
+fn next_append_position(headers: &HeaderMap) -> Result<u64, OssError> {
+    Ok(headers
+        .get(NEXT_APPEND_POSITION)
+        .ok_or(OssError::NoFoundNextAppendPosition)?
+        .to_str()?
+        .parse()?)
+}
+
+const HASH_CRC64ECMA: &str = "x-oss-hash-crc64ecma";
+
+fn hash_crc64ecma(headers: &HeaderMap) -> Result<u64, OssError> {
+    Ok(headers
+        .get(HASH_CRC64ECMA)
+        .ok_or(OssError::NoFoundCrc64)?
+        .to_str()?
+        .parse()?)
+}
+
+/// 追加到已有内容之后时，携带追加前文件的 CRC64 值
+fn append_crc_headers(position: u64, crc: u64) -> Result<HeaderMap, OssError> {
+    let mut headers = HeaderMap::new();
+    if position > 0 {
+        headers.insert(HASH_CRC64ECMA, crc.to_string().try_into()?);
+    }
+    Ok(headers)
+}
+
+/// OSS 使用的 CRC64（ECMA-182），`crc` 为已有内容的 CRC64 值，可以分段计算
+fn crc64_ecma(crc: u64, data: &[u8]) -> u64 {
+    const POLY: u64 = 0xC96C_5795_D787_0F42;
+    const TABLE: [u64; 256] = {
+        let mut table = [0u64; 256];
+        let mut i = 0;
+        while i < 256 {
+            let mut value = i as u64;
+            let mut bit = 0;
+            while bit < 8 {
+                value = if value & 1 == 1 {
+                    (value >> 1) ^ POLY
+                } else {
+                    value >> 1
+                };
+                bit += 1;
+            }
+            table[i] = value;
+            i += 1;
+        }
+        table
+    };
+
+    let mut crc = !crc;
+    for byte in data {
+        crc = TABLE[((crc ^ *byte as u64) & 0xff) as usize] ^ (crc >> 8);
+    }
+    !crc
+}
+
+fn to_log_line(line: &str) -> Vec<u8> {
+    let mut content = line.as_bytes().to_vec();
+    if !line.ends_with('\n') {
+        content.push(b'\n');
+    }
+    content
+}
+
+/// 对象的一个历史版本
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectVersion {
+    path: String,
+    version_id: String,
+    is_latest: bool,
+    last_modified: DateTime<Utc>,
+    etag: String,
+    size: u64,
+}
+
+impl ObjectVersion {
+    pub fn new(
+        path: String,
+        version_id: String,
+        is_latest: bool,
+        last_modified: DateTime<Utc>,
+        etag: String,
+        size: u64,
+    ) -> Self {
+        ObjectVersion {
+            path,
+            version_id,
+            is_latest,
+            last_modified,
+            etag,
+            size,
+        }
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn version_id(&self) -> &str {
+        &self.version_id
+    }
+
+    pub fn is_latest(&self) -> bool {
+        self.is_latest
+    }
+
+    pub fn last_modified(&self) -> &DateTime<Utc> {
+        &self.last_modified
+    }
+
+    pub fn etag(&self) -> &str {
+        &self.etag
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+/// 对象的删除标记
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeleteMarker {
+    path: String,
+    version_id: String,
+    is_latest: bool,
+    last_modified: DateTime<Utc>,
+}
+
+impl DeleteMarker {
+    pub fn new(
+        path: String,
+        version_id: String,
+        is_latest: bool,
+        last_modified: DateTime<Utc>,
+    ) -> Self {
+        DeleteMarker {
+            path,
+            version_id,
+            is_latest,
+            last_modified,
+        }
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn version_id(&self) -> &str {
+        &self.version_id
+    }
+
+    pub fn is_latest(&self) -> bool {
+        self.is_latest
+    }
+
+    pub fn last_modified(&self) -> &DateTime<Utc> {
+        &self.last_modified
+    }
+}
+
+/// 一页对象版本列表
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ObjectVersions {
+    versions: Vec<ObjectVersion>,
+    delete_markers: Vec<DeleteMarker>,
+    next_key_marker: Option<String>,
+    next_version_id_marker: Option<String>,
+}
+
+impl ObjectVersions {
+    pub fn new(
+        versions: Vec<ObjectVersion>,
+        delete_markers: Vec<DeleteMarker>,
+        next_key_marker: Option<String>,
+        next_version_id_marker: Option<String>,
+    ) -> Self {
+        ObjectVersions {
+            versions,
+            delete_markers,
+            next_key_marker,
+            next_version_id_marker,
+        }
+    }
+
+    pub fn versions(&self) -> &[ObjectVersion] {
+        &self.versions
+    }
+
+    pub fn delete_markers(&self) -> &[DeleteMarker] {
+        &self.delete_markers
+    }
+
+    /// 获取下一页时作为 `key-marker` 使用，没有下一页时为 None
+    pub fn next_key_marker(&self) -> Option<&str> {
+        self.next_key_marker.as_deref()
+    }
+
+    /// 获取下一页时作为 `version-id-marker` 使用，没有下一页时为 None
+    pub fn next_version_id_marker(&self) -> Option<&str> {
+        self.next_version_id_marker.as_deref()
+    }
+
+    /// 取出当前页的所有版本和删除标记，先返回版本，再返回删除标记
+    pub fn into_entries(self) -> Vec<VersionEntry> {
+        self.versions
+            .into_iter()
+            .map(VersionEntry::Version)
+            .chain(
+                self.delete_markers
+                    .into_iter()
+                    .map(VersionEntry::DeleteMarker),
+            )
+            .collect()
+    }
+
+    /// 获取下一页时使用的查询条件，在当前页的查询条件上替换 `key-marker` 和
+    /// `version-id-marker`，没有下一页时返回 `None`
+    pub(crate) fn next_query(&self, query: &ObjectQuery) -> Option<ObjectQuery> {
+        let key_marker = self.next_key_marker.clone()?;
+        let mut query = query.clone();
+        query.insert(ObjectQuery::KEY_MARKER, key_marker);
+        if let Some(marker) = &self.next_version_id_marker {
+            query.insert(ObjectQuery::VERSION_ID_MARKER, marker.clone());
+        }
+        Some(query)
+    }
+}
+
+/// 对象版本列表中的一项，可能是一个版本，也可能是一个删除标记
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionEntry {
+    Version(ObjectVersion),
+    DeleteMarker(DeleteMarker),
+}
+
+impl VersionEntry {
+    pub fn path(&self) -> &str {
+        match self {
+            VersionEntry::Version(v) => v.path(),
+            VersionEntry::DeleteMarker(m) => m.path(),
+        }
+    }
+
+    pub fn version_id(&self) -> &str {
+        match self {
+            VersionEntry::Version(v) => v.version_id(),
+            VersionEntry::DeleteMarker(m) => m.version_id(),
+        }
+    }
+
+    pub fn is_latest(&self) -> bool {
+        match self {
+            VersionEntry::Version(v) => v.is_latest(),
+            VersionEntry::DeleteMarker(m) => m.is_latest(),
+        }
+    }
+
+    pub fn is_delete_marker(&self) -> bool {
+        matches!(self, VersionEntry::DeleteMarker(_))
+    }
+}
+
+/// 除 `-_.~` 以外的字符都需要编码
+const TAGGING_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+/// 文件路径中除 `-_.~/` 以外的字符都需要编码
+const PATH_ENCODE_SET: &AsciiSet = &TAGGING_ENCODE_SET.remove(b'/');
+
+/// 生成 `x-oss-tagging` 的值，形如 `k1=v1&k2=v2`，key 和 value 都会经过 url 编码
+///
+/// ```
+/// # use aliyun_oss_client::object::tagging_header;
+/// let value = tagging_header(&[("k1", "v1"), ("k2", "a b")]).unwrap();
+/// assert_eq!(value, "k1=v1&k2=a%20b");
+///
+/// assert!(tagging_header::<&str, &str>(&[]).is_err());
+/// ```
+pub fn tagging_header<K: AsRef<str>, V: AsRef<str>>(
+    tags: &[(K, V)],
+) -> Result<HeaderValue, OssError> {
+    const MAX_TAGS: usize = 10;
+    const MAX_KEY_LEN: usize = 128;
+    const MAX_VALUE_LEN: usize = 256;
+
+    if tags.is_empty() || tags.len() > MAX_TAGS {
+        return Err(OssError::InvalidTagging);
+    }
+
+    let mut list = Vec::with_capacity(tags.len());
+    for (key, value) in tags {
+        let (key, value) = (key.as_ref(), value.as_ref());
+        if key.is_empty()
+            || key.chars().count() > MAX_KEY_LEN
+            || value.chars().count() > MAX_VALUE_LEN
+        {
+            return Err(OssError::InvalidTagging);
+        }
+        list.push(format!(
+            "{}={}",
+            utf8_percent_encode(key, TAGGING_ENCODE_SET),
+            utf8_percent_encode(value, TAGGING_ENCODE_SET)
+        ));
+    }
+
+    Ok(list.join("&").try_into()?)
+}
+
+/// OSS 文件路径的长度限制
+const MAX_PATH_LEN: usize = 1023;
+
+fn is_valid_path(path: &str) -> bool {
+    !path.is_empty() && path.len() <= MAX_PATH_LEN && !path.starts_with('\\')
+}
+
+/// 根据 `Content-Encoding` 解压内容，目前支持 gzip，其他编码原样返回
+pub(crate) fn decode_content(
+    encoding: Option<&str>,
+    content: Vec<u8>,
+) -> Result<Vec<u8>, OssError> {
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+
+    match encoding {
+        Some(encoding) if encoding.trim().eq_ignore_ascii_case("gzip") => {
+            let mut decoded = Vec::new();
+            GzDecoder::new(content.as_slice()).read_to_end(&mut decoded)?;
+            Ok(decoded)
+        }
+        _ => Ok(content),
+    }
+}
+
+/// 根据文件扩展名解压内容，目前支持 `.gz` 和 `.zst`（需要开启 `zstd` feature），其他文件原样返回
+fn decode_by_ext(path: &str, content: Vec<u8>) -> Result<Vec<u8>, OssError> {
+    let ext = Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase());
+
+    match ext.as_deref() {
+        Some("gz") => decode_content(Some("gzip"), content),
+        #[cfg(feature = "zstd")]
+        Some("zst") => Ok(zstd::decode_all(content.as_slice())?),
+        _ => Ok(content),
+    }
+}
+
+/// 保留文件的前 `start` 个字节，并在之后写入 `content`，文件不存在时创建，返回写入后的文件大小
+fn write_at(path: &Path, start: u64, content: &[u8]) -> std::io::Result<u64> {
+    use std::io::{Seek, SeekFrom, Write};
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(path)?;
+    file.set_len(start)?;
+    file.seek(SeekFrom::Start(start))?;
+    file.write_all(content)?;
+
+    Ok(file.metadata()?.len())
+}
+
+/// 路径已有扩展名时原样返回，否则根据文件内容或 `Content-Type` 追加推断出的扩展名
+fn with_inferred_ext(path: &Path, content: &[u8], content_type: Option<&str>) -> PathBuf {
+    if path.extension().is_some() {
+        return path.to_path_buf();
+    }
+
+    let ext = infer::get(content)
+        .map(|kind| kind.extension())
+        .or_else(|| content_type.and_then(ext_from_content_type));
+
+    match ext {
+        Some(ext) => path.with_extension(ext),
+        None => path.to_path_buf(),
+    }
+}
+
+/// 扩展名与 `Content-Type` 的对应关系，同一个扩展名或 `Content-Type` 出现多次时，
+/// 以第一个为准，后面的只用于反向查找
+const MIME_TYPES: &[(&str, &str)] = &[
+    ("txt", "text/plain"),
+    ("log", "text/plain"),
+    ("html", "text/html"),
+    ("htm", "text/html"),
+    ("css", "text/css"),
+    ("csv", "text/csv"),
+    ("xml", "application/xml"),
+    ("xml", "text/xml"),
+    ("js", "application/javascript"),
+    ("js", "text/javascript"),
+    ("json", "application/json"),
+    ("pdf", "application/pdf"),
+    ("zip", "application/zip"),
+    ("gz", "application/gzip"),
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("gif", "image/gif"),
+    ("webp", "image/webp"),
+    ("svg", "image/svg+xml"),
+    ("ico", "image/x-icon"),
+    ("mp3", "audio/mpeg"),
+    ("mp4", "video/mp4"),
+];
+
+fn ext_from_content_type(content_type: &str) -> Option<&'static str> {
+    let mime = content_type.split(';').next()?.trim();
+    MIME_TYPES
+        .iter()
+        .find(|(_, m)| m.eq_ignore_ascii_case(mime))
+        .map(|(ext, _)| *ext)
+}
+
+/// 根据路径的扩展名推断 `Content-Type`
+fn content_type_from_path(path: &str) -> &'static str {
+    path.rsplit('/')
+        .next()
+        .and_then(|name| name.rsplit_once('.'))
+        .and_then(|(_, ext)| MIME_TYPES.iter().find(|(e, _)| e.eq_ignore_ascii_case(ext)))
+        .map_or("application/octet-stream", |(_, mime)| *mime)
+}
+
+#[derive(Debug)]
+pub struct ObjectInfo {
+    last_modified: DateTime<Utc>,
     etag: String,
     size: u64,
+    acl: Option<Grant>,
+    version_id: Option<String>,
+    storage_class: Option<StorageClass>,
+    content_type: Option<String>,
+    owner: Option<Owner>,
+    user_metadata: HashMap<String, String>,
 }
+
+/// 用户自定义元信息的 header 前缀
+const USER_META_PREFIX: &str = "x-oss-meta-";
+
+/// 文件存储类型的 header
+const STORAGE_CLASS: &str = "x-oss-storage-class";
+
 impl ObjectInfo {
     pub fn new(last_modified: DateTime<Utc>, etag: String, size: u64) -> Self {
         ObjectInfo {
             last_modified,
             etag,
             size,
+            acl: None,
+            version_id: None,
+            storage_class: None,
+            content_type: None,
+            owner: None,
+            user_metadata: HashMap::new(),
+        }
+    }
+
+    /// 从 meta 信息接口的响应 header 中解析
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Result<Self, OssError> {
+        let content_length = headers
+            .get(CONTENT_LENGTH)
+            .ok_or(OssError::NoFoundContentLength)?;
+        let etag = headers.get("etag").ok_or(OssError::NoFoundEtag)?;
+
+        let date = DateTime::parse_from_rfc2822(
+            headers
+                .get("last-modified")
+                .ok_or(OssError::NoFoundLastModified)?
+                .to_str()?,
+        )?;
+        let acl = match headers.get("x-oss-object-acl") {
+            Some(v) => v.to_str()?.parse().ok(),
+            None => None,
+        };
+        let version_id = match headers.get("x-oss-version-id") {
+            Some(v) => Some(v.to_str()?.to_owned()),
+            None => None,
+        };
+        let content_type = match headers.get(CONTENT_TYPE) {
+            Some(v) => Some(v.to_str()?.to_owned()),
+            None => None,
+        };
+        let storage_class = match headers.get(STORAGE_CLASS) {
+            Some(v) => StorageClass::new(v.to_str()?),
+            None => None,
+        };
+        // 用户元信息可能包含非 ASCII 的内容，按 UTF-8 解码，不能解码的部分会被替换
+        let mut user_metadata = HashMap::new();
+        for (name, value) in headers {
+            if let Some(key) = name.as_str().strip_prefix(USER_META_PREFIX) {
+                let value = String::from_utf8_lossy(value.as_bytes()).into_owned();
+                user_metadata.insert(key.to_owned(), value);
+            }
+        }
+
+        Ok(ObjectInfo {
+            last_modified: date.with_timezone(&Utc),
+            etag: etag.to_str()?.to_string(),
+            size: content_length.to_str()?.parse()?,
+            acl,
+            version_id,
+            storage_class,
+            content_type,
+            owner: None,
+            user_metadata,
+        })
+    }
+
+    pub fn last_modified(&self) -> &DateTime<Utc> {
+        &self.last_modified
+    }
+
+    /// 解析列举文件结果中 ISO 8601 格式的修改时间，如 `2022-06-26T09:53:21.000Z`，
+    /// 格式不正确时返回带有原始内容的 `InvalidLastModified` 错误
+    /// ```
+    /// # use aliyun_oss_client::ObjectInfo;
+    /// use chrono::{TimeZone, Utc};
+    /// let time = ObjectInfo::parse_last_modified("2022-06-26T09:53:21.000Z").unwrap();
+    /// assert_eq!(time, Utc.with_ymd_and_hms(2022, 6, 26, 9, 53, 21).unwrap());
+    /// ```
+    pub fn parse_last_modified(s: &str) -> Result<DateTime<Utc>, OssError> {
+        s.parse()
+            .map_err(|_| OssError::InvalidLastModified(s.into()))
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn etag(&self) -> &str {
+        &self.etag
+    }
+
+    /// 对象的访问权限，响应中没有返回，或者为 `default`（继承 bucket 的权限）时为 `None`
+    pub fn acl(&self) -> Option<Grant> {
+        self.acl
+    }
+
+    /// 只有开启了版本控制的 bucket 才会返回
+    pub fn version_id(&self) -> Option<&str> {
+        self.version_id.as_deref()
+    }
+
+    /// 只有通过列举文件接口获取的信息才包含存储类型
+    pub fn storage_class(&self) -> Option<StorageClass> {
+        self.storage_class
+    }
+
+    /// 只有下载文件时的响应才包含 `Content-Type`
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    /// 只有通过 [`Bucket::list_objects_with_owner`] 获取的信息才包含拥有者
+    pub fn owner(&self) -> Option<&Owner> {
+        self.owner.as_ref()
+    }
+
+    /// 上传时设置的 `x-oss-meta-*` 元信息，key 不含 `x-oss-meta-` 前缀，
+    /// 列举文件接口获取的信息中为空
+    pub fn user_metadata(&self) -> &HashMap<String, String> {
+        &self.user_metadata
+    }
+
+    /// `object` 为大小为 0 且以 `/` 结尾的目录占位文件，文件浏览器可以将其显示为目录
+    pub fn is_directory_marker(&self, object: &Object) -> bool {
+        self.size == 0 && object.is_dir()
+    }
+
+    pub(crate) fn set_storage_class(&mut self, storage_class: Option<StorageClass>) {
+        self.storage_class = storage_class;
+    }
+
+    pub(crate) fn set_owner(&mut self, owner: Option<Owner>) {
+        self.owner = owner;
+    }
+}
+
+/// 上传文件后，OSS 返回的对象信息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadResult {
+    etag: String,
+    version_id: Option<String>,
+    crc64: Option<u64>,
+    stats: Option<UploadStats>,
+}
+
+impl UploadResult {
+    pub fn new(etag: String, version_id: Option<String>, crc64: Option<u64>) -> Self {
+        UploadResult {
+            etag,
+            version_id,
+            crc64,
+            stats: None,
+        }
+    }
+
+    /// 从上传接口的响应 header 中解析
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Result<Self, OssError> {
+        let etag = headers
+            .get("etag")
+            .ok_or(OssError::NoFoundEtag)?
+            .to_str()?
+            .to_owned();
+        let version_id = match headers.get("x-oss-version-id") {
+            Some(v) => Some(v.to_str()?.to_owned()),
+            None => None,
+        };
+        let crc64 = match headers.get("x-oss-hash-crc64ecma") {
+            Some(v) => Some(v.to_str()?.parse()?),
+            None => None,
+        };
+
+        Ok(UploadResult {
+            etag,
+            version_id,
+            crc64,
+            stats: None,
+        })
+    }
+
+    pub fn etag(&self) -> &str {
+        &self.etag
+    }
+
+    /// 只有开启了版本控制的 bucket 才会返回
+    pub fn version_id(&self) -> Option<&str> {
+        self.version_id.as_deref()
+    }
+
+    pub fn crc64(&self) -> Option<u64> {
+        self.crc64
+    }
+
+    /// 只有通过 [`Object::upload_with_stats`] 上传时才有统计信息
+    pub fn stats(&self) -> Option<&UploadStats> {
+        self.stats.as_ref()
+    }
+}
+
+/// 上传的字节数和耗时，可用于调整分片大小和并发数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UploadStats {
+    bytes: u64,
+    duration: Duration,
+}
+
+impl UploadStats {
+    pub fn new(bytes: u64, duration: Duration) -> Self {
+        UploadStats { bytes, duration }
+    }
+
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// 每秒上传的字节数，耗时为 0 时返回 0
+    pub fn throughput(&self) -> f64 {
+        let secs = self.duration.as_secs_f64();
+        if secs > 0.0 {
+            self.bytes as f64 / secs
+        } else {
+            0.0
+        }
+    }
+}
+
+/// 响应中带有 `Content-MD5` 时，校验内容是否一致
+fn verify_content_md5(headers: &HeaderMap, content: &[u8]) -> Result<(), OssError> {
+    match headers.get(CONTENT_MD5) {
+        Some(md5) if md5.to_str()? != ContentMd5::from_bytes(content).as_str() => {
+            Err(OssError::Md5Mismatch)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// 删除文件后，OSS 返回的删除结果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeleteOutcome {
+    version_id: Option<String>,
+    delete_marker: bool,
+}
+
+impl DeleteOutcome {
+    /// 从删除接口的响应 header 中解析
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Result<Self, OssError> {
+        let version_id = match headers.get("x-oss-version-id") {
+            Some(v) => Some(v.to_str()?.to_owned()),
+            None => None,
+        };
+        let delete_marker = match headers.get("x-oss-delete-marker") {
+            Some(v) => v.to_str()?.eq_ignore_ascii_case("true"),
+            None => false,
+        };
+
+        Ok(DeleteOutcome {
+            version_id,
+            delete_marker,
+        })
+    }
+
+    /// 创建的删除标记或被删除版本的 version id，只有开启了版本控制的 bucket 才会返回
+    pub fn version_id(&self) -> Option<&str> {
+        self.version_id.as_deref()
+    }
+
+    /// 是否创建了（或删除的是）删除标记
+    pub fn delete_marker(&self) -> bool {
+        self.delete_marker
+    }
+}
+
+/// 图片的基本信息，由 [`Object::get_image_info`] 返回
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageInfo {
+    format: String,
+    width: u32,
+    height: u32,
+    size: u64,
+}
+
+impl ImageInfo {
+    /// 解析 `image/info` 处理返回的 JSON，每个字段的值都是 `{"value": "..."}` 的形式
+    pub(crate) fn from_json(json: &str) -> Result<Self, OssError> {
+        #[derive(serde::Deserialize)]
+        struct Value {
+            value: String,
+        }
+
+        #[derive(serde::Deserialize)]
+        #[serde(rename_all = "PascalCase")]
+        struct Info {
+            format: Value,
+            image_width: Value,
+            image_height: Value,
+            file_size: Value,
+        }
+
+        let info: Info = serde_json::from_str(json)?;
+        Ok(ImageInfo {
+            format: info.format.value,
+            width: info.image_width.value.parse()?,
+            height: info.image_height.value.parse()?,
+            size: info.file_size.value.parse()?,
+        })
+    }
+
+    /// 图片格式，如 `jpg`，`png`
+    pub fn format(&self) -> &str {
+        &self.format
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// 文件大小，单位为字节
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Object;
+    use crate::{
+        bucket::Bucket,
+        client::{init_client, Client},
+        types::{EndPoint, Key, ObjectQuery, Secret, StorageClass},
+    };
+    use std::sync::{Arc, Mutex};
+
+    fn set_client() -> Client {
+        let mut client = init_client();
+        client.set_bucket(Bucket::new("honglei123", EndPoint::CN_SHANGHAI));
+
+        client
+    }
+
+    /// 不访问网络的客户端，记录每个请求的 method 和 query，所有请求都返回成功
+    fn mock_client() -> (Client, Arc<Mutex<Vec<String>>>) {
+        let mut client = Client::new(Key::new("foo"), Secret::new("bar"));
+        client.set_bucket(Bucket::new("foo", EndPoint::CN_QINGDAO));
+
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let log = requests.clone();
+        client.set_mock(move |request| {
+            log.lock().unwrap().push(format!(
+                "{} {}",
+                request.method(),
+                request.url().query().unwrap_or_default()
+            ));
+            http::Response::builder()
+                .header("etag", "\"mock\"")
+                .body("<UploadId>mock</UploadId>")
+                .unwrap()
+                .into()
+        });
+
+        (client, requests)
+    }
+
+    #[tokio::test]
+    async fn test_object_info() {
+        let object = Object::new("app-config.json");
+
+        let info = object.get_info(&set_client()).await.unwrap();
+
+        println!("{info:?}");
+    }
+
+    #[tokio::test]
+    async fn test_upload() {
+        let object = Object::new("abc.txt");
+
+        let info = object.upload("aaa".into(), &set_client()).await.unwrap();
+
+        println!("{info:?}");
+    }
+
+    #[tokio::test]
+    async fn test_upload_from_reader() {
+        let client = set_client();
+        let object = Object::new("reader.txt");
+
+        let size = object
+            .upload_from_reader(std::io::Cursor::new(b"hello reader"), &client)
+            .await
+            .unwrap();
+        assert_eq!(size, 12);
+        assert_eq!(object.download(&client).await.unwrap(), b"hello reader");
+    }
+
+    #[tokio::test]
+    async fn test_upload_stream() {
+        use crate::client::MIN_PART_SIZE;
+
+        let mut client = set_client();
+        client.set_part_size(MIN_PART_SIZE).unwrap();
+        let object = Object::new("stream.bin");
+
+        let content: Vec<u8> = (0..MIN_PART_SIZE * 2 + 10)
+            .map(|i| (i % 251) as u8)
+            .collect();
+        let result = object
+            .upload_stream(futures_util::io::Cursor::new(content.clone()), &client)
+            .await
+            .unwrap();
+        // 分片上传的 etag 带有分片数量的后缀
+        assert!(result.etag().ends_with("-3\""));
+        assert_eq!(object.download(&client).await.unwrap(), content);
+
+        // 不足一个分片时使用普通上传，etag 为内容的 MD5
+        let result = object
+            .upload_stream(futures_util::io::Cursor::new(b"aaa".to_vec()), &client)
+            .await
+            .unwrap();
+        assert!(!result.etag().is_empty());
+        assert!(Client::verify_etag(b"aaa", result.etag()));
+    }
+
+    #[tokio::test]
+    async fn test_download_all() {
+        use super::Objects;
+
+        let client = set_client();
+        let files = [
+            ("download_all/a.txt", "aaa"),
+            ("download_all/sub/b.txt", "bbbb"),
+            ("download_all/sub/c.bin", "\0\x01\x02"),
+        ];
+        for (path, content) in files {
+            Object::new(path)
+                .upload(content.into(), &client)
+                .await
+                .unwrap();
+        }
+
+        let dir = std::env::temp_dir().join("oss_download_all");
+        let _ = std::fs::remove_dir_all(&dir);
+        let list = Objects::new(files.iter().map(|(p, _)| Object::new(*p)).collect(), None);
+        let result = list.download_all(&dir, &client).await.unwrap();
+
+        assert_eq!(result.len(), 3);
+        for ((path, content), (object, size)) in files.iter().zip(result) {
+            assert_eq!(object.get_path(), *path);
+            assert_eq!(size, content.len() as u64);
+            assert_eq!(std::fs::read(dir.join(path)).unwrap(), content.as_bytes());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_download_to_file_resume() {
+        let client = set_client();
+        let object = Object::new("resume.txt");
+        let content = b"0123456789abcdefghij".to_vec();
+        object.upload(content.clone(), &client).await.unwrap();
+
+        let path = std::env::temp_dir().join("oss_resume.txt");
+        std::fs::write(&path, &content[..10]).unwrap();
+        let size = object
+            .download_to_file_resume(&path, &client)
+            .await
+            .unwrap();
+        assert_eq!(size, content.len() as u64);
+        assert_eq!(std::fs::read(&path).unwrap(), content);
+    }
+
+    #[test]
+    fn test_write_at() {
+        use super::write_at;
+
+        let path = std::env::temp_dir().join("oss_write_at.txt");
+        let _ = std::fs::remove_file(&path);
+        let full = b"hello world";
+
+        // 中断时只写入了一半
+        assert_eq!(write_at(&path, 0, &full[..5]).unwrap(), 5);
+        assert_eq!(write_at(&path, 5, &full[5..]).unwrap(), 11);
+        assert_eq!(std::fs::read(&path).unwrap(), full);
+
+        // 本地文件比续传的起始位置长时，多余的部分会被丢弃
+        assert_eq!(write_at(&path, 6, b"rust").unwrap(), 10);
+        assert_eq!(std::fs::read(&path).unwrap(), b"hello rust");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_diff_local() {
+        use super::{ObjectInfo, ObjectInfos};
+        use chrono::Utc;
+        use std::fs;
+
+        let dir = std::env::temp_dir().join("oss_diff_local");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(dir.join("same.txt"), b"aaa").unwrap();
+        fs::write(dir.join("sub/edited.txt"), b"aab").unwrap();
+        fs::write(dir.join("resized.txt"), b"aaaa").unwrap();
+        fs::write(dir.join("multipart.bin"), b"aaa").unwrap();
+        fs::write(dir.join("sub/local.txt"), b"local").unwrap();
+
+        let md5 = "\"47BCE5C74F589F4867DBD57E9CA9F808\"";
+        let info = |etag: &str, size| ObjectInfo::new(Utc::now(), etag.to_owned(), size);
+        let list = ObjectInfos::new(
+            vec![
+                (Object::new("same.txt"), info(md5, 3)),
+                (Object::new("sub/edited.txt"), info(md5, 3)),
+                (Object::new("resized.txt"), info(md5, 3)),
+                (Object::new("multipart.bin"), info("\"ABC-2\"", 3)),
+                (Object::new("remote.txt"), info(md5, 3)),
+                (Object::new("sub/"), info(md5, 0)),
+            ],
+            None,
+        );
+
+        let plan = list.diff_local(&dir).unwrap();
+        assert_eq!(plan.missing_local(), [Object::new("remote.txt")]);
+        assert_eq!(plan.missing_remote(), [Object::new("sub/local.txt")]);
+        assert_eq!(
+            plan.changed(),
+            [Object::new("resized.txt"), Object::new("sub/edited.txt")]
+        );
+        assert!(!plan.is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+        let plan = list.diff_local(&dir).unwrap();
+        assert_eq!(plan.missing_local().len(), 5);
+    }
+
+    #[test]
+    fn test_image_info_from_json() {
+        use super::ImageInfo;
+
+        let json = r#"{
+            "FileSize": {"value": "21839"},
+            "Format": {"value": "jpg"},
+            "ImageHeight": {"value": "267"},
+            "ImageWidth": {"value": "400"},
+            "ResolutionUnit": {"value": "1"}
+        }"#;
+        let info = ImageInfo::from_json(json).unwrap();
+        assert_eq!(info.format(), "jpg");
+        assert_eq!(info.width(), 400);
+        assert_eq!(info.height(), 267);
+        assert_eq!(info.size(), 21839);
+
+        assert!(ImageInfo::from_json(r#"{"Format": {"value": "jpg"}}"#).is_err());
+    }
+
+    #[test]
+    fn test_multipart_ranges() {
+        use super::multipart_ranges;
+
+        assert_eq!(multipart_ranges(100, 100, 30), None);
+        assert_eq!(multipart_ranges(0, 0, 30), None);
+        assert_eq!(
+            multipart_ranges(101, 100, 30),
+            Some(vec![0..30, 30..60, 60..90, 90..101])
+        );
+        assert_eq!(multipart_ranges(60, 50, 30), Some(vec![0..30, 30..60]));
+    }
+
+    #[tokio::test]
+    async fn test_upload_auto() {
+        use crate::client::MIN_PART_SIZE;
+
+        let mut client = set_client();
+        client.set_part_size(MIN_PART_SIZE).unwrap();
+        client.set_multipart_threshold(MIN_PART_SIZE * 2);
+        let object = Object::new("upload_auto.bin");
+
+        let content: Vec<u8> = (0..MIN_PART_SIZE * 2 + 10)
+            .map(|i| (i % 251) as u8)
+            .collect();
+        let result = object.upload_auto(content.clone(), &client).await.unwrap();
+        // 分片上传的 etag 带有分片数量的后缀
+        assert!(result.etag().ends_with("-3\""));
+        assert_eq!(object.download(&client).await.unwrap(), content);
+
+        let result = object.upload_auto(b"aaa".to_vec(), &client).await.unwrap();
+        assert!(Client::verify_etag(b"aaa", result.etag()));
+    }
+
+    #[test]
+    fn test_try_from_iter() {
+        let res = Object::try_from_iter(vec![
+            "a.txt".to_owned(),
+            "\\bad.txt".to_owned(),
+            "x".repeat(1024),
+        ]);
+        assert!(matches!(res, Err(crate::Error::InvalidObjectPath(p)) if &*p == "\\bad.txt"));
+
+        let res = Object::try_from_iter(["a.txt", &"x".repeat(1024)]);
+        assert!(matches!(res, Err(crate::Error::InvalidObjectPath(p)) if p.len() == 1024));
+
+        let list = Object::try_from_iter(["/a.txt", &"x".repeat(1023)]).unwrap();
+        assert_eq!(list.len(), 2);
+    }
+
+    #[test]
+    fn test_local_path() {
+        use super::local_path;
+        use std::path::Path;
+
+        let dir = Path::new("/tmp/dest");
+        assert_eq!(
+            local_path(dir, "a/b.txt"),
+            Some(Path::new("/tmp/dest/a/b.txt").to_path_buf())
+        );
+        assert_eq!(
+            local_path(dir, "../../etc//passwd"),
+            Some(Path::new("/tmp/dest/etc/passwd").to_path_buf())
+        );
+        assert_eq!(local_path(dir, "a/dir/"), None);
+        assert_eq!(local_path(dir, "./.."), None);
+    }
+
+    #[test]
+    fn test_common_prefixes_across_pages() {
+        use super::Objects;
+
+        let prefixes = |list: &[&str]| list.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+
+        let first = Objects::new(vec![], Some("token".to_owned()))
+            .with_common_prefixes(prefixes(&["a/", "b/", "a/"]));
+        assert_eq!(first.common_prefixes(), ["a/", "b/"]);
+
+        let second = Objects::new(vec![], Some("token2".to_owned()))
+            .with_common_prefixes(prefixes(&["b/", "c/"]))
+            .after_page(&first);
+        assert_eq!(second.common_prefixes(), ["c/"]);
+
+        let third = Objects::new(vec![], None)
+            .with_common_prefixes(prefixes(&["a/", "c/", "d/"]))
+            .after_page(&second);
+        assert_eq!(third.common_prefixes(), ["d/"]);
+    }
+
+    #[tokio::test]
+    async fn test_read_part() {
+        use super::read_part;
+        use futures_util::io::Cursor;
+
+        let mut reader = Cursor::new(b"abcdefg");
+        assert_eq!(read_part(&mut reader, 3).await.unwrap(), b"abc");
+        assert_eq!(read_part(&mut reader, 3).await.unwrap(), b"def");
+        assert_eq!(read_part(&mut reader, 3).await.unwrap(), b"g");
+        assert!(read_part(&mut reader, 3).await.unwrap().is_empty());
+
+        // 内容很少时不会按分片大小分配内存
+        let part = read_part(&mut Cursor::new(vec![1u8; 10]), 200 * 1024 * 1024)
+            .await
+            .unwrap();
+        assert_eq!(part.len(), 10);
+        assert!(part.capacity() < 1024 * 1024);
+    }
+
+    #[tokio::test]
+    async fn test_upload_from_reader_parts() {
+        use crate::client::MIN_PART_SIZE;
+
+        let (mut client, requests) = mock_client();
+        client.set_part_size(MIN_PART_SIZE).unwrap();
+        let object = Object::new("reader.bin");
+
+        let content = vec![1u8; MIN_PART_SIZE as usize * 3 + 1];
+        let size = object
+            .upload_from_reader(std::io::Cursor::new(content), &client)
+            .await
+            .unwrap();
+        assert_eq!(size, MIN_PART_SIZE * 3 + 1);
+
+        let requests = requests.lock().unwrap();
+        let parts = requests
+            .iter()
+            .filter(|r| r.starts_with("PUT partNumber="))
+            .count();
+        assert_eq!(parts, 4);
+        assert_eq!(requests.first().unwrap(), "POST uploads");
+        assert_eq!(requests.last().unwrap(), "POST uploadId=mock");
+    }
+
+    #[tokio::test]
+    async fn test_upload_stream_switch() {
+        use crate::client::MIN_PART_SIZE;
+        use futures_util::io::Cursor;
+
+        let (mut client, requests) = mock_client();
+        client.set_part_size(MIN_PART_SIZE).unwrap();
+        let object = Object::new("stream.bin");
+
+        // 不足一个分片时只发送一次 PUT 请求
+        object
+            .upload_stream(Cursor::new(vec![1u8; 10]), &client)
+            .await
+            .unwrap();
+        assert_eq!(*requests.lock().unwrap(), ["PUT "]);
+
+        requests.lock().unwrap().clear();
+        let content = vec![1u8; MIN_PART_SIZE as usize * 2 + 10];
+        object
+            .upload_stream(Cursor::new(content), &client)
+            .await
+            .unwrap();
+        assert_eq!(
+            *requests.lock().unwrap(),
+            [
+                "POST uploads",
+                "PUT partNumber=1&uploadId=mock",
+                "PUT partNumber=2&uploadId=mock",
+                "PUT partNumber=3&uploadId=mock",
+                "POST uploadId=mock",
+            ]
+        );
+
+        // 内容恰好为一个分片时，读到末尾后才能确定，仍然使用分片上传
+        requests.lock().unwrap().clear();
+        let content = vec![1u8; MIN_PART_SIZE as usize];
+        object
+            .upload_stream(Cursor::new(content), &client)
+            .await
+            .unwrap();
+        assert_eq!(requests.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_down() {
+        let object = Object::new("abc.txt");
+
+        let info = object.download(&set_client()).await.unwrap();
+
+        println!("{:?}", std::str::from_utf8(&info).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_download_with_info() {
+        let client = set_client();
+        let object = Object::new("abc.txt");
+        object.upload("aaa".into(), &client).await.unwrap();
+
+        let (content, info) = object.download_with_info(&client).await.unwrap();
+        assert_eq!(content, b"aaa");
+        assert_eq!(info.size(), 3);
+        assert_eq!(info.etag(), "\"47BCE5C74F589F4867DBD57E9CA9F808\"");
+        assert_eq!(info.content_type(), Some("text/xml"));
+    }
+
+    #[tokio::test]
+    async fn test_copy() {
+        let object = Object::new("def.txt");
+        let res = object
+            .copy_from(&set_client(), &Object::new("/honglei123/aaabbb3.txt"))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_copy_with_storage_class() {
+        use super::MetadataDirective;
+        use reqwest::header::HeaderMap;
+
+        let client = set_client();
+        let object = Object::new("storage_class.txt");
+        object
+            .upload(b"storage class".to_vec(), &client)
+            .await
+            .unwrap();
+
+        object
+            .copy_with_storage_class(
+                &client,
+                &Object::new("/honglei123/storage_class.txt"),
+                MetadataDirective::Copy,
+                Some(StorageClass::IA),
+                HeaderMap::new(),
+            )
+            .await
+            .unwrap();
+
+        let info = object.get_info(&client).await.unwrap();
+        assert_eq!(info.storage_class(), Some(StorageClass::IA));
+
+        object.delete(&client).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_delete() {
+        let object = Object::new("abc.txt");
+
+        let info = object.delete(&set_client()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_delete_with_uploads() {
+        use crate::multipart::MultipartUpload;
+        use reqwest::header::HeaderMap;
+
+        let client = set_client();
+        let object = Object::new("dangling_upload.txt");
+        object.upload(b"content".to_vec(), &client).await.unwrap();
+        // 未完成的分片上传任务，合并后会重新创建文件
+        let upload = MultipartUpload::init(&object, HeaderMap::new(), &client)
+            .await
+            .unwrap();
+
+        assert_eq!(object.delete_with_uploads(&client).await.unwrap(), 1);
+
+        let bucket = client.bucket().unwrap();
+        let uploads = bucket
+            .list_multipart_uploads(object.as_ref(), &client)
+            .await
+            .unwrap();
+        assert!(uploads.iter().all(|u| u.upload_id() != upload.upload_id()));
+        assert!(object.get_info(&client).await.unwrap_err().is_not_found());
+    }
+
+    #[tokio::test]
+    async fn test_next_list() {
+        let client = set_client();
+        let condition = {
+            let mut map = ObjectQuery::new();
+            map.insert(ObjectQuery::MAX_KEYS, "5");
+            map
+        };
+        let first_list = client
+            .bucket()
+            .unwrap()
+            .get_objects(&condition, &client)
+            .await
+            .unwrap();
+
+        let second_list = first_list.next_list(&condition, &client).await.unwrap();
+
+        println!("{:?}", second_list);
+    }
+
+    #[tokio::test]
+    async fn test_refresh() {
+        let client = set_client();
+        let condition = {
+            let mut map = ObjectQuery::new();
+            map.insert(ObjectQuery::PREFIX, "refresh/");
+            map
+        };
+        let bucket = client.bucket().unwrap();
+        let mut list = bucket.get_objects(&condition, &client).await.unwrap();
+        let len = list.len();
+
+        let object = Object::new("refresh/abc.txt");
+        object.upload("aaa".into(), &client).await.unwrap();
+        list.refresh(&client).await.unwrap();
+        assert_eq!(list.len(), len + 1);
+
+        object.delete(&client).await.unwrap();
+        list.refresh(&client).await.unwrap();
+        assert_eq!(list.len(), len);
+    }
+
+    #[test]
+    fn test_objects_eq() {
+        use super::Objects;
+
+        let build = || {
+            let mut query = ObjectQuery::new();
+            query.insert(ObjectQuery::PREFIX, "abc/");
+            Objects::new(
+                vec![Object::new("abc/1.txt"), Object::new("abc/2.txt")],
+                Some("token".to_owned()),
+            )
+            .with_search(Bucket::new("foo", EndPoint::CN_QINGDAO), query)
+        };
+
+        assert_eq!(build(), build());
+
+        let other = Objects::new(
+            vec![Object::new("abc/1.txt"), Object::new("abc/2.txt")],
+            Some("token".to_owned()),
+        );
+        assert_ne!(build(), other);
+    }
+
+    #[tokio::test]
+    async fn test_upload_empty_file() {
+        let object = Object::new("empty.txt");
+
+        let info = object.upload(vec![], &set_client()).await;
+        assert!(info.is_ok())
+    }
+
+    #[test]
+    fn test_object_info_from_headers() {
+        use super::ObjectInfo;
+        use crate::bucket::Grant;
+        use reqwest::header::HeaderMap;
+
+        let mut headers = HeaderMap::new();
+        headers.insert("content-length", "344606".parse().unwrap());
+        headers.insert(
+            "etag",
+            "\"5B3C1A2E053D763E1B002CC607C5A0FE\"".parse().unwrap(),
+        );
+        headers.insert(
+            "last-modified",
+            "Fri, 24 Feb 2012 06:07:48 GMT".parse().unwrap(),
+        );
+
+        let info = ObjectInfo::from_headers(&headers).unwrap();
+        assert_eq!(info.size(), 344606);
+        assert_eq!(info.acl(), None);
+        assert_eq!(info.version_id(), None);
+
+        headers.insert("x-oss-object-acl", "public-read".parse().unwrap());
+        headers.insert("x-oss-version-id", "CAEQNRiBgID+".parse().unwrap());
+        let info = ObjectInfo::from_headers(&headers).unwrap();
+        assert_eq!(info.acl(), Some(Grant::PublicRead));
+        assert_eq!(info.version_id(), Some("CAEQNRiBgID+"));
+
+        headers.insert("x-oss-object-acl", "default".parse().unwrap());
+        let info = ObjectInfo::from_headers(&headers).unwrap();
+        assert_eq!(info.acl(), None);
+        assert_eq!(info.content_type(), None);
+
+        headers.insert("content-type", "text/plain".parse().unwrap());
+        let info = ObjectInfo::from_headers(&headers).unwrap();
+        assert_eq!(info.content_type(), Some("text/plain"));
+        assert!(info.user_metadata().is_empty());
+
+        headers.insert("x-oss-meta-author", "tu6ge".parse().unwrap());
+        headers.insert("x-oss-meta-project", "oss-rs".parse().unwrap());
+        let info = ObjectInfo::from_headers(&headers).unwrap();
+        let meta = info.user_metadata();
+        assert_eq!(meta.len(), 2);
+        assert_eq!(meta.get("author").map(String::as_str), Some("tu6ge"));
+        assert_eq!(meta.get("project").map(String::as_str), Some("oss-rs"));
+
+        // 非 ASCII 的元信息不会导致整个解析失败
+        headers.insert(
+            "x-oss-meta-title",
+            reqwest::header::HeaderValue::from_bytes("你好".as_bytes()).unwrap(),
+        );
+        headers.insert(
+            "x-oss-meta-raw",
+            reqwest::header::HeaderValue::from_bytes(b"a\xffb").unwrap(),
+        );
+        let info = ObjectInfo::from_headers(&headers).unwrap();
+        let meta = info.user_metadata();
+        assert_eq!(meta.get("title").map(String::as_str), Some("你好"));
+        assert_eq!(meta.get("raw").map(String::as_str), Some("a\u{FFFD}b"));
+
+        assert_eq!(info.storage_class(), None);
+        headers.insert("x-oss-storage-class", "IA".parse().unwrap());
+        let info = ObjectInfo::from_headers(&headers).unwrap();
+        assert_eq!(info.storage_class(), Some(StorageClass::IA));
+    }
+
+    #[tokio::test]
+    async fn test_write_in_order() {
+        use super::write_in_order;
+        use tokio::sync::oneshot;
+
+        let (senders, receivers): (Vec<_>, Vec<_>) = (0..4).map(|_| oneshot::channel()).unzip();
+        let parts = receivers
+            .into_iter()
+            .map(|rx| async move { Ok::<Vec<u8>, crate::Error>(rx.await.unwrap()) });
+
+        let mut writer = Vec::new();
+        // 后面的段先完成
+        let complete = async {
+            for (i, tx) in senders.into_iter().enumerate().rev() {
+                tx.send(vec![b'a' + i as u8; 2]).unwrap();
+                tokio::task::yield_now().await;
+            }
+        };
+        let (written, _) = futures_util::join!(write_in_order(parts, 4, &mut writer), complete);
+
+        assert_eq!(written.unwrap(), 8);
+        assert_eq!(writer, b"aabbccdd");
+    }
+
+    #[test]
+    fn test_checksum_algorithm() {
+        use super::{crc64_ecma, verify_crc64, ChecksumAlgorithm, UploadResult};
+        use crate::error::OssError;
+
+        assert_eq!(ChecksumAlgorithm::default(), ChecksumAlgorithm::Crc64);
+        assert!(ChecksumAlgorithm::Crc64.headers(b"aaa").unwrap().is_empty());
+        assert!(ChecksumAlgorithm::None.headers(b"aaa").unwrap().is_empty());
+        let headers = ChecksumAlgorithm::Md5.headers(b"aaa").unwrap();
+        assert_eq!(
+            headers.get("content-md5").unwrap(),
+            "R7zlx09Yn0hn29V+nKn4CA=="
+        );
+
+        let crc = crc64_ecma(0, b"aaa");
+        let result = |crc64| UploadResult::new("etag".to_owned(), None, crc64);
+        assert!(verify_crc64(crc, &result(Some(crc))).is_ok());
+        assert!(matches!(
+            verify_crc64(crc, &result(Some(crc + 1))),
+            Err(OssError::Crc64Mismatch)
+        ));
+        assert!(matches!(
+            verify_crc64(crc, &result(None)),
+            Err(OssError::NoFoundCrc64)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_upload_with_checksum() {
+        use super::ChecksumAlgorithm;
+
+        let client = set_client();
+        let object = Object::new("checksum.txt");
+        for algorithm in [
+            ChecksumAlgorithm::Crc64,
+            ChecksumAlgorithm::Md5,
+            ChecksumAlgorithm::None,
+        ] {
+            object
+                .upload_with_checksum(b"checksum".to_vec(), algorithm, &client)
+                .await
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_is_directory_marker() {
+        use super::ObjectInfo;
+        use chrono::Utc;
+
+        let empty = ObjectInfo::new(Utc::now(), "etag".to_owned(), 0);
+        assert!(empty.is_directory_marker(&Object::new("foo/")));
+        assert!(!empty.is_directory_marker(&Object::new("foo.txt")));
+
+        let file = ObjectInfo::new(Utc::now(), "etag".to_owned(), 3);
+        assert!(!file.is_directory_marker(&Object::new("foo/")));
+    }
+
+    #[test]
+    fn test_seek_position() {
+        use super::seek_position;
+        use std::io::SeekFrom;
+
+        assert_eq!(seek_position(3, 10, SeekFrom::Start(5)).unwrap(), 5);
+        assert_eq!(seek_position(3, 10, SeekFrom::Start(20)).unwrap(), 20);
+        assert_eq!(seek_position(3, 10, SeekFrom::Current(2)).unwrap(), 5);
+        assert_eq!(seek_position(3, 10, SeekFrom::Current(-3)).unwrap(), 0);
+        assert_eq!(seek_position(3, 10, SeekFrom::End(-4)).unwrap(), 6);
+        assert!(seek_position(3, 10, SeekFrom::Current(-4)).is_err());
+        assert!(seek_position(3, 10, SeekFrom::End(-11)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_object_reader() {
+        use super::ObjectReader;
+        use std::io::SeekFrom;
+
+        let client = set_client();
+        let object = Object::new("reader.txt");
+        object
+            .upload(b"0123456789".to_vec(), &client)
+            .await
+            .unwrap();
+
+        let mut reader = ObjectReader::open(object, &client).await.unwrap();
+        assert_eq!(reader.size(), 10);
+        assert_eq!(reader.seek(SeekFrom::Start(3)).unwrap(), 3);
+
+        let mut buf = [0; 4];
+        assert_eq!(reader.read(&mut buf, &client).await.unwrap(), 4);
+        assert_eq!(&buf, b"3456");
+        assert_eq!(reader.position(), 7);
+
+        reader.seek(SeekFrom::End(-2)).unwrap();
+        assert_eq!(reader.read(&mut buf, &client).await.unwrap(), 2);
+        assert_eq!(&buf[..2], b"89");
+        assert_eq!(reader.read(&mut buf, &client).await.unwrap(), 0);
+    }
+
+    #[test]
+    fn test_upload_stats() {
+        use super::UploadStats;
+        use std::time::Duration;
+
+        let stats = UploadStats::new(1000, Duration::from_secs(2));
+        assert_eq!(stats.bytes(), 1000);
+        assert_eq!(stats.throughput(), 500.0);
+        assert_eq!(UploadStats::new(1000, Duration::ZERO).throughput(), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_upload_with_stats() {
+        let client = set_client();
+        let result = Object::new("stats.txt")
+            .upload_with_stats(b"upload stats".to_vec(), &client)
+            .await
+            .unwrap();
+
+        let stats = result.stats().unwrap();
+        assert_eq!(stats.bytes(), 12);
+        assert!(stats.duration() > std::time::Duration::ZERO);
+        assert!(stats.throughput() > 0.0);
+    }
+
+    #[test]
+    fn test_range_download() {
+        use super::RangeDownload;
+        use reqwest::StatusCode;
+
+        let res = RangeDownload::new(StatusCode::PARTIAL_CONTENT, b"23".to_vec());
+        assert!(res.is_partial());
+        assert_eq!(res.into_content(), b"23");
+
+        let res = RangeDownload::new(StatusCode::OK, b"0123".to_vec());
+        assert_eq!(res, RangeDownload::Full(b"0123".to_vec()));
+    }
+
+    #[tokio::test]
+    async fn test_download_range_if() {
+        use super::RangeDownload;
+
+        let client = set_client();
+        let object = Object::new("if-range.txt");
+        let result = object
+            .upload_full(b"0123456789".to_vec(), &client)
+            .await
+            .unwrap();
+
+        let res = object
+            .download_range_if(2..=3, result.etag(), &client)
+            .await
+            .unwrap();
+        assert_eq!(res, RangeDownload::Partial(b"23".to_vec()));
+
+        let res = object
+            .download_range_if(2..=3, "\"00000000000000000000000000000000\"", &client)
+            .await
+            .unwrap();
+        assert_eq!(res, RangeDownload::Full(b"0123456789".to_vec()));
+    }
+
+    #[test]
+    fn test_cache_control() {
+        use super::CacheControl;
+
+        assert_eq!(
+            CacheControl::Public { max_age: 86400 }.to_string(),
+            "public, max-age=86400"
+        );
+        assert_eq!(
+            CacheControl::Private { max_age: 60 }.to_string(),
+            "private, max-age=60"
+        );
+        assert_eq!(CacheControl::NoCache.to_string(), "no-cache");
+        assert_eq!(CacheControl::NoStore.to_string(), "no-store");
+    }
+
+    #[tokio::test]
+    async fn test_upload_with_cache_control() {
+        use super::CacheControl;
+
+        let client = set_client();
+        let object = Object::new("cache-control.txt");
+        object
+            .upload_with_cache_control(b"cache".to_vec(), CacheControl::NoStore, &client)
+            .await
+            .unwrap();
+
+        let bucket = client.bucket().unwrap();
+        let response = client
+            .send(
+                reqwest::Method::HEAD,
+                object.to_url(bucket),
+                crate::types::CanonicalizedResource::from_object(bucket, &object),
+                reqwest::header::HeaderMap::new(),
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.headers().get("cache-control").unwrap(), "no-store");
+    }
+
+    #[test]
+    fn test_stream_part_size() {
+        use super::{stream_part_size, MAX_PARTS, MAX_PART_SIZE};
+        use crate::client::MIN_PART_SIZE;
+
+        assert_eq!(stream_part_size(MIN_PART_SIZE, 1), MIN_PART_SIZE);
+        assert_eq!(stream_part_size(MIN_PART_SIZE, 1000), MIN_PART_SIZE);
+        assert_eq!(stream_part_size(MIN_PART_SIZE, 1001), MIN_PART_SIZE * 2);
+        assert_eq!(stream_part_size(MAX_PART_SIZE, 9000), MAX_PART_SIZE);
+
+        // 按固定大小需要 20000 个分片的内容，调整后不超过分片数量的限制
+        let total = MIN_PART_SIZE * 20000;
+        let mut uploaded = 0;
+        let mut part_number = 0;
+        while uploaded < total {
+            part_number += 1;
+            uploaded += stream_part_size(MIN_PART_SIZE, part_number);
         }
+        assert!(part_number <= MAX_PARTS);
+        assert!(stream_part_size(MIN_PART_SIZE, part_number) > MIN_PART_SIZE);
     }
 
-    pub fn last_modified(&self) -> &DateTime<Utc> {
-        &self.last_modified
+    #[test]
+    fn test_verify_content_md5() {
+        use super::verify_content_md5;
+        use crate::error::OssError;
+        use reqwest::header::HeaderMap;
+
+        let mut headers = HeaderMap::new();
+        assert!(verify_content_md5(&headers, b"aaa").is_ok());
+
+        headers.insert("content-md5", "R7zlx09Yn0hn29V+nKn4CA==".parse().unwrap());
+        assert!(verify_content_md5(&headers, b"aaa").is_ok());
+        assert!(matches!(
+            verify_content_md5(&headers, b"aab"),
+            Err(OssError::Md5Mismatch)
+        ));
     }
 
-    pub fn size(&self) -> u64 {
-        self.size
+    #[test]
+    fn test_delete_outcome_from_headers() {
+        use super::DeleteOutcome;
+        use reqwest::header::HeaderMap;
+
+        let mut headers = HeaderMap::new();
+        let res = DeleteOutcome::from_headers(&headers).unwrap();
+        assert_eq!(res, DeleteOutcome::default());
+
+        headers.insert("x-oss-delete-marker", "true".parse().unwrap());
+        headers.insert(
+            "x-oss-version-id",
+            "CAEQMxiBgIDh3ZCB0BYiIGE4YjIyMjExZDhhYjQxNzZiNGUyZTI4ZjljZDcz****"
+                .parse()
+                .unwrap(),
+        );
+        let res = DeleteOutcome::from_headers(&headers).unwrap();
+        assert!(res.delete_marker());
+        assert_eq!(
+            res.version_id(),
+            Some("CAEQMxiBgIDh3ZCB0BYiIGE4YjIyMjExZDhhYjQxNzZiNGUyZTI4ZjljZDcz****")
+        );
+
+        headers.insert("x-oss-delete-marker", "false".parse().unwrap());
+        assert!(!DeleteOutcome::from_headers(&headers)
+            .unwrap()
+            .delete_marker());
     }
 
-    pub fn etag(&self) -> &str {
-        &self.etag
+    #[test]
+    fn test_upload_result_from_headers() {
+        use super::UploadResult;
+        use reqwest::header::HeaderMap;
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "etag",
+            "\"F75A15996D0857B16FA31A3B16624C26\"".parse().unwrap(),
+        );
+        headers.insert("x-oss-version-id", "CAEQNRiBgID".parse().unwrap());
+        headers.insert(
+            "x-oss-hash-crc64ecma",
+            "8378424751584183301".parse().unwrap(),
+        );
+
+        let res = UploadResult::from_headers(&headers).unwrap();
+        assert_eq!(res.etag(), "\"F75A15996D0857B16FA31A3B16624C26\"");
+        assert_eq!(res.version_id(), Some("CAEQNRiBgID"));
+        assert_eq!(res.crc64(), Some(8378424751584183301));
+
+        headers.remove("x-oss-version-id");
+        let res = UploadResult::from_headers(&headers).unwrap();
+        assert_eq!(res.version_id(), None);
+
+        headers.remove("etag");
+        assert!(UploadResult::from_headers(&headers).is_err());
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::Object;
-    use crate::{
-        bucket::Bucket,
-        client::{init_client, Client},
-        types::{EndPoint, ObjectQuery},
-    };
+    #[tokio::test]
+    async fn test_append_line() {
+        let client = set_client();
+        let object = Object::new("append-log.txt");
+        let _ = object.delete(&client).await;
+
+        let first = object.append_line("first", &client).await.unwrap();
+        assert_eq!(first, 6);
+        let second = object.append_line("second\n", &client).await.unwrap();
+        assert_eq!(second, 13);
+    }
+
+    #[test]
+    fn test_append_helpers() {
+        use super::{next_append_position, to_log_line};
+        use reqwest::header::HeaderMap;
+
+        let bucket = Bucket::new("foo", EndPoint::CN_QINGDAO);
+        let object = Object::new("log.txt");
+        assert_eq!(
+            object.to_append_url(&bucket, 6).as_str(),
+            "https://foo.oss-cn-qingdao.aliyuncs.com/log.txt?append&position=6"
+        );
+
+        assert_eq!(to_log_line("abc"), b"abc\n");
+        assert_eq!(to_log_line("abc\n"), b"abc\n");
+
+        let mut headers = HeaderMap::new();
+        assert!(next_append_position(&headers).is_err());
+        headers.insert("x-oss-next-append-position", "13".parse().unwrap());
+        assert_eq!(next_append_position(&headers).unwrap(), 13);
+    }
+
+    #[tokio::test]
+    async fn test_download_auto() {
+        use crate::client::MIN_PART_SIZE;
+        use reqwest::header::RANGE;
+
+        let part = MIN_PART_SIZE as usize;
+        let content: Arc<Vec<u8>> = Arc::new((0..part * 20 + 5).map(|i| (i % 251) as u8).collect());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let mut client = Client::new(Key::new("foo"), Secret::new("bar"));
+        client.set_bucket(Bucket::new("foo", EndPoint::CN_QINGDAO));
+        client.set_download_part_size(MIN_PART_SIZE).unwrap();
+        {
+            let (content, requests) = (content.clone(), requests.clone());
+            client.set_mock(move |request| {
+                let response = http::Response::builder()
+                    .header("etag", "\"mock\"")
+                    .header("last-modified", "Fri, 24 Feb 2012 06:07:48 GMT");
+                if request.url().query() == Some("objectMeta") {
+                    requests.lock().unwrap().push("objectMeta".to_owned());
+                    return response
+                        .header("content-length", content.len())
+                        .body(Vec::new())
+                        .unwrap()
+                        .into();
+                }
+                let range = request.headers()[RANGE].to_str().unwrap().to_owned();
+                let (start, end) = range
+                    .strip_prefix("bytes=")
+                    .and_then(|r| r.split_once('-'))
+                    .unwrap();
+                let (start, end): (usize, usize) = (start.parse().unwrap(), end.parse().unwrap());
+                requests.lock().unwrap().push(range);
+                response.body(content[start..=end].to_vec()).unwrap().into()
+            });
+        }
+
+        let object = Object::new("auto/large.bin");
+        assert_eq!(object.download_auto(&client).await.unwrap(), *content);
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 22);
+        assert_eq!(requests[0], "objectMeta");
+        assert_eq!(requests[1], format!("bytes=0-{}", part - 1));
+        assert_eq!(
+            requests[21],
+            format!("bytes={}-{}", part * 20, part * 20 + 4)
+        );
+    }
+
+    #[test]
+    fn test_split_ranges() {
+        use super::split_ranges;
+
+        assert_eq!(split_ranges(0, 10), vec![]);
+        assert_eq!(split_ranges(10, 10), vec![(0, 9)]);
+        assert_eq!(split_ranges(25, 10), vec![(0, 9), (10, 19), (20, 24)]);
+    }
+
+    #[test]
+    fn test_upload_dry_run() {
+        use crate::types::{Key, Secret};
+        use reqwest::{
+            header::{HeaderMap, AUTHORIZATION, CONTENT_LENGTH, DATE},
+            Method,
+        };
+
+        let mut client = Client::new(Key::new("foo"), Secret::new("bar"));
+        assert!(Object::new("a.txt")
+            .upload_dry_run(3, HeaderMap::new(), &client)
+            .is_err());
 
-    fn set_client() -> Client {
-        let mut client = init_client();
         client.set_bucket(Bucket::new("honglei123", EndPoint::CN_SHANGHAI));
+        let request = Object::new("path/a.txt")
+            .upload_dry_run(3, HeaderMap::new(), &client)
+            .unwrap();
 
-        client
+        assert_eq!(request.method(), Method::PUT);
+        assert_eq!(
+            request.url().as_str(),
+            "https://honglei123.oss-cn-shanghai.aliyuncs.com/path/a.txt"
+        );
+        assert_eq!(request.resource(), "/honglei123/path/a.txt");
+        let headers = request.headers();
+        assert_eq!(headers.get(CONTENT_LENGTH).unwrap(), "3");
+        assert!(headers.get(DATE).is_some());
+        assert!(headers
+            .get(AUTHORIZATION)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .starts_with("OSS foo:"));
+    }
+
+    #[test]
+    fn test_appender_state() {
+        use super::Appender;
+
+        let appender = Appender::new(Object::new("log.txt"));
+        assert_eq!(appender.position(), None);
+        assert_eq!(appender.crc64(), None);
+
+        let appender = Appender::resume(Object::new("log.txt"), 10, 123);
+        assert_eq!(appender.object(), &Object::new("log.txt"));
+        assert_eq!(appender.position(), Some(10));
+        assert_eq!(appender.crc64(), Some(123));
     }
 
     #[tokio::test]
-    async fn test_object_info() {
-        let object = Object::new("app-config.json");
+    async fn test_appender() {
+        use super::Appender;
 
-        let info = object.get_info(&set_client()).await.unwrap();
+        let client = set_client();
+        let object = Object::new("appender.log");
+        let _ = object.delete(&client).await;
 
-        println!("{info:?}");
+        let mut appender = Appender::new(object.clone());
+        assert_eq!(appender.write(b"aaa", &client).await.unwrap(), 3);
+        assert_eq!(appender.write(b"bb", &client).await.unwrap(), 5);
+        assert_eq!(appender.write(b"c", &client).await.unwrap(), 6);
+        assert_eq!(object.download(&client).await.unwrap(), b"aaabbc");
     }
 
     #[tokio::test]
-    async fn test_upload() {
-        let object = Object::new("abc.txt");
+    async fn test_upload_idempotent_retry() {
+        use crate::types::ContentMd5;
+        use reqwest::header::{HeaderMap, AUTHORIZATION, DATE};
 
-        let info = object.upload("aaa".into(), &set_client()).await.unwrap();
+        let (mut client, _) = mock_client();
+        let attempts: Arc<Mutex<Vec<HeaderMap>>> = Arc::new(Mutex::new(Vec::new()));
+        {
+            let attempts = attempts.clone();
+            client.set_mock_result(move |request| {
+                let mut attempts = attempts.lock().unwrap();
+                attempts.push(request.headers().clone());
+                if attempts.len() == 1 {
+                    // 模拟第一次请求的网络错误
+                    return Err(reqwest::Client::new().get("http://").build().unwrap_err());
+                }
+                Ok(http::Response::builder()
+                    .header("etag", "\"mock\"")
+                    .body(Vec::new())
+                    .unwrap()
+                    .into())
+            });
+        }
 
-        println!("{info:?}");
+        let result = Object::new("idempotent.txt")
+            .upload_idempotent(b"hello".to_vec(), 1, &client)
+            .await
+            .unwrap();
+        assert_eq!(result.etag(), "\"mock\"");
+
+        let mut attempts = attempts.lock().unwrap();
+        assert_eq!(attempts.len(), 2);
+        assert_eq!(
+            attempts[0].get("content-md5").unwrap(),
+            ContentMd5::from_bytes(b"hello").as_str()
+        );
+        // 签名时间可能不同，其余 header 两次重试完全一致
+        for headers in attempts.iter_mut() {
+            headers.remove(DATE);
+            headers.remove(AUTHORIZATION);
+        }
+        assert_eq!(attempts[0], attempts[1]);
     }
 
     #[tokio::test]
-    async fn test_down() {
-        let object = Object::new("abc.txt");
+    async fn test_appender_requests() {
+        use super::{crc64_ecma, Appender};
 
-        let info = object.download(&set_client()).await.unwrap();
+        let (mut client, requests) = mock_client();
+        let content = Arc::new(Mutex::new(Vec::new()));
+        {
+            let (content, requests) = (content.clone(), requests.clone());
+            client.set_mock(move |request| {
+                requests.lock().unwrap().push(request.method().to_string());
+                let mut content = content.lock().unwrap();
+                if request.method() == "HEAD" {
+                    return http::Response::builder()
+                        .status(404)
+                        .body(Vec::new())
+                        .unwrap()
+                        .into();
+                }
+                let position = format!("position={}", content.len());
+                assert!(request.url().query().unwrap().ends_with(&position));
+                content.extend_from_slice(request.body().unwrap().as_bytes().unwrap());
+                http::Response::builder()
+                    .header("x-oss-next-append-position", content.len())
+                    .header("x-oss-hash-crc64ecma", crc64_ecma(0, &content))
+                    .body(Vec::new())
+                    .unwrap()
+                    .into()
+            });
+        }
 
-        println!("{:?}", std::str::from_utf8(&info).unwrap());
+        let mut appender = Appender::new(Object::new("appender.log"));
+        assert_eq!(appender.write(b"aaa", &client).await.unwrap(), 3);
+        assert_eq!(appender.write(b"bb", &client).await.unwrap(), 5);
+        assert_eq!(appender.write(b"c", &client).await.unwrap(), 6);
+
+        // 只在第一次写入前读取一次文件状态，之后每次写入只有一个追加请求
+        assert_eq!(*requests.lock().unwrap(), ["HEAD", "POST", "POST", "POST"]);
+        assert_eq!(*content.lock().unwrap(), b"aaabbc");
     }
 
     #[tokio::test]
-    async fn test_copy() {
-        let object = Object::new("def.txt");
-        let res = object
-            .copy_from(&set_client(), &Object::new("/honglei123/aaabbb3.txt"))
+    async fn test_append_position_without_crc() {
+        let (mut client, _) = mock_client();
+        client.set_mock(|_| {
+            http::Response::builder()
+                .header("x-oss-next-append-position", "42")
+                .body(Vec::new())
+                .unwrap()
+                .into()
+        });
+
+        let position = Object::new("appender.log")
+            .append_position(&client)
             .await
             .unwrap();
+        assert_eq!(position, 42);
     }
 
-    #[tokio::test]
-    async fn test_delete() {
-        let object = Object::new("abc.txt");
+    #[test]
+    fn test_append_crc() {
+        use super::{append_crc_headers, crc64_ecma, hash_crc64ecma};
+        use reqwest::header::HeaderMap;
 
-        let info = object.delete(&set_client()).await.unwrap();
+        assert_eq!(crc64_ecma(0, b"123456789"), 0x995D_C9BB_DF19_39FA);
+
+        // 第一次追加不携带 CRC
+        assert!(append_crc_headers(0, 0).unwrap().is_empty());
+        let first = crc64_ecma(0, b"hello ");
+
+        // 第二次追加携带第一次返回的 CRC，追加后的值与整个文件的 CRC 一致
+        let headers = append_crc_headers(6, first).unwrap();
+        assert_eq!(
+            headers.get("x-oss-hash-crc64ecma").unwrap(),
+            first.to_string().as_str()
+        );
+        let second = crc64_ecma(first, b"world");
+        assert_eq!(second, crc64_ecma(0, b"hello world"));
+
+        let mut response = HeaderMap::new();
+        response.insert("x-oss-hash-crc64ecma", second.to_string().parse().unwrap());
+        assert_eq!(hash_crc64ecma(&response).unwrap(), second);
+    }
+
+    #[test]
+    fn test_matches_glob() {
+        let obj = Object::new("logs/2024-01/app.gz");
+
+        // 字面量
+        assert!(obj.matches_glob("logs/2024-01/app.gz"));
+        assert!(!obj.matches_glob("logs/2024-01/app.g"));
+        assert!(!obj.matches_glob("logs/2024-01"));
+
+        // `*` 和 `?` 不跨越 `/`
+        assert!(obj.matches_glob("logs/*/*"));
+        assert!(obj.matches_glob("*/2024-??/*.gz"));
+        assert!(!obj.matches_glob("logs/*"));
+        assert!(!obj.matches_glob("*.gz"));
+
+        // `**` 跨越任意层目录
+        assert!(obj.matches_glob("**/*.gz"));
+        assert!(obj.matches_glob("logs/**/2024-01/app.gz"));
+        assert!(obj.matches_glob("**"));
+        assert!(Object::new("logs/a/b/c.gz").matches_glob("logs/**/*.gz"));
+        assert!(!Object::new("logs/a/b/c.txt").matches_glob("logs/**/*.gz"));
+
+        assert!(Object::new("目录/文件.txt").matches_glob("目录/??.txt"));
+    }
+
+    #[test]
+    fn test_uploads_of() {
+        use super::uploads_of;
+        use crate::multipart::MultipartUpload;
+
+        let xml = r#"<ListMultipartUploadsResult>
+          <Upload><Key>a.txt</Key><UploadId>1</UploadId></Upload>
+          <Upload><Key>a.txt.bak</Key><UploadId>2</UploadId></Upload>
+          <Upload><Key>a.txt</Key><UploadId>3</UploadId></Upload>
+        </ListMultipartUploadsResult>"#;
+        let (uploads, _) = MultipartUpload::parse_list_xml(xml).unwrap();
+
+        let ids: Vec<_> = uploads_of(&Object::new("a.txt"), uploads)
+            .iter()
+            .map(|u| u.upload_id().to_owned())
+            .collect();
+        assert_eq!(ids, ["1", "3"]);
+    }
+
+    #[test]
+    fn test_copy_ranges() {
+        use super::{copy_ranges, MAX_COPY_SIZE};
+
+        assert_eq!(copy_ranges(MAX_COPY_SIZE, 1024), None);
+
+        let part_size = 200 * 1024 * 1024;
+        let ranges = copy_ranges(MAX_COPY_SIZE + 1, part_size).unwrap();
+        assert_eq!(ranges.len(), 6);
+        assert_eq!(ranges[0], (0, part_size - 1));
+        assert_eq!(ranges[5], (5 * part_size, MAX_COPY_SIZE));
+    }
+
+    #[test]
+    fn test_copy_headers() {
+        use super::{copy_headers, MetadataDirective};
+        use reqwest::header::{HeaderMap, CONTENT_TYPE};
+
+        let source = Object::new("/foo/abc.png");
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, "image/png".parse().unwrap());
+
+        let res = copy_headers(&source, MetadataDirective::Replace, None, headers.clone()).unwrap();
+        assert_eq!(res.get("x-oss-copy-source").unwrap(), "/foo/abc.png");
+        assert_eq!(res.get("x-oss-metadata-directive").unwrap(), "REPLACE");
+        assert_eq!(res.get(CONTENT_TYPE).unwrap(), "image/png");
+
+        let res = copy_headers(&source, MetadataDirective::Copy, None, headers.clone()).unwrap();
+        assert_eq!(res.get("x-oss-metadata-directive").unwrap(), "COPY");
+        assert!(res.get(CONTENT_TYPE).is_none());
+        assert!(res.get("x-oss-storage-class").is_none());
+
+        let res = copy_headers(
+            &source,
+            MetadataDirective::Copy,
+            Some(StorageClass::IA),
+            headers,
+        )
+        .unwrap();
+        assert_eq!(res.get("x-oss-storage-class").unwrap(), "IA");
+    }
+
+    #[test]
+    fn test_restore_status() {
+        use super::{restore_status, RestoreStatus};
+        use crate::error::OssError;
+        use reqwest::StatusCode;
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Error>
+  <Code>RestoreAlreadyInProgress</Code>
+  <Message>The restore operation is in progress.</Message>
+  <RequestId>5C3D8D2A0ACA54D87B43****</RequestId>
+</Error>"#;
+        let res = restore_status(Err(OssError::from_status(StatusCode::CONFLICT, xml)));
+        assert_eq!(res.unwrap(), RestoreStatus::InProgress);
+
+        let not_archived = xml.replace("RestoreAlreadyInProgress", "OperationNotSupported");
+        let res = restore_status(Err(OssError::from_status(
+            StatusCode::BAD_REQUEST,
+            &not_archived,
+        )));
+        assert_eq!(res.unwrap(), RestoreStatus::NotArchived);
+
+        let denied = xml.replace("RestoreAlreadyInProgress", "AccessDenied");
+        let res = restore_status(Err(OssError::from_status(StatusCode::FORBIDDEN, &denied)));
+        assert!(matches!(res, Err(OssError::Service(e)) if e.code() == "AccessDenied"));
+
+        let response = |status: u16| http::Response::builder().status(status).body("").unwrap();
+        let res = restore_status(Ok(response(202).into()));
+        assert_eq!(res.unwrap(), RestoreStatus::Started);
+        let res = restore_status(Ok(response(200).into()));
+        assert_eq!(res.unwrap(), RestoreStatus::AlreadyRestored);
+    }
+
+    #[test]
+    fn test_copy_condition() {
+        use super::{copy_headers, CopyCondition, MetadataDirective};
+        use chrono::{TimeZone, Utc};
+        use reqwest::header::HeaderMap;
+
+        let condition = CopyCondition::new()
+            .if_match("\"abc\"")
+            .if_none_match("\"def\"")
+            .if_modified_since(Utc.with_ymd_and_hms(2024, 3, 20, 8, 0, 0).unwrap())
+            .if_unmodified_since(Utc.with_ymd_and_hms(2024, 3, 21, 8, 0, 0).unwrap());
+        let mut headers = HeaderMap::new();
+        condition.apply(&mut headers).unwrap();
+
+        let headers = copy_headers(
+            &Object::new("/foo/a.txt"),
+            MetadataDirective::Copy,
+            None,
+            headers,
+        )
+        .unwrap();
+        assert_eq!(
+            headers.get("x-oss-copy-source-if-match").unwrap(),
+            "\"abc\""
+        );
+        assert_eq!(
+            headers.get("x-oss-copy-source-if-none-match").unwrap(),
+            "\"def\""
+        );
+        assert_eq!(
+            headers.get("x-oss-copy-source-if-modified-since").unwrap(),
+            "Wed, 20 Mar 2024 08:00:00 GMT"
+        );
+        assert_eq!(
+            headers
+                .get("x-oss-copy-source-if-unmodified-since")
+                .unwrap(),
+            "Thu, 21 Mar 2024 08:00:00 GMT"
+        );
+        assert_eq!(headers.get("x-oss-metadata-directive").unwrap(), "COPY");
+    }
+
+    #[test]
+    fn test_version_url() {
+        use crate::types::CanonicalizedResource;
+
+        let bucket = Bucket::new("foo", EndPoint::CN_QINGDAO);
+        let object = Object::new("path/abc.txt");
+        let url = object.to_version_url(&bucket, "CAEQNRiBgID+");
+        assert_eq!(
+            url.as_str(),
+            "https://foo.oss-cn-qingdao.aliyuncs.com/path/abc.txt?versionId=CAEQNRiBgID%2B"
+        );
+
+        let resource = CanonicalizedResource::from_object_version(&bucket, &object, "CAEQNRiBgID+");
+        assert_eq!(
+            resource.as_str(),
+            "/foo/path/abc.txt?versionId=CAEQNRiBgID+"
+        );
+    }
+
+    #[test]
+    fn test_tagging_header() {
+        use super::tagging_header;
+        use crate::auth::to_oss_header;
+        use reqwest::header::HeaderMap;
+
+        let value = tagging_header(&[("k1", "v1"), ("中文", "a&b=c")]).unwrap();
+        assert_eq!(value, "k1=v1&%E4%B8%AD%E6%96%87=a%26b%3Dc");
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-oss-tagging", value);
+        assert_eq!(
+            to_oss_header(&headers),
+            "x-oss-tagging:k1=v1&%E4%B8%AD%E6%96%87=a%26b%3Dc\n"
+        );
+
+        let too_many: Vec<_> = (0..11).map(|i| (i.to_string(), "v")).collect();
+        assert!(tagging_header(&too_many).is_err());
+
+        let long_key = "k".repeat(129);
+        assert!(tagging_header(&[(long_key.as_str(), "v")]).is_err());
+
+        let long_value = "v".repeat(257);
+        assert!(tagging_header(&[("k", long_value.as_str())]).is_err());
+    }
+
+    #[test]
+    fn test_decode_content() {
+        use super::decode_content;
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"hello oss").unwrap();
+        let gzip = encoder.finish().unwrap();
+
+        let content = decode_content(Some("gzip"), gzip.clone()).unwrap();
+        assert_eq!(content, b"hello oss");
+
+        let content = decode_content(None, gzip.clone()).unwrap();
+        assert_eq!(content, gzip);
+
+        assert!(decode_content(Some("gzip"), b"hello oss".to_vec()).is_err());
+    }
+
+    #[test]
+    fn test_decode_by_ext() {
+        use super::decode_by_ext;
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"hello oss").unwrap();
+        let gzip = encoder.finish().unwrap();
+        assert_eq!(decode_by_ext("logs/a.log.gz", gzip).unwrap(), b"hello oss");
+
+        let content = decode_by_ext("logs/a.log", b"hello oss".to_vec()).unwrap();
+        assert_eq!(content, b"hello oss");
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_decode_by_ext_zstd() {
+        use super::decode_by_ext;
+
+        let zst = zstd::encode_all(&b"hello oss"[..], 0).unwrap();
+        assert_eq!(decode_by_ext("logs/a.log.ZST", zst).unwrap(), b"hello oss");
+    }
+
+    #[test]
+    fn test_next_query() {
+        use super::Objects;
+
+        let mut query = ObjectQuery::new();
+        query.insert(ObjectQuery::MAX_KEYS, "5");
+        query.insert(ObjectQuery::PREFIX, "foo");
+        let bucket = Bucket::new("foo", EndPoint::CN_QINGDAO);
+
+        let last = Objects::new(vec![], None).with_search(bucket.clone(), query.clone());
+        assert!(last.next_query().is_none());
+
+        let objects = Objects::new(vec![], Some("CgJiYw--".to_owned())).with_search(bucket, query);
+        let next = objects.next_query().unwrap();
+        assert_eq!(next.get(ObjectQuery::MAX_KEYS).unwrap(), "5");
+        assert_eq!(next.get(ObjectQuery::PREFIX).unwrap(), "foo");
+        assert_eq!(
+            next.get(ObjectQuery::CONTINUATION_TOKEN).unwrap(),
+            "CgJiYw--"
+        );
+    }
+
+    #[test]
+    fn test_next_query_start_after() {
+        use super::Objects;
+
+        let mut query = ObjectQuery::new();
+        query.insert(ObjectQuery::START_AFTER, "b");
+        let bucket = Bucket::new("foo", EndPoint::CN_QINGDAO);
+
+        let objects = Objects::new(vec![], Some("CgJiYw--".to_owned())).with_search(bucket, query);
+        let next = objects.next_query().unwrap();
+        assert_eq!(next.get(ObjectQuery::START_AFTER), None);
+        assert_eq!(
+            next.get(ObjectQuery::CONTINUATION_TOKEN).unwrap(),
+            "CgJiYw--"
+        );
     }
 
     #[tokio::test]
-    async fn test_next_list() {
+    async fn test_start_after_pages() {
         let client = set_client();
-        let condition = {
-            let mut map = ObjectQuery::new();
-            map.insert(ObjectQuery::MAX_KEYS, "5");
-            map
-        };
-        let first_list = client
-            .bucket()
-            .unwrap()
-            .get_objects(&condition, &client)
+        let bucket = client.bucket().unwrap();
+        let mut query = ObjectQuery::new();
+        query.insert(ObjectQuery::START_AFTER, "b");
+        query.insert(ObjectQuery::MAX_KEYS, "2");
+
+        let first = bucket.get_objects(&query, &client).await.unwrap();
+        let second = first.next_page(&client).await.unwrap().unwrap();
+        let paths: Vec<_> = first
+            .get_vec()
+            .iter()
+            .chain(second.get_vec())
+            .map(|obj| obj.get_path().to_owned())
+            .collect();
+
+        assert!(paths.iter().all(|p| p.as_str() > "b"));
+        let mut deduped = paths.clone();
+        deduped.dedup();
+        assert_eq!(deduped, paths);
+    }
+
+    #[test]
+    fn test_symlink_target() {
+        use super::symlink_target;
+        use reqwest::header::HeaderMap;
+
+        let mut headers = HeaderMap::new();
+        assert!(symlink_target(&headers).is_err());
+
+        headers.insert(
+            "x-oss-symlink-target",
+            "path/%E4%B8%AD%E6%96%87%20a.txt".parse().unwrap(),
+        );
+        assert_eq!(
+            symlink_target(&headers).unwrap(),
+            (None, Object::new("path/中文 a.txt"))
+        );
+
+        headers.insert(
+            "x-oss-symlink-target",
+            "/other-bucket/path/%E4%B8%AD%E6%96%87%20a.txt"
+                .parse()
+                .unwrap(),
+        );
+        assert_eq!(
+            symlink_target(&headers).unwrap(),
+            (
+                Some("other-bucket".to_owned()),
+                Object::new("path/中文 a.txt")
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_download_follow_symlink() {
+        let (mut client, _) = mock_client();
+        let hosts = Arc::new(Mutex::new(Vec::new()));
+        {
+            let hosts = hosts.clone();
+            client.set_mock(move |request| {
+                hosts
+                    .lock()
+                    .unwrap()
+                    .push(request.url().host_str().unwrap().to_owned());
+                let response = http::Response::builder();
+                let response = if request.url().query() == Some("symlink") {
+                    response.header("x-oss-symlink-target", "/other-bucket/a.txt")
+                } else {
+                    response
+                };
+                response.body("hello").unwrap().into()
+            });
+        }
+
+        let (bucket, target, content) = Object::new("link.txt")
+            .download_follow_symlink(&client)
             .await
             .unwrap();
+        assert_eq!(bucket, Bucket::new("other-bucket", EndPoint::CN_QINGDAO));
+        assert_eq!(target, Object::new("a.txt"));
+        assert_eq!(content, b"hello");
+        assert_eq!(
+            *hosts.lock().unwrap(),
+            [
+                "foo.oss-cn-qingdao.aliyuncs.com",
+                "other-bucket.oss-cn-qingdao.aliyuncs.com"
+            ]
+        );
+    }
 
-        let second_list = first_list.next_list(&condition, &client).await.unwrap();
+    #[test]
+    fn test_symlink_headers() {
+        use crate::{bucket::Bucket, types::EndPoint};
 
-        println!("{:?}", second_list);
+        let link = Object::new("link.txt");
+        let target = Object::new("path/中文 a.txt");
+        let headers = link.symlink_headers(None, &target).unwrap();
+        assert_eq!(
+            headers["x-oss-symlink-target"],
+            "path/%E4%B8%AD%E6%96%87%20a.txt"
+        );
+
+        let other = Bucket::new("other-bucket", EndPoint::CN_QINGDAO);
+        let headers = link.symlink_headers(Some(&other), &target).unwrap();
+        assert_eq!(
+            headers["x-oss-symlink-target"],
+            "/other-bucket/path/%E4%B8%AD%E6%96%87%20a.txt"
+        );
+
+        let res = link.symlink_headers(Some(&other), &Object::new(""));
+        assert!(matches!(res, Err(crate::Error::InvalidObjectPath(p)) if p.is_empty()));
+        let res = Object::new("\\link").symlink_headers(None, &target);
+        assert!(matches!(res, Err(crate::Error::InvalidObjectPath(p)) if &*p == "\\link"));
     }
 
-    #[tokio::test]
-    async fn test_upload_empty_file() {
-        let object = Object::new("empty.txt");
+    #[test]
+    fn test_content_type_from_path() {
+        use super::content_type_from_path;
 
-        let info = object.upload(vec![], &set_client()).await;
-        assert!(info.is_ok())
+        assert_eq!(content_type_from_path("img/foo.png"), "image/png");
+        assert_eq!(content_type_from_path("FOO.PNG"), "image/png");
+        assert_eq!(content_type_from_path("data.json"), "application/json");
+        assert_eq!(
+            content_type_from_path("foo.abcdef"),
+            "application/octet-stream"
+        );
+        assert_eq!(
+            content_type_from_path("dir.png/foo"),
+            "application/octet-stream"
+        );
+    }
+
+    #[test]
+    fn test_ext_from_content_type() {
+        use super::{content_type_from_path, ext_from_content_type};
+
+        assert_eq!(
+            ext_from_content_type("text/plain; charset=utf-8"),
+            Some("txt")
+        );
+        assert_eq!(ext_from_content_type("text/xml"), Some("xml"));
+        assert_eq!(ext_from_content_type("image/jpeg"), Some("jpg"));
+        assert_eq!(ext_from_content_type("application/octet-stream"), None);
+
+        // 两个方向使用同一张表，推断出的扩展名可以得到原来的 Content-Type
+        for mime in ["text/html", "application/json", "image/png", "video/mp4"] {
+            let ext = ext_from_content_type(mime).unwrap();
+            assert_eq!(content_type_from_path(&format!("foo.{ext}")), mime);
+        }
+    }
+
+    #[test]
+    fn test_with_inferred_ext() {
+        use super::with_inferred_ext;
+        use std::path::Path;
+
+        let png = [0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00];
+        let path = with_inferred_ext(Path::new("download/foo"), &png, None);
+        assert_eq!(path, Path::new("download/foo.png"));
+
+        let path = with_inferred_ext(Path::new("download/foo.bin"), &png, None);
+        assert_eq!(path, Path::new("download/foo.bin"));
+
+        let path = with_inferred_ext(Path::new("foo"), b"{}", Some("application/json"));
+        assert_eq!(path, Path::new("foo.json"));
+
+        let path = with_inferred_ext(Path::new("foo"), b"abc", None);
+        assert_eq!(path, Path::new("foo"));
     }
 }
@@ -0,0 +1,116 @@
+//! bucket 的图片样式，通过 `x-oss-process=style/<name>` 使用预设的图片处理参数
+
+use crate::{
+    bucket::{xml_escape, xml_unescape, Bucket},
+    error::OssError,
+};
+
+/// 图片样式，`content` 为图片处理参数，如 `image/resize,p_50`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageStyle {
+    name: String,
+    content: String,
+    create_time: Option<String>,
+    last_modify_time: Option<String>,
+}
+
+impl ImageStyle {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// 创建时间，如 `Wed, 20 May 2020 12:07:15 GMT`
+    pub fn create_time(&self) -> Option<&str> {
+        self.create_time.as_deref()
+    }
+
+    pub fn last_modify_time(&self) -> Option<&str> {
+        self.last_modify_time.as_deref()
+    }
+
+    pub(crate) fn from_xml(xml: &str) -> Result<Self, OssError> {
+        Ok(ImageStyle {
+            name: xml_unescape(Bucket::parse_item(xml, "Name").ok_or(OssError::InvalidStyle)?),
+            content: xml_unescape(
+                Bucket::parse_item(xml, "Content").ok_or(OssError::InvalidStyle)?,
+            ),
+            create_time: Bucket::parse_item(xml, "CreateTime").map(|t| t.to_owned()),
+            last_modify_time: Bucket::parse_item(xml, "LastModifyTime").map(|t| t.to_owned()),
+        })
+    }
+
+    pub(crate) fn parse_list_xml(xml: &str) -> Result<Vec<Self>, OssError> {
+        Bucket::parse_blocks(xml, "Style")
+            .into_iter()
+            .map(Self::from_xml)
+            .collect()
+    }
+}
+
+/// 创建样式的请求内容
+pub(crate) fn style_xml(content: &str) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?><Style><Content>{}</Content></Style>",
+        xml_escape(content)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{style_xml, ImageStyle};
+
+    #[test]
+    fn test_style_xml() {
+        assert!(style_xml("image/resize,p_50")
+            .ends_with("<Style><Content>image/resize,p_50</Content></Style>"));
+    }
+
+    #[test]
+    fn test_style_round_trip() {
+        // 图片处理参数中可能含有 `&` 等 XML 特殊字符
+        let content = "image/watermark,text_a&b<c>";
+        let xml = style_xml(content).replace("<Style>", "<Style><Name>mark</Name>");
+        assert_eq!(ImageStyle::from_xml(&xml).unwrap().content(), content);
+    }
+
+    #[test]
+    fn test_parse_xml() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <Style>
+          <Name>imagestyle</Name>
+          <Content>image/resize,p_50</Content>
+          <CreateTime>Wed, 20 May 2020 12:07:15 GMT</CreateTime>
+          <LastModifyTime>Wed, 21 May 2020 12:07:15 GMT</LastModifyTime>
+        </Style>"#;
+        let style = ImageStyle::from_xml(xml).unwrap();
+        assert_eq!(style.name(), "imagestyle");
+        assert_eq!(style.content(), "image/resize,p_50");
+        assert_eq!(style.create_time(), Some("Wed, 20 May 2020 12:07:15 GMT"));
+        assert_eq!(
+            style.last_modify_time(),
+            Some("Wed, 21 May 2020 12:07:15 GMT")
+        );
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <StyleList>
+          <Style>
+            <Name>imagestyle</Name>
+            <Content>image/resize,p_50</Content>
+          </Style>
+          <Style>
+            <Name>thumbnail</Name>
+            <Content>image/resize,w_100</Content>
+          </Style>
+        </StyleList>"#;
+        let list = ImageStyle::parse_list_xml(xml).unwrap();
+        let names: Vec<_> = list.iter().map(ImageStyle::name).collect();
+        assert_eq!(names, ["imagestyle", "thumbnail"]);
+        assert_eq!(list[1].content(), "image/resize,w_100");
+
+        assert!(ImageStyle::from_xml("<Style><Name>a</Name></Style>").is_err());
+    }
+}
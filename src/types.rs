@@ -1,9 +1,23 @@
-use std::{collections::HashMap, env::VarError};
-
+use std::{
+    collections::HashMap,
+    env::VarError,
+    fmt,
+    ops::{Range, RangeFrom, RangeInclusive, RangeToInclusive},
+};
+
+use chrono::{DateTime, Utc};
+#[cfg(feature = "client")]
+use percent_encoding::percent_decode_str;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+#[cfg(feature = "client")]
+use url::Url;
+
+use crate::error::OssError;
+#[cfg(feature = "client")]
 use crate::{bucket::Bucket, Object};
 
 mod endpoint;
-pub use endpoint::{EndPoint, EndPointKind};
+pub use endpoint::{EndPoint, EndPointKind, Network};
 use serde::{de::Visitor, Deserialize};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -24,6 +38,135 @@ impl Key {
     }
 }
 
+/// 内容的 MD5 值，经过 base64 编码，用于 `Content-MD5` header
+/// ```
+/// # use aliyun_oss_client::ContentMd5;
+/// assert_eq!(ContentMd5::from_bytes(b"aaa").as_str(), "R7zlx09Yn0hn29V+nKn4CA==");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentMd5(String);
+
+impl ContentMd5 {
+    pub fn from_bytes(content: &[u8]) -> ContentMd5 {
+        use base64::engine::general_purpose::STANDARD;
+        use base64::Engine;
+
+        ContentMd5(STANDARD.encode(md5::compute(content).0))
+    }
+
+    /// 使用已有的 base64 编码的 MD5 值，解码后不是 16 字节时返回 `InvalidContentMd5` 错误
+    /// ```
+    /// # use aliyun_oss_client::ContentMd5;
+    /// let md5 = ContentMd5::from_base64("R7zlx09Yn0hn29V+nKn4CA==").unwrap();
+    /// assert_eq!(md5, ContentMd5::from_bytes(b"aaa"));
+    /// assert!(ContentMd5::from_base64("R7zlx09Yn0hn29V+").is_err());
+    /// ```
+    pub fn from_base64<S: Into<String>>(md5: S) -> Result<ContentMd5, OssError> {
+        use base64::engine::general_purpose::STANDARD;
+        use base64::Engine;
+
+        let md5 = md5.into();
+        match STANDARD.decode(&md5) {
+            Ok(bytes) if bytes.len() == 16 => Ok(ContentMd5(md5)),
+            _ => Err(OssError::InvalidContentMd5),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for ContentMd5 {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+/// 下载时使用的字节范围（`Range` header），OSS 的字节范围两端都包含在内，
+/// 因此 `10..=20` 对应 `bytes=10-20`，`10..21` 也对应 `bytes=10-20`，
+/// `10..21` 这类半开区间可能为空，需要使用 `try_from` 转换
+/// ```
+/// # use aliyun_oss_client::types::ContentRange;
+/// assert_eq!(ContentRange::from(10..=20).to_string(), "bytes=10-20");
+/// assert_eq!(ContentRange::try_from(10..21).unwrap().to_string(), "bytes=10-20");
+/// assert!(ContentRange::try_from(10..10).is_err());
+/// assert_eq!(ContentRange::from(..=20).to_string(), "bytes=0-20");
+/// assert_eq!(ContentRange::from(10..).to_string(), "bytes=10-");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContentRange {
+    start: u64,
+    end: Option<u64>,
+}
+
+impl ContentRange {
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+
+    /// 结束位置（包含），到文件末尾时为 `None`
+    pub fn end(&self) -> Option<u64> {
+        self.end
+    }
+}
+
+impl From<RangeInclusive<u64>> for ContentRange {
+    fn from(range: RangeInclusive<u64>) -> Self {
+        ContentRange {
+            start: *range.start(),
+            end: Some(*range.end()),
+        }
+    }
+}
+
+impl From<RangeToInclusive<u64>> for ContentRange {
+    fn from(range: RangeToInclusive<u64>) -> Self {
+        ContentRange {
+            start: 0,
+            end: Some(range.end),
+        }
+    }
+}
+
+impl TryFrom<Range<u64>> for ContentRange {
+    type Error = OssError;
+
+    /// 范围为空或者起止位置颠倒时返回 [`OssError::InvalidRange`]
+    fn try_from(range: Range<u64>) -> Result<Self, Self::Error> {
+        if range.is_empty() {
+            return Err(OssError::InvalidRange);
+        }
+        Ok(ContentRange {
+            start: range.start,
+            end: Some(range.end - 1),
+        })
+    }
+}
+
+impl From<RangeFrom<u64>> for ContentRange {
+    fn from(range: RangeFrom<u64>) -> Self {
+        ContentRange {
+            start: range.start,
+            end: None,
+        }
+    }
+}
+
+impl fmt::Display for ContentRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.end {
+            Some(end) => write!(f, "bytes={}-{}", self.start, end),
+            None => write!(f, "bytes={}-", self.start),
+        }
+    }
+}
+
+/// 格式化为 HTTP header 中使用的时间格式，如 `Wed, 20 Mar 2024 08:00:00 GMT`
+pub(crate) fn to_gmt(time: &DateTime<Utc>) -> String {
+    time.format("%a, %d %b %Y %T GMT").to_string()
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Secret(String);
 
@@ -61,6 +204,7 @@ impl Secret {
     }
 }
 
+#[derive(Debug, Clone)]
 pub struct CanonicalizedResource(String);
 
 impl Default for CanonicalizedResource {
@@ -77,14 +221,44 @@ impl CanonicalizedResource {
         &self.0
     }
 
+    #[cfg(feature = "client")]
     pub fn from_bucket_info(bucket: &Bucket) -> CanonicalizedResource {
         Self(format!("/{}/?bucketInfo", bucket.as_str()))
     }
 
+    #[cfg(feature = "client")]
+    /// bucket 子资源的签名路径
+    /// ```
+    /// # use aliyun_oss_client::{types::CanonicalizedResource, Bucket, EndPoint};
+    /// let bucket = Bucket::new("foo", EndPoint::CN_QINGDAO);
+    /// let resource = CanonicalizedResource::from_bucket_query(&bucket, "policy");
+    /// assert_eq!(resource.as_str(), "/foo/?policy");
+    /// ```
+    pub fn from_bucket_query(bucket: &Bucket, query: &str) -> CanonicalizedResource {
+        Self(format!("/{}/?{}", bucket.as_str(), query))
+    }
+
+    #[cfg(feature = "client")]
     pub fn from_object(bucket: &Bucket, object: &Object) -> CanonicalizedResource {
         CanonicalizedResource::new(format!("/{}/{}", bucket.as_str(), object.get_path()))
     }
 
+    #[cfg(feature = "client")]
+    /// 对象指定版本的签名路径
+    pub fn from_object_version(
+        bucket: &Bucket,
+        object: &Object,
+        version_id: &str,
+    ) -> CanonicalizedResource {
+        CanonicalizedResource::new(format!(
+            "/{}/{}?versionId={}",
+            bucket.as_str(),
+            object.get_path(),
+            version_id
+        ))
+    }
+
+    #[cfg(feature = "client")]
     pub fn from_object_list(
         bucket: &Bucket,
         continuation_token: Option<&String>,
@@ -98,6 +272,40 @@ impl CanonicalizedResource {
             None => Self(format!("/{}/", bucket.as_str())),
         }
     }
+
+    #[cfg(feature = "client")]
+    /// 列举文件请求的签名路径，查询条件中只有 `continuation-token` 参与签名，
+    /// `fetch-owner`，`encoding-type`，`list-type` 等参数不参与签名
+    pub fn from_object_query(bucket: &Bucket, query: &ObjectQuery) -> CanonicalizedResource {
+        Self::from_object_list(bucket, query.get_next_token())
+    }
+
+    #[cfg(feature = "client")]
+    /// 从 OSS 的请求链接计算签名路径，路径会被解码，查询参数中只有签名的子资源会被保留，
+    /// 适用于自行构造请求。不是 OSS 域名（如自定义域名）时返回错误
+    /// ```
+    /// # use aliyun_oss_client::types::CanonicalizedResource;
+    /// let url = "https://foo.oss-cn-qingdao.aliyuncs.com/a%20b.txt?uploadId=x&partNumber=1&foo=bar"
+    ///     .parse()
+    ///     .unwrap();
+    /// let resource = CanonicalizedResource::from_url(&url).unwrap();
+    /// assert_eq!(resource.as_str(), "/foo/a b.txt?partNumber=1&uploadId=x");
+    /// ```
+    pub fn from_url(url: &Url) -> Result<CanonicalizedResource, OssError> {
+        let bucket = Bucket::from_url(url)?;
+        let path = percent_decode_str(url.path().trim_start_matches('/'))
+            .decode_utf8()
+            .map_err(|_| OssError::InvalidObjectPath(url.path().into()))?;
+        let mut query = ObjectQuery::new();
+        query.extend(url.query_pairs());
+
+        Ok(Self(format!(
+            "/{}/{}{}",
+            bucket.as_str(),
+            path,
+            query.to_signed_resource_suffix()
+        )))
+    }
 }
 
 /// 存储类型
@@ -154,6 +362,17 @@ impl StorageClass {
     }
 }
 
+impl AsRef<str> for StorageClass {
+    fn as_ref(&self) -> &str {
+        match self.kind {
+            StorageClassKind::Standard => "Standard",
+            StorageClassKind::IA => "IA",
+            StorageClassKind::Archive => "Archive",
+            StorageClassKind::ColdArchive => "ColdArchive",
+        }
+    }
+}
+
 struct StorageClassVisitor;
 
 impl<'de> Visitor<'de> for StorageClassVisitor {
@@ -179,9 +398,111 @@ impl<'de> Deserialize<'de> for StorageClass {
     }
 }
 
-#[derive(Debug, Default, Clone)]
+/// bucket 或 object 的拥有者
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Owner {
+    #[serde(rename = "ID")]
+    id: String,
+    #[serde(rename = "DisplayName")]
+    display_name: String,
+}
+
+impl Owner {
+    pub fn new<I: Into<String>, N: Into<String>>(id: I, display_name: N) -> Owner {
+        Owner {
+            id: id.into(),
+            display_name: display_name.into(),
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn display_name(&self) -> &str {
+        &self.display_name
+    }
+}
+
+/// OSS 签名时需要包含在 `CanonicalizedResource` 中的子资源
+pub const SIGNED_SUB_RESOURCES: &[&str] = &[
+    "acl",
+    "append",
+    "bucketInfo",
+    "callback",
+    "callback-var",
+    "cname",
+    "comp",
+    "continuation-token",
+    "cors",
+    "delete",
+    "encryption",
+    "endTime",
+    "img",
+    "inventory",
+    "inventoryId",
+    "lifecycle",
+    "live",
+    "location",
+    "logging",
+    "objectMeta",
+    "partNumber",
+    "policy",
+    "position",
+    "qos",
+    "referer",
+    "replication",
+    "replicationLocation",
+    "replicationProgress",
+    "requestPayment",
+    "response-cache-control",
+    "response-content-disposition",
+    "response-content-encoding",
+    "response-content-language",
+    "response-content-type",
+    "response-expires",
+    "restore",
+    "security-token",
+    "sequential",
+    "startTime",
+    "stat",
+    "status",
+    "style",
+    "styleName",
+    "symlink",
+    "tagging",
+    "transferAcceleration",
+    "uploadId",
+    "uploads",
+    "versionId",
+    "versioning",
+    "versions",
+    "vod",
+    "website",
+    "worm",
+    "wormExtend",
+    "wormId",
+    "x-oss-process",
+];
+
+/// 自定义查询参数中除 `-_.~` 以外的字符都需要编码
+const QUERY_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+/// 对查询参数进行 url 编码，如调用方传入的 `inventoryId`，`styleName` 等，
+/// 编码后的值同时用于请求地址和签名路径
+pub(crate) fn encode_query(value: &str) -> String {
+    utf8_percent_encode(value, QUERY_ENCODE_SET).to_string()
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct ObjectQuery {
     map: HashMap<String, String>,
+    /// 为 true 时不携带 `list-type=2`，即使用 V1 版本的列举接口
+    without_list_type: bool,
 }
 
 impl ObjectQuery {
@@ -192,22 +513,135 @@ impl ObjectQuery {
     pub const PREFIX: &'static str = "prefix";
     pub const ENCODING_TYPE: &'static str = "encoding-type";
     pub const FETCH_OWNER: &'static str = "fetch-owner";
+    pub const KEY_MARKER: &'static str = "key-marker";
+    pub const VERSION_ID_MARKER: &'static str = "version-id-marker";
+    pub const MARKER: &'static str = "marker";
     pub fn new() -> Self {
         Self {
             map: HashMap::new(),
+            without_list_type: false,
+        }
+    }
+
+    /// 不携带 `list-type=2`，使用 V1 版本的列举接口，此时翻页使用 `marker` 而不是
+    /// `continuation-token`，列举结果中的 `NextMarker` 会作为下一页的 token，
+    /// 由 [`insert_next_token`](Self::insert_next_token) 设置为 `marker`
+    pub fn without_list_type(&mut self) {
+        self.without_list_type = true;
+    }
+
+    /// 为 true 时携带 `encoding-type=url`，OSS 会对返回的文件路径和前缀进行 URL 编码，
+    /// 解析列举结果时会自动解码，适用于路径中含有 `&`，`=`，换行等字符的文件
+    pub fn url_encode(&mut self, enable: bool) {
+        if enable {
+            self.map.insert(Self::ENCODING_TYPE.into(), "url".into());
+        } else {
+            self.map.remove(Self::ENCODING_TYPE);
         }
     }
+
     pub fn insert<K: Into<String>, V: Into<String>>(&mut self, key: K, value: V) -> Option<String> {
         self.map.insert(key.into(), value.into())
     }
 
+    /// 插入 SDK 没有提供常量的查询参数（如 OSS 新增的参数），key 和 value 都会经过 url 编码，
+    /// 可以安全地携带任意字符
+    /// ```
+    /// # use aliyun_oss_client::types::ObjectQuery;
+    /// let mut query = ObjectQuery::new();
+    /// query.custom("x-new-flag", "a b&c");
+    /// assert_eq!(query.get("x-new-flag").unwrap(), "a%20b%26c");
+    /// ```
+    pub fn custom<K: AsRef<str>, V: AsRef<str>>(&mut self, key: K, value: V) -> Option<String> {
+        self.map
+            .insert(encode_query(key.as_ref()), encode_query(value.as_ref()))
+    }
+
+    pub fn get(&self, key: &str) -> Option<&String> {
+        self.map.get(key)
+    }
+
+    /// 合并另一个查询条件，相同的 key 以 `other` 中的值为准
+    /// ```
+    /// # use aliyun_oss_client::types::ObjectQuery;
+    /// let mut query = ObjectQuery::new();
+    /// query.insert(ObjectQuery::MAX_KEYS, "10");
+    /// query.insert(ObjectQuery::PREFIX, "foo/");
+    ///
+    /// let mut other = ObjectQuery::new();
+    /// other.insert(ObjectQuery::MAX_KEYS, "5");
+    /// other.insert(ObjectQuery::DELIMITER, "/");
+    ///
+    /// query.merge(other);
+    /// assert_eq!(query.get(ObjectQuery::MAX_KEYS).unwrap(), "5");
+    /// assert_eq!(query.get(ObjectQuery::PREFIX).unwrap(), "foo/");
+    /// assert_eq!(query.get(ObjectQuery::DELIMITER).unwrap(), "/");
+    /// ```
+    pub fn merge(&mut self, other: ObjectQuery) {
+        self.map.extend(other.map);
+    }
+
+    /// 需要参与签名的子资源（如 `acl`，`uploadId`，`partNumber`），按 key 排序，
+    /// 可用于自行拼接 [`CanonicalizedResource`]
+    pub fn signed_subresources(&self) -> impl Iterator<Item = (&str, &str)> {
+        let mut list: Vec<_> = self
+            .map
+            .iter()
+            .filter(|(k, _)| SIGNED_SUB_RESOURCES.contains(&k.as_str()))
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect();
+        list.sort();
+        list.into_iter()
+    }
+
+    /// 由 [`signed_subresources`](Self::signed_subresources) 拼接的签名路径后缀，
+    /// 值为空的子资源只保留 key，没有子资源时返回空字符串
+    /// ```
+    /// # use aliyun_oss_client::types::ObjectQuery;
+    /// let mut query = ObjectQuery::new();
+    /// query.insert("uploadId", "x");
+    /// query.insert("partNumber", "1");
+    /// assert_eq!(query.to_signed_resource_suffix(), "?partNumber=1&uploadId=x");
+    /// ```
+    pub fn to_signed_resource_suffix(&self) -> String {
+        let mut suffix = String::new();
+        for (key, value) in self.signed_subresources() {
+            suffix.push(if suffix.is_empty() { '?' } else { '&' });
+            suffix += key;
+            if !value.is_empty() {
+                suffix.push('=');
+                suffix += value;
+            }
+        }
+        suffix
+    }
+
     pub(crate) fn get_next_token(&self) -> Option<&String> {
         self.map.get(Self::CONTINUATION_TOKEN)
     }
 
     pub(crate) fn to_oss_query(&self) -> String {
         const LIST_TYPE2: &str = "list-type=2";
-        let mut query_str = String::from(LIST_TYPE2);
+        let mut query_str = if self.without_list_type {
+            String::new()
+        } else {
+            String::from(LIST_TYPE2)
+        };
+        for (key, value) in self.map.iter() {
+            if !query_str.is_empty() {
+                query_str += "&";
+            }
+            query_str += key;
+            query_str += "=";
+            query_str += value;
+        }
+        query_str
+    }
+
+    /// 列举对象版本时使用的查询字符串，`versions` 是签名的子资源
+    pub(crate) fn to_versions_query(&self) -> String {
+        const VERSIONS: &str = "versions";
+        let mut query_str = String::from(VERSIONS);
         for (key, value) in self.map.iter() {
             query_str += "&";
             query_str += key;
@@ -217,7 +651,235 @@ impl ObjectQuery {
         query_str
     }
 
+    /// 设置翻页 token，同时去掉 `start-after`，翻页时由 token 决定起始位置，
+    /// 继续携带 `start-after` 可能导致跳过或重复文件
+    ///
+    /// 使用 V1 版本的列举接口时，token 为上一页的 `NextMarker`，即文件路径，
+    /// 经过 url 编码后作为 `marker` 携带
+    /// ```
+    /// # use aliyun_oss_client::types::ObjectQuery;
+    /// let mut query = ObjectQuery::new();
+    /// query.without_list_type();
+    /// query.insert_next_token("a&b.txt".to_owned());
+    /// assert_eq!(query.get(ObjectQuery::MARKER).unwrap(), "a%26b.txt");
+    /// assert!(query.get(ObjectQuery::CONTINUATION_TOKEN).is_none());
+    /// ```
     pub fn insert_next_token(&mut self, token: String) -> Option<String> {
-        self.map.insert(Self::CONTINUATION_TOKEN.into(), token)
+        self.map.remove(Self::START_AFTER);
+        if self.without_list_type {
+            self.map.insert(Self::MARKER.into(), encode_query(&token))
+        } else {
+            self.map.insert(Self::CONTINUATION_TOKEN.into(), token)
+        }
+    }
+}
+
+/// 批量插入查询条件，相同的 key 以后插入的值为准
+/// ```
+/// # use aliyun_oss_client::types::ObjectQuery;
+/// let mut query = ObjectQuery::new();
+/// query.insert(ObjectQuery::MAX_KEYS, "10");
+/// query.extend([(ObjectQuery::MAX_KEYS, "5"), (ObjectQuery::PREFIX, "foo/")]);
+///
+/// assert_eq!(query.get(ObjectQuery::MAX_KEYS).unwrap(), "5");
+/// assert_eq!(query.get(ObjectQuery::PREFIX).unwrap(), "foo/");
+/// ```
+impl<K: Into<String>, V: Into<String>> Extend<(K, V)> for ObjectQuery {
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        self.map
+            .extend(iter.into_iter().map(|(k, v)| (k.into(), v.into())));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ContentMd5, ContentRange, ObjectQuery};
+
+    #[test]
+    fn test_content_range() {
+        assert_eq!(ContentRange::from(10..=20).to_string(), "bytes=10-20");
+        assert_eq!(ContentRange::from(..=20).to_string(), "bytes=0-20");
+        assert_eq!(
+            ContentRange::try_from(10..21).unwrap(),
+            ContentRange::from(10..=20)
+        );
+        assert_eq!(ContentRange::from(0..=0).to_string(), "bytes=0-0");
+        assert_eq!(
+            ContentRange::try_from(0..1).unwrap().to_string(),
+            "bytes=0-0"
+        );
+
+        let range = ContentRange::from(5..);
+        assert_eq!(range.to_string(), "bytes=5-");
+        assert_eq!((range.start(), range.end()), (5, None));
+    }
+
+    #[test]
+    fn test_content_range_rejects_empty() {
+        use crate::error::OssError;
+
+        assert!(matches!(
+            ContentRange::try_from(0..0),
+            Err(OssError::InvalidRange)
+        ));
+        assert!(matches!(
+            ContentRange::try_from(10..10),
+            Err(OssError::InvalidRange)
+        ));
+        #[allow(clippy::reversed_empty_ranges)]
+        let inverted = 10..5;
+        assert!(matches!(
+            ContentRange::try_from(inverted),
+            Err(OssError::InvalidRange)
+        ));
+    }
+
+    #[test]
+    fn test_custom_query() {
+        let mut query = ObjectQuery::new();
+        query.insert(ObjectQuery::PREFIX, "foo/");
+        query.custom("archive-direct-read", "true");
+        query.custom("x-new", "1=2");
+
+        let oss_query = query.to_oss_query();
+        let mut pairs: Vec<_> = oss_query.split('&').collect();
+        pairs.sort();
+        assert_eq!(
+            pairs,
+            [
+                "archive-direct-read=true",
+                "list-type=2",
+                "prefix=foo/",
+                "x-new=1%3D2"
+            ]
+        );
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_object_query_resource() {
+        use super::{CanonicalizedResource, EndPoint};
+        use crate::bucket::Bucket;
+
+        let bucket = Bucket::new("bucket", EndPoint::CN_QINGDAO);
+        let mut query = ObjectQuery::new();
+        query.insert(ObjectQuery::FETCH_OWNER, "true");
+        query.insert("list-type", "2");
+        query.url_encode(true);
+        query.insert(ObjectQuery::PREFIX, "foo/");
+        assert_eq!(
+            CanonicalizedResource::from_object_query(&bucket, &query).as_str(),
+            "/bucket/"
+        );
+
+        query.insert_next_token("CgJiYw--".to_owned());
+        assert_eq!(
+            CanonicalizedResource::from_object_query(&bucket, &query).as_str(),
+            "/bucket/?continuation-token=CgJiYw--"
+        );
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn test_resource_from_url() {
+        use super::{CanonicalizedResource, EndPoint};
+        use crate::{Bucket, Object};
+
+        let bucket = Bucket::new("foo", EndPoint::CN_QINGDAO);
+        let object = Object::new("path/foo bar.txt");
+
+        let url = object.to_url(&bucket);
+        assert_eq!(
+            CanonicalizedResource::from_url(&url).unwrap().as_str(),
+            CanonicalizedResource::from_object(&bucket, &object).as_str()
+        );
+
+        let mut url = object.to_url(&bucket);
+        url.set_query(Some("versionId=CAEQ&response-content-type=text"));
+        assert_eq!(
+            CanonicalizedResource::from_url(&url).unwrap().as_str(),
+            "/foo/path/foo bar.txt?response-content-type=text&versionId=CAEQ"
+        );
+
+        let url = bucket.to_url();
+        assert_eq!(
+            CanonicalizedResource::from_url(&url).unwrap().as_str(),
+            "/foo/"
+        );
+
+        let url = "https://static.example.com/a.txt".parse().unwrap();
+        assert!(CanonicalizedResource::from_url(&url).is_err());
+    }
+
+    #[test]
+    fn test_signed_subresources() {
+        let mut query = ObjectQuery::new();
+        query.insert("uploadId", "0004B9");
+        query.insert(ObjectQuery::PREFIX, "foo/");
+        query.insert("partNumber", "1");
+        query.insert(ObjectQuery::MAX_KEYS, "5");
+        query.insert("acl", "");
+
+        let list: Vec<_> = query.signed_subresources().collect();
+        assert_eq!(
+            list,
+            [("acl", ""), ("partNumber", "1"), ("uploadId", "0004B9")]
+        );
+        assert_eq!(
+            query.to_signed_resource_suffix(),
+            "?acl&partNumber=1&uploadId=0004B9"
+        );
+
+        let mut query = ObjectQuery::new();
+        assert_eq!(query.to_signed_resource_suffix(), "");
+        query.insert(ObjectQuery::PREFIX, "foo/");
+        assert_eq!(query.to_signed_resource_suffix(), "");
+    }
+
+    #[test]
+    fn test_content_md5() {
+        assert_eq!(
+            ContentMd5::from_bytes(b"aaa").as_str(),
+            "R7zlx09Yn0hn29V+nKn4CA=="
+        );
+        assert_eq!(
+            ContentMd5::from_bytes(b"<Delete><Quiet>true</Quiet></Delete>").as_ref(),
+            "l/kaH4kX+6APpvyDt6+W2w=="
+        );
+        assert_eq!(ContentMd5::from_bytes(b""), ContentMd5::from_bytes(b""));
+    }
+
+    #[test]
+    fn test_content_md5_from_base64() {
+        let md5 = ContentMd5::from_base64("l/kaH4kX+6APpvyDt6+W2w==").unwrap();
+        assert_eq!(md5.as_str(), "l/kaH4kX+6APpvyDt6+W2w==");
+
+        assert!(ContentMd5::from_base64("not base64!").is_err());
+        assert!(ContentMd5::from_base64("YWFh").is_err());
+        assert!(ContentMd5::from_base64("").is_err());
+    }
+
+    #[test]
+    fn test_to_gmt() {
+        use super::to_gmt;
+        use chrono::{TimeZone, Utc};
+
+        let time = Utc.with_ymd_and_hms(2024, 3, 20, 8, 0, 0).unwrap();
+        assert_eq!(to_gmt(&time), "Wed, 20 Mar 2024 08:00:00 GMT");
+    }
+
+    #[test]
+    fn test_without_list_type() {
+        let mut query = ObjectQuery::new();
+        query.insert(ObjectQuery::PREFIX, "abc/");
+        assert_eq!(query.to_oss_query(), "list-type=2&prefix=abc/");
+
+        query.without_list_type();
+        assert_eq!(query.to_oss_query(), "prefix=abc/");
+
+        assert_eq!(ObjectQuery::new().to_oss_query(), "list-type=2");
+        let mut query = ObjectQuery::new();
+        query.without_list_type();
+        assert_eq!(query.to_oss_query(), "");
     }
 }
@@ -1,6 +1,8 @@
 use crate::error::OssError;
 
-use serde::{de::Visitor, Deserialize};
+use std::str::FromStr;
+
+use serde::{de::Visitor, Deserialize, Serialize};
 use url::Url;
 
 /// # OSS 的可用区
@@ -11,10 +13,29 @@ pub struct EndPoint {
     pub(crate) kind: EndPointKind,
     /// default false
     pub(crate) is_internal: bool,
+    pub(crate) network: Network,
+}
+
+/// endpoint 的网络类型，决定请求使用的域名，签名使用的 region 不受影响
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Network {
+    /// 默认的 IPv4 域名，如 `oss-cn-hangzhou.aliyuncs.com`
+    #[default]
+    Ipv4,
+    /// 同时支持 IPv4 和 IPv6 的双栈域名，如 `cn-hangzhou.oss.aliyuncs.com`
+    DualStack,
+    /// 全球传输加速域名 `oss-accelerate.aliyuncs.com`
+    Accelerate,
+    /// 非中国内地传输加速域名 `oss-accelerate-overseas.aliyuncs.com`
+    AccelerateOverseas,
 }
 
 const OSS_INTERNAL: &str = "-internal";
 const OSS_DOMAIN_MAIN: &str = ".aliyuncs.com";
+const OSS_DUAL_STACK_DOMAIN: &str = ".oss.aliyuncs.com";
+const OSS_ACCELERATE_HOST: &str = "oss-accelerate.aliyuncs.com";
+const OSS_ACCELERATE_OVERSEAS_HOST: &str = "oss-accelerate-overseas.aliyuncs.com";
 
 const HANGZHOU: &str = "cn-hangzhou";
 const SHANGHAI: &str = "cn-shanghai";
@@ -40,6 +61,7 @@ impl EndPoint {
     pub const CN_HANGZHOU: Self = Self {
         kind: EndPointKind::CnHangzhou,
         is_internal: false,
+        network: Network::Ipv4,
     };
     /// 杭州
     pub const HANGZHOU: Self = Self::CN_HANGZHOU;
@@ -48,6 +70,7 @@ impl EndPoint {
     pub const CN_SHANGHAI: Self = Self {
         kind: EndPointKind::CnShanghai,
         is_internal: false,
+        network: Network::Ipv4,
     };
     /// 上海
     pub const SHANGHAI: Self = Self::CN_SHANGHAI;
@@ -56,6 +79,7 @@ impl EndPoint {
     pub const CN_QINGDAO: Self = Self {
         kind: EndPointKind::CnQingdao,
         is_internal: false,
+        network: Network::Ipv4,
     };
     /// 青岛
     pub const QINGDAO: Self = Self::CN_QINGDAO;
@@ -64,6 +88,7 @@ impl EndPoint {
     pub const CN_BEIJING: Self = Self {
         kind: EndPointKind::CnBeijing,
         is_internal: false,
+        network: Network::Ipv4,
     };
     /// 北京
     pub const BEIJING: Self = Self::CN_BEIJING;
@@ -74,12 +99,14 @@ impl EndPoint {
     pub const ZHANGJIAKOU: Self = Self {
         kind: EndPointKind::CnZhangjiakou,
         is_internal: false,
+        network: Network::Ipv4,
     };
 
     /// 香港
     pub const CN_HONGKONG: Self = Self {
         kind: EndPointKind::CnHongkong,
         is_internal: false,
+        network: Network::Ipv4,
     };
     /// 香港
     pub const HONGKONG: Self = Self::CN_HONGKONG;
@@ -88,6 +115,7 @@ impl EndPoint {
     pub const CN_SHENZHEN: Self = Self {
         kind: EndPointKind::CnShenzhen,
         is_internal: false,
+        network: Network::Ipv4,
     };
     /// 深圳
     pub const SHENZHEN: Self = Self::CN_SHENZHEN;
@@ -96,22 +124,29 @@ impl EndPoint {
     pub const US_WEST_1: Self = Self {
         kind: EndPointKind::UsWest1,
         is_internal: false,
+        network: Network::Ipv4,
     };
 
     /// UsEast1
     pub const US_EAST_1: Self = Self {
         kind: EndPointKind::UsEast1,
         is_internal: false,
+        network: Network::Ipv4,
     };
 
     /// ApSouthEast1
     pub const AP_SOUTH_EAST_1: Self = Self {
         kind: EndPointKind::ApSouthEast1,
         is_internal: false,
+        network: Network::Ipv4,
     };
 
     pub fn init(kind: EndPointKind, is_internal: bool) -> Self {
-        Self { kind, is_internal }
+        Self {
+            kind,
+            is_internal,
+            network: Network::Ipv4,
+        }
     }
 
     /// 初始化 endpoint enum
@@ -177,7 +212,95 @@ impl EndPoint {
             Ok(Other(url.to_owned()))
         };
 
-        kind.map(|kind| Self { kind, is_internal })
+        kind.map(|kind| Self::init(kind, is_internal))
+    }
+
+    /// 根据 region id 初始化 endpoint，如 `cn-hangzhou`，`us-west-1`，不接受内网的形式
+    /// ```rust
+    /// # use aliyun_oss_client::types::EndPoint;
+    /// for id in ["cn-hangzhou", "cn-shanghai", "us-west-1", "ap-southeast-1", "cn-nanjing"] {
+    ///     let endpoint = EndPoint::from_region_id(id).unwrap();
+    ///     assert_eq!(endpoint.region_id(), id);
+    ///     assert!(!endpoint.is_internal());
+    /// }
+    /// assert_eq!(EndPoint::from_region_id("us-west-1").unwrap(), EndPoint::US_WEST_1);
+    ///
+    /// assert!(EndPoint::from_region_id("").is_err());
+    /// assert!(EndPoint::from_region_id("cn-nanjing-internal").is_err());
+    /// assert!(EndPoint::from_region_id("oss-cn-nanjing").is_err());
+    /// ```
+    pub fn from_region_id(region_id: &str) -> Result<Self, OssError> {
+        let endpoint = Self::new(region_id)?;
+        if endpoint.is_internal {
+            return Err(OssError::InvalidEndPoint);
+        }
+
+        Ok(endpoint)
+    }
+
+    /// 从完整的 url 中解析 endpoint，url 可以带有 bucket 和文件路径，不是 OSS 的域名时返回错误
+    /// ```rust
+    /// # use aliyun_oss_client::types::EndPoint;
+    /// # use url::Url;
+    /// let url = Url::parse("https://bucket.oss-cn-hangzhou.aliyuncs.com/path/a.txt").unwrap();
+    /// let endpoint = EndPoint::from_url(&url).unwrap();
+    /// assert_eq!(endpoint, EndPoint::CN_HANGZHOU);
+    /// assert!(!endpoint.is_internal());
+    ///
+    /// let url = Url::parse("http://bucket.oss-cn-nanjing-internal.aliyuncs.com").unwrap();
+    /// let endpoint = EndPoint::from_url(&url).unwrap();
+    /// assert_eq!(endpoint.region_id(), "cn-nanjing");
+    /// assert!(endpoint.is_internal());
+    ///
+    /// let url = Url::parse("https://oss-cn-shanghai.aliyuncs.com").unwrap();
+    /// assert_eq!(EndPoint::from_url(&url).unwrap(), EndPoint::CN_SHANGHAI);
+    ///
+    /// let url = Url::parse("https://bucket.cn-hangzhou.oss.aliyuncs.com").unwrap();
+    /// assert_eq!(EndPoint::from_url(&url).unwrap().region_id(), "cn-hangzhou");
+    ///
+    /// let url = Url::parse("https://static.example.com/a.txt").unwrap();
+    /// assert!(EndPoint::from_url(&url).is_err());
+    /// ```
+    pub fn from_url(url: &Url) -> Result<Self, OssError> {
+        let host = url.host_str().ok_or(OssError::InvalidEndPoint)?;
+        if !host.ends_with(OSS_DOMAIN_MAIN) {
+            return Err(OssError::InvalidEndPoint);
+        }
+        let host = match Self::split_bucket_host(host) {
+            Some((_, rest)) => rest,
+            None => host,
+        };
+
+        host.parse()
+    }
+
+    /// bucket 的域名比 endpoint 的域名多一级 bucket 名称，拆分出 bucket 名称和 endpoint 的域名，
+    /// 不是 bucket 的域名（如只有 endpoint）时返回 `None`
+    pub(crate) fn split_bucket_host(host: &str) -> Option<(&str, &str)> {
+        const OSS_DOMAIN_PREFIX: &str = "oss-";
+
+        host.split_once('.').filter(|(_, rest)| {
+            rest.starts_with(OSS_DOMAIN_PREFIX) || rest.ends_with(OSS_DUAL_STACK_DOMAIN)
+        })
+    }
+
+    /// 返回 region id，如 `cn-hangzhou`，可用于签名的 scope 以及判断是否为同一区域
+    pub fn region_id(&self) -> &str {
+        self.as_ref()
+    }
+
+    /// 是否为同一区域，不区分内网和网络类型，`==` 会同时比较这两项
+    /// ```
+    /// # use aliyun_oss_client::types::EndPoint;
+    /// let mut internal = EndPoint::CN_SHANGHAI;
+    /// internal.set_internal(true);
+    ///
+    /// assert!(EndPoint::CN_SHANGHAI.same_region(&internal));
+    /// assert_ne!(EndPoint::CN_SHANGHAI, internal);
+    /// assert!(!EndPoint::CN_SHANGHAI.same_region(&EndPoint::CN_HANGZHOU));
+    /// ```
+    pub fn same_region(&self, other: &EndPoint) -> bool {
+        self.region_id() == other.region_id()
     }
 
     /// 反序列化一个 endpoint 的域名
@@ -223,7 +346,7 @@ impl EndPoint {
             Other(part_url)
         };
 
-        Self { kind, is_internal }
+        Self::init(kind, is_internal)
     }
 
     /// use env init Endpoint
@@ -231,19 +354,31 @@ impl EndPoint {
         let endpoint = std::env::var("ALIYUN_ENDPOINT").map_err(|_| OssError::InvalidEndPoint)?;
         let mut endpoint = EndPoint::new(&endpoint)?;
 
-        if let Ok(is_internal) = std::env::var("ALIYUN_OSS_INTERNAL") {
-            if is_internal == "true"
-                || is_internal == "1"
-                || is_internal == "yes"
-                || is_internal == "Y"
-            {
-                endpoint.set_internal(true);
-            }
+        if env_internal() == Some(true) {
+            endpoint.set_internal(true);
         }
 
         Ok(endpoint)
     }
 
+    /// 根据运行环境自动选择是否使用内网 endpoint
+    ///
+    /// 设置了 `ALIYUN_OSS_INTERNAL` 环境变量时以它为准，否则检查当前机器是否为阿里云 ECS
+    /// ```
+    /// # use aliyun_oss_client::types::EndPoint;
+    /// std::env::set_var("ALIYUN_OSS_INTERNAL", "true");
+    /// let endpoint = EndPoint::CN_SHANGHAI.auto_internal();
+    /// assert!(endpoint.is_internal());
+    /// assert_eq!(endpoint.to_host(), "oss-cn-shanghai-internal.aliyuncs.com");
+    ///
+    /// std::env::set_var("ALIYUN_OSS_INTERNAL", "false");
+    /// assert!(!EndPoint::CN_SHANGHAI.auto_internal().is_internal());
+    /// ```
+    pub fn auto_internal(mut self) -> Self {
+        self.is_internal = env_internal().unwrap_or_else(is_ecs);
+        self
+    }
+
     /// # 调整 API 指向是否为内网
     ///
     /// 当在 Aliyun ECS 上执行时，设为 true 会更高效，默认是 false
@@ -256,10 +391,32 @@ impl EndPoint {
         self.is_internal
     }
 
+    /// 设置使用的网络类型，如双栈或传输加速域名，内网设置只对 [`Network::Ipv4`] 生效
+    /// ```
+    /// # use aliyun_oss_client::types::{EndPoint, Network};
+    /// let mut endpoint = EndPoint::CN_HANGZHOU;
+    /// endpoint.set_network(Network::DualStack);
+    /// assert_eq!(endpoint.to_host(), "cn-hangzhou.oss.aliyuncs.com");
+    ///
+    /// endpoint.set_network(Network::Accelerate);
+    /// assert_eq!(endpoint.to_host(), "oss-accelerate.aliyuncs.com");
+    /// assert_eq!(endpoint.region_id(), "cn-hangzhou");
+    ///
+    /// endpoint.set_network(Network::AccelerateOverseas);
+    /// assert_eq!(endpoint.to_host(), "oss-accelerate-overseas.aliyuncs.com");
+    /// ```
+    pub fn set_network(&mut self, network: Network) {
+        self.network = network;
+    }
+
+    pub fn network(&self) -> Network {
+        self.network
+    }
+
     /// 转化成 Url
     /// ```
     /// # use aliyun_oss_client::types::EndPoint;
-    /// use reqwest::Url;
+    /// use url::Url;
     /// let mut endpoint = EndPoint::CN_SHANGHAI;;
     /// assert_eq!(
     ///     endpoint.to_url(),
@@ -273,13 +430,27 @@ impl EndPoint {
     /// );
     /// ```
     pub fn to_url(&self) -> Url {
-        let url = format!("https://{}", self.host());
+        let url = format!("https://{}", self.to_host());
         Url::parse(&url).unwrap_or_else(|_| panic!("covert to url failed, endpoint: {}", url))
     }
 
-    /// 获取 host
-    pub(crate) fn host(&self) -> String {
+    /// 获取完整的 host
+    /// ```
+    /// # use aliyun_oss_client::types::EndPoint;
+    /// let mut endpoint = EndPoint::CN_HANGZHOU;
+    /// assert_eq!(endpoint.to_host(), "oss-cn-hangzhou.aliyuncs.com");
+    ///
+    /// endpoint.set_internal(true);
+    /// assert_eq!(endpoint.to_host(), "oss-cn-hangzhou-internal.aliyuncs.com");
+    /// ```
+    pub fn to_host(&self) -> String {
         const OSS_DOMAIN_PREFIX: &str = "oss-";
+        match self.network {
+            Network::Ipv4 => {}
+            Network::DualStack => return format!("{}{}", self.as_ref(), OSS_DUAL_STACK_DOMAIN),
+            Network::Accelerate => return OSS_ACCELERATE_HOST.to_owned(),
+            Network::AccelerateOverseas => return OSS_ACCELERATE_OVERSEAS_HOST.to_owned(),
+        }
         let mut host = String::from(OSS_DOMAIN_PREFIX);
         host.push_str(self.as_ref());
 
@@ -336,6 +507,107 @@ impl AsRef<str> for EndPoint {
     }
 }
 
+/// 显示为 region id，如 `cn-hangzhou`，内网时为 `cn-hangzhou-internal`，
+/// 完整的 host 请使用 [`EndPoint::to_host`]
+/// ```
+/// # use aliyun_oss_client::types::EndPoint;
+/// let mut endpoint = EndPoint::CN_HANGZHOU;
+/// assert_eq!(endpoint.to_string(), "cn-hangzhou");
+///
+/// endpoint.set_internal(true);
+/// assert_eq!(endpoint.to_string(), "cn-hangzhou-internal");
+/// ```
+impl fmt::Display for EndPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_ref())?;
+        if self.is_internal {
+            f.write_str(OSS_INTERNAL)?;
+        }
+        Ok(())
+    }
+}
+
+/// 读取 `ALIYUN_OSS_INTERNAL` 环境变量，没有设置时返回 `None`
+fn env_internal() -> Option<bool> {
+    let is_internal = std::env::var("ALIYUN_OSS_INTERNAL").ok()?;
+    Some(matches!(is_internal.as_str(), "true" | "1" | "yes" | "Y"))
+}
+
+/// 通过 DMI 信息判断当前机器是否为阿里云 ECS
+fn is_ecs() -> bool {
+    std::fs::read_to_string("/sys/class/dmi/id/product_name")
+        .map(|name| name.starts_with("Alibaba Cloud ECS"))
+        .unwrap_or(false)
+}
+
+/// 同时支持 region id 和 host 两种形式
+/// ```
+/// # use aliyun_oss_client::types::EndPoint;
+/// let endpoint: EndPoint = "cn-hangzhou".parse().unwrap();
+/// assert_eq!(endpoint, EndPoint::CN_HANGZHOU);
+///
+/// let endpoint: EndPoint = "oss-cn-hangzhou.aliyuncs.com".parse().unwrap();
+/// assert_eq!(endpoint, EndPoint::CN_HANGZHOU);
+///
+/// let endpoint: EndPoint = "https://oss-cn-nanjing-internal.aliyuncs.com".parse().unwrap();
+/// assert_eq!(endpoint.to_string(), "cn-nanjing-internal");
+///
+/// assert!("oss-cn-hangzhou.example.com".parse::<EndPoint>().is_err());
+/// ```
+///
+/// 双栈域名会识别为 [`Network::DualStack`]，传输加速域名不包含 region，无法解析
+/// ```
+/// # use aliyun_oss_client::types::{EndPoint, Network};
+/// let endpoint: EndPoint = "cn-hangzhou.oss.aliyuncs.com".parse().unwrap();
+/// assert_eq!(endpoint.region_id(), "cn-hangzhou");
+/// assert_eq!(endpoint.network(), Network::DualStack);
+///
+/// assert!("oss-accelerate.aliyuncs.com".parse::<EndPoint>().is_err());
+/// assert!("https://oss-accelerate-overseas.aliyuncs.com".parse::<EndPoint>().is_err());
+/// ```
+impl FromStr for EndPoint {
+    type Err = OssError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const OSS_DOMAIN_PREFIX: &str = "oss-";
+
+        let host = s
+            .trim_start_matches("https://")
+            .trim_start_matches("http://")
+            .trim_end_matches('/');
+        if !host.contains('.') {
+            return Self::new(host);
+        }
+        if host == OSS_ACCELERATE_HOST || host == OSS_ACCELERATE_OVERSEAS_HOST {
+            return Err(OssError::InvalidEndPoint);
+        }
+        if let Some(region) = host.strip_suffix(OSS_DUAL_STACK_DOMAIN) {
+            let mut endpoint = Self::new(region)?;
+            endpoint.set_network(Network::DualStack);
+            return Ok(endpoint);
+        }
+
+        host.strip_suffix(OSS_DOMAIN_MAIN)
+            .and_then(|host| host.strip_prefix(OSS_DOMAIN_PREFIX))
+            .ok_or(OssError::InvalidEndPoint)
+            .and_then(Self::new)
+    }
+}
+
+/// 序列化为 region id 的形式，与 `Display` 一致
+/// ```
+/// # use aliyun_oss_client::types::EndPoint;
+/// assert_eq!(serde_json::to_string(&EndPoint::CN_SHANGHAI).unwrap(), "\"cn-shanghai\"");
+/// ```
+impl Serialize for EndPoint {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
 /// # OSS 的可用区种类 enum
 #[derive(Clone, Debug, PartialEq, Eq, Default, Hash)]
 #[non_exhaustive]
@@ -0,0 +1,158 @@
+//! bucket 的合规保留策略（WORM），锁定后保护期内的文件不能被删除和修改
+
+use std::str::FromStr;
+
+use reqwest::header::HeaderMap;
+
+use crate::{bucket::Bucket, error::OssError};
+
+/// 创建策略时，响应中返回策略 ID 的 header
+const WORM_ID: &str = "x-oss-worm-id";
+
+/// bucket 的合规保留策略
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WormConfiguration {
+    id: String,
+    state: WormState,
+    retention_days: u32,
+    creation_date: Option<String>,
+}
+
+impl WormConfiguration {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn state(&self) -> WormState {
+        self.state
+    }
+
+    /// 文件的保护天数
+    pub fn retention_days(&self) -> u32 {
+        self.retention_days
+    }
+
+    /// 策略的创建时间，如 `2020-10-15T15:50:32`
+    pub fn creation_date(&self) -> Option<&str> {
+        self.creation_date.as_deref()
+    }
+
+    pub(crate) fn from_xml(xml: &str) -> Result<Self, OssError> {
+        Ok(WormConfiguration {
+            id: Bucket::parse_item(xml, "WormId")
+                .ok_or(OssError::InvalidWorm)?
+                .to_owned(),
+            state: Bucket::parse_item(xml, "State")
+                .ok_or(OssError::InvalidWorm)?
+                .parse()?,
+            retention_days: Bucket::parse_item(xml, "RetentionPeriodInDays")
+                .ok_or(OssError::InvalidWorm)?
+                .parse()?,
+            creation_date: Bucket::parse_item(xml, "CreationDate").map(|d| d.to_owned()),
+        })
+    }
+
+    /// 创建策略的请求内容
+    pub(crate) fn initiate_xml(days: u32) -> String {
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?><InitiateWormConfiguration>\
+            <RetentionPeriodInDays>{}</RetentionPeriodInDays></InitiateWormConfiguration>",
+            days
+        )
+    }
+
+    /// 延长保护天数的请求内容
+    pub(crate) fn extend_xml(days: u32) -> String {
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?><ExtendWormConfiguration>\
+            <RetentionPeriodInDays>{}</RetentionPeriodInDays></ExtendWormConfiguration>",
+            days
+        )
+    }
+
+    /// 从创建策略的响应 header 中获取策略 ID
+    pub(crate) fn id_from_headers(headers: &HeaderMap) -> Result<String, OssError> {
+        Ok(headers
+            .get(WORM_ID)
+            .ok_or(OssError::InvalidWorm)?
+            .to_str()?
+            .to_owned())
+    }
+}
+
+/// 合规保留策略的状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WormState {
+    /// 创建后未锁定，24 小时内可以锁定或删除
+    InProgress,
+    /// 已锁定，不能删除，只能延长保护天数
+    Locked,
+}
+
+impl FromStr for WormState {
+    type Err = OssError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "InProgress" => Ok(WormState::InProgress),
+            "Locked" => Ok(WormState::Locked),
+            _ => Err(OssError::InvalidWorm),
+        }
+    }
+}
+
+impl AsRef<str> for WormState {
+    fn as_ref(&self) -> &str {
+        match self {
+            WormState::InProgress => "InProgress",
+            WormState::Locked => "Locked",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::HeaderMap;
+
+    use super::{WormConfiguration, WormState};
+
+    #[test]
+    fn test_from_xml() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <WormConfiguration>
+          <WormId>1666E2CFB2B3418****</WormId>
+          <State>Locked</State>
+          <RetentionPeriodInDays>1</RetentionPeriodInDays>
+          <CreationDate>2020-10-15T15:50:32</CreationDate>
+        </WormConfiguration>"#;
+
+        let config = WormConfiguration::from_xml(xml).unwrap();
+        assert_eq!(config.id(), "1666E2CFB2B3418****");
+        assert_eq!(config.state(), WormState::Locked);
+        assert_eq!(config.retention_days(), 1);
+        assert_eq!(config.creation_date(), Some("2020-10-15T15:50:32"));
+
+        assert!(WormConfiguration::from_xml("<WormConfiguration></WormConfiguration>").is_err());
+    }
+
+    #[test]
+    fn test_id_from_headers() {
+        let mut headers = HeaderMap::new();
+        assert!(WormConfiguration::id_from_headers(&headers).is_err());
+
+        headers.insert("x-oss-worm-id", "1666E2CFB2B3418****".parse().unwrap());
+        assert_eq!(
+            WormConfiguration::id_from_headers(&headers).unwrap(),
+            "1666E2CFB2B3418****"
+        );
+    }
+
+    #[test]
+    fn test_request_xml() {
+        assert!(WormConfiguration::initiate_xml(3).contains(
+            "<InitiateWormConfiguration><RetentionPeriodInDays>3</RetentionPeriodInDays>"
+        ));
+        assert!(WormConfiguration::extend_xml(5)
+            .contains("<ExtendWormConfiguration><RetentionPeriodInDays>5</RetentionPeriodInDays>"));
+    }
+}